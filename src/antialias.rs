@@ -0,0 +1,204 @@
+use crate::framebuffer::{resolve_average, Framebuffer};
+
+/// Lado del bloque de píxeles que se dibuja por cada píxel final con SSAA.
+pub const SSAA_FACTOR: usize = 2;
+
+/// Umbral de contraste relativo por debajo del cual FXAA no toca un píxel.
+const FXAA_EDGE_THRESHOLD: f32 = 0.125;
+/// Contraste mínimo absoluto; evita suavizar el ruido de las zonas oscuras.
+const FXAA_EDGE_THRESHOLD_MIN: f32 = 0.0312;
+/// Cuánto se suavizan los detalles más finos que un píxel.
+const FXAA_SUBPIXEL_QUALITY: f32 = 0.75;
+/// Píxeles que se recorren a cada lado buscando el final de un borde.
+const FXAA_SEARCH_STEPS: i32 = 12;
+
+/// Cómo se suavizan los bordes escalonados de los triángulos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AntiAliasing {
+    /// Una muestra en el centro de cada píxel.
+    Off,
+    /// Cobertura y profundidad en 4 muestras por píxel; el shader corre una vez.
+    Msaa,
+    /// Toda la escena a `SSAA_FACTOR` veces la resolución, reducida al final.
+    Ssaa,
+    /// Detecta bordes en la imagen final y los difumina; casi gratis, pero más borroso.
+    Fxaa,
+}
+
+impl AntiAliasing {
+    /// Todos los modos, en el orden en que se recorren.
+    pub fn all() -> [AntiAliasing; 4] {
+        [AntiAliasing::Off, AntiAliasing::Msaa, AntiAliasing::Ssaa, AntiAliasing::Fxaa]
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            AntiAliasing::Off => AntiAliasing::Msaa,
+            AntiAliasing::Msaa => AntiAliasing::Ssaa,
+            AntiAliasing::Ssaa => AntiAliasing::Fxaa,
+            AntiAliasing::Fxaa => AntiAliasing::Off,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AntiAliasing::Off => "Sin AA",
+            AntiAliasing::Msaa => "MSAA 4x",
+            AntiAliasing::Ssaa => "SSAA 2x2",
+            AntiAliasing::Fxaa => "FXAA",
+        }
+    }
+
+    /// Modo por su nombre, sin importar mayúsculas: `off`, `msaa`, `ssaa` o `fxaa`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "off" | "no" => Some(AntiAliasing::Off),
+            "msaa" => Some(AntiAliasing::Msaa),
+            "ssaa" => Some(AntiAliasing::Ssaa),
+            "fxaa" => Some(AntiAliasing::Fxaa),
+            _ => None,
+        }
+    }
+
+    /// Muestras por píxel que necesita el framebuffer donde se dibuja.
+    pub fn samples(self) -> usize {
+        match self {
+            AntiAliasing::Msaa => 4,
+            _ => 1,
+        }
+    }
+}
+
+/// Reduce `source`, de `factor` veces el tamaño de `target`, promediando cada bloque de
+/// `factor` x `factor` píxeles. La profundidad es la más cercana del bloque y el
+/// sobredibujado el mayor.
+pub fn downsample(source: &Framebuffer, target: &mut Framebuffer, factor: usize) {
+    let mut block = Vec::with_capacity(factor * factor);
+    for y in 0..target.height {
+        for x in 0..target.width {
+            block.clear();
+            let mut depth = f32::INFINITY;
+            let mut overdraw = 0;
+            for sy in y * factor..((y + 1) * factor).min(source.height) {
+                for sx in x * factor..((x + 1) * factor).min(source.width) {
+                    let index = sy * source.width + sx;
+                    block.push(source.color[index]);
+                    depth = depth.min(source.zbuffer[index]);
+                    overdraw = overdraw.max(source.overdraw[index]);
+                }
+            }
+            let index = y * target.width + x;
+            target.color[index] = resolve_average(&block);
+            target.zbuffer[index] = depth;
+            target.overdraw[index] = overdraw;
+        }
+    }
+}
+
+/// Antialiasing aproximado (FXAA) sobre `buffer`, ya en sRGB.
+///
+/// Donde el contraste de luma con los vecinos es alto, decide si el borde es horizontal
+/// o vertical, lo recorre hasta sus extremos y mezcla el píxel con el del otro lado del
+/// borde según lo cerca que quede de uno de ellos. Los píxeles aislados se mezclan
+/// además con el promedio de sus vecinos.
+pub fn fxaa(framebuffer: &mut Framebuffer) {
+    let (width, height) = (framebuffer.width as i32, framebuffer.height as i32);
+    let source = framebuffer.buffer.clone();
+    let luma: Vec<f32> = source.iter().map(|&pixel| luma(pixel)).collect();
+    let at = |x: i32, y: i32| {
+        luma[(y.clamp(0, height - 1) * width + x.clamp(0, width - 1)) as usize]
+    };
+
+    for y in 0..height {
+        for x in 0..width {
+            let center = at(x, y);
+            let (north, south) = (at(x, y - 1), at(x, y + 1));
+            let (west, east) = (at(x - 1, y), at(x + 1, y));
+            let highest = center.max(north).max(south).max(west).max(east);
+            let lowest = center.min(north).min(south).min(west).min(east);
+            let range = highest - lowest;
+            if range < FXAA_EDGE_THRESHOLD_MIN.max(highest * FXAA_EDGE_THRESHOLD) {
+                continue;
+            }
+
+            let (north_west, north_east) = (at(x - 1, y - 1), at(x + 1, y - 1));
+            let (south_west, south_east) = (at(x - 1, y + 1), at(x + 1, y + 1));
+            let horizontal_edge = (north + south - 2.0 * center).abs() * 2.0
+                + (north_east + south_east - 2.0 * east).abs()
+                + (north_west + south_west - 2.0 * west).abs();
+            let vertical_edge = (west + east - 2.0 * center).abs() * 2.0
+                + (north_west + north_east - 2.0 * north).abs()
+                + (south_west + south_east - 2.0 * south).abs();
+            let horizontal = horizontal_edge >= vertical_edge;
+
+            // Del lado del borde con más contraste está el píxel con el que se mezcla
+            let (before, after) = if horizontal { (north, south) } else { (west, east) };
+            let (gradient_before, gradient_after) = (before - center, after - center);
+            let toward_before = gradient_before.abs() >= gradient_after.abs();
+            let (across, side_luma) = if toward_before { (-1, before) } else { (1, after) };
+            let (across_x, across_y) = if horizontal { (0, across) } else { (across, 0) };
+            let (along_x, along_y) = if horizontal { (1, 0) } else { (0, 1) };
+            let gradient = gradient_before.abs().max(gradient_after.abs()) * 0.25;
+            let local_average = 0.5 * (side_luma + center);
+
+            // Luma sobre la línea del borde, entre cada píxel y su vecino del otro lado
+            let edge_luma = |x: i32, y: i32| 0.5 * (at(x, y) + at(x + across_x, y + across_y));
+            let search = |direction: i32| {
+                let mut end = 0.0;
+                for step in 1..=FXAA_SEARCH_STEPS {
+                    end = edge_luma(x + along_x * step * direction, y + along_y * step * direction)
+                        - local_average;
+                    if end.abs() >= gradient {
+                        return (step as f32, end);
+                    }
+                }
+                (FXAA_SEARCH_STEPS as f32, end)
+            };
+            let (distance_before, end_before) = search(-1);
+            let (distance_after, end_after) = search(1);
+            let (distance, end) = if distance_before < distance_after {
+                (distance_before, end_before)
+            } else {
+                (distance_after, end_after)
+            };
+            let edge_length = distance_before + distance_after;
+            // Solo se mezcla si el extremo más cercano va en la dirección opuesta al centro
+            let edge_offset = if (end < 0.0) != (center < local_average) {
+                0.5 - distance / edge_length
+            } else {
+                0.0
+            };
+
+            let average = (2.0 * (north + south + west + east)
+                + north_west
+                + north_east
+                + south_west
+                + south_east)
+                / 12.0;
+            let subpixel = ((average - center).abs() / range).clamp(0.0, 1.0);
+            let subpixel = (-2.0 * subpixel + 3.0) * subpixel * subpixel;
+            let subpixel_offset = subpixel * subpixel * FXAA_SUBPIXEL_QUALITY;
+
+            let amount = edge_offset.max(subpixel_offset);
+            let neighbor_x = (x + across_x).clamp(0, width - 1);
+            let neighbor_y = (y + across_y).clamp(0, height - 1);
+            let index = (y * width + x) as usize;
+            let neighbor = source[(neighbor_y * width + neighbor_x) as usize];
+            framebuffer.buffer[index] = mix(source[index], neighbor, amount);
+        }
+    }
+}
+
+/// Luma perceptual de un color sRGB `0xRRGGBB`, de 0 a 1.
+fn luma(pixel: u32) -> f32 {
+    let channel = |shift: u32| ((pixel >> shift) & 0xFF) as f32 / 255.0;
+    0.299 * channel(16) + 0.587 * channel(8) + 0.114 * channel(0)
+}
+
+fn mix(a: u32, b: u32, t: f32) -> u32 {
+    let channel = |shift: u32| {
+        let (a, b) = (((a >> shift) & 0xFF) as f32, ((b >> shift) & 0xFF) as f32);
+        ((a + (b - a) * t).round() as u32) << shift
+    };
+    channel(16) | channel(8) | channel(0)
+}
@@ -0,0 +1,114 @@
+use nalgebra_glm::{dot, scaling, translation, Mat4, Vec3};
+use crate::color::Color;
+use crate::shaders::smoothstep;
+
+/// Brillo extra de la dispersión de Rayleigh al mirar en la dirección de la luz o en
+/// contra: `3/4 · (1 + cos²θ)`.
+fn rayleigh_phase(cos_angle: f32) -> f32 {
+    0.75 * (1.0 + cos_angle * cos_angle)
+}
+
+/// Lóbulo de Mie hacia adelante: casi nada salvo al mirar hacia la luz.
+fn mie_phase(cos_angle: f32) -> f32 {
+    cos_angle.max(0.0).powi(8)
+}
+
+/// Capa de gas alrededor de un cuerpo, dibujada como una esfera algo mayor que él.
+///
+/// La opacidad crece con el camino que recorre la mirada dentro de la capa, así que el
+/// borde (el limbo) se ve mucho más denso que el centro del disco. El color sale de dos
+/// aproximaciones de la dispersión: la de Rayleigh tiñe con `color` el lado iluminado
+/// por el Sol y la de Mie agrega un brillo de `haze` al mirar hacia el Sol a través de
+/// la capa.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Atmosphere {
+    /// Color de la dispersión de Rayleigh, el del cielo visto desde el suelo.
+    pub color: Color,
+    /// Color del brillo de Mie alrededor del Sol.
+    pub haze: Color,
+    /// Intensidad del brillo de Mie; 0 lo apaga.
+    pub haze_strength: f32,
+    /// Grosor de la capa como fracción del radio del cuerpo.
+    pub thickness: f32,
+    /// Espesor óptico en el centro del disco, donde la mirada cruza la capa de frente.
+    pub density: f32,
+    /// Cuánto se enrarece el gas con la altura; 0 la deja uniforme.
+    pub falloff: f32,
+}
+
+impl Atmosphere {
+    pub fn new(color: Color, thickness: f32, density: f32) -> Self {
+        Atmosphere {
+            color,
+            haze: color,
+            haze_strength: 0.0,
+            thickness,
+            density,
+            falloff: 3.0,
+        }
+    }
+
+    pub fn with_haze(mut self, haze: Color, strength: f32) -> Self {
+        self.haze = haze;
+        self.haze_strength = strength;
+        self
+    }
+
+    pub fn with_falloff(mut self, falloff: f32) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    /// Escala de la capa respecto del cuerpo.
+    pub fn scale(&self) -> f32 {
+        1.0 + self.thickness
+    }
+
+    /// Matriz de modelo de la capa: el modelo del cuerpo agrandado por `scale()`
+    /// alrededor de `mesh_center`, el centro de la malla en espacio de modelo.
+    pub fn shell_matrix(&self, body_matrix: &Mat4, mesh_center: Vec3) -> Mat4 {
+        let scale = self.scale();
+        body_matrix
+            * translation(&mesh_center)
+            * scaling(&Vec3::new(scale, scale, scale))
+            * translation(&-mesh_center)
+    }
+
+    /// Color y opacidad de la capa en `point`, visto desde `eye`, para un cuerpo de
+    /// radio `radius` centrado en `center` e iluminado por el Sol en `sun`.
+    ///
+    /// Solo aporta la cara de la capa que mira a la cámara: la de atrás devuelve un
+    /// color transparente, porque el camino completo de la mirada ya se cuenta delante.
+    pub fn scatter(&self, eye: Vec3, point: Vec3, center: Vec3, radius: f32, sun: Vec3) -> Color {
+        let view = point - eye;
+        if view.magnitude() <= f32::EPSILON || radius <= 0.0 || self.thickness <= 0.0 {
+            return Color::transparent();
+        }
+        let view = view.normalize();
+        let up = point - center;
+        if dot(&up, &view) > 0.0 {
+            return Color::transparent();
+        }
+
+        // Altura mínima de la mirada sobre el centro y cuerda que recorre en cada esfera
+        let shell_radius = radius * self.scale();
+        let to_center = center - eye;
+        let along = dot(&to_center, &view);
+        let closest = (to_center.magnitude_squared() - along * along).max(0.0).sqrt();
+        let chord = |sphere: f32| 2.0 * (sphere * sphere - closest * closest).max(0.0).sqrt();
+        let path = chord(shell_radius) - chord(radius);
+        let shell_depth = shell_radius - radius;
+        let altitude = ((closest - radius) / shell_depth).clamp(0.0, 1.0);
+        let optical_depth =
+            0.5 * self.density * path / shell_depth * (-self.falloff * altitude).exp();
+        let alpha = 1.0 - (-optical_depth).exp();
+
+        // La capa sigue iluminada un poco más allá del terminador
+        let to_sun = (sun - point).normalize();
+        let daylight = smoothstep(-0.3, 0.3, dot(&up.normalize(), &to_sun));
+        let cos_angle = dot(&view, &to_sun);
+        let color = self.color * rayleigh_phase(cos_angle)
+            + self.haze * (self.haze_strength * mie_phase(cos_angle));
+        (color * daylight).with_alpha(alpha)
+    }
+}
@@ -0,0 +1,211 @@
+use nalgebra_glm::{mat3_to_quat, quat_normalize, quat_rotate_vec3, quat_slerp, Mat3, Quat, Vec3};
+use crate::focus::ease_in_out;
+use crate::gravity::circular_velocity;
+use crate::scene::Scene;
+
+/// Radio de la órbita de estacionamiento, en múltiplos del radio del cuerpo.
+pub const PARKING_FACTOR: f32 = 3.0;
+/// Distancia mínima a la que la ruta pasa junto a otro cuerpo, en múltiplos de su radio.
+pub const CLEARANCE_FACTOR: f32 = 1.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutopilotPhase {
+    /// La nave gira, quieta, hasta apuntar a lo largo de la ruta.
+    Aligning,
+    /// Salto warp: acelera y frena siguiendo la ruta.
+    Warp,
+    /// En órbita de estacionamiento; el piloto automático ya no hace nada.
+    Arrived,
+}
+
+/// Piloto automático que lleva la nave hasta una órbita de estacionamiento alrededor
+/// de un cuerpo.
+///
+/// La ruta es una curva de Bézier cuadrática desde la nave hasta un punto a
+/// [`PARKING_FACTOR`] radios del objetivo. Si el tramo recto pasa demasiado cerca de
+/// otro cuerpo (típicamente el Sol), el punto de control se aleja para rodearlo. El
+/// final de la ruta se mueve con el objetivo, que sigue en su órbita durante el salto.
+pub struct Autopilot {
+    pub target: usize,
+    pub phase: AutopilotPhase,
+    /// Segundos que tarda la nave en apuntar a la ruta antes de saltar.
+    pub align_time: f32,
+    /// Velocidad media del salto en unidades por segundo.
+    pub warp_speed: f32,
+    start: Vec3,
+    start_orientation: Quat,
+    control: Vec3,
+    /// Punto de llegada relativo al centro del objetivo.
+    arrival: Vec3,
+    elapsed: f32,
+    duration: f32,
+}
+
+impl Autopilot {
+    /// Planea la ruta desde la posición actual de la nave hacia `target`.
+    pub fn engage(scene: &Scene, target: usize, time: u32) -> Self {
+        let ship = &scene.ship;
+        let center = scene.body_bounds(target, time).center;
+        let direction = (ship.position - center)
+            .try_normalize(1e-4)
+            .unwrap_or(Vec3::new(0.0, 0.0, 1.0));
+        let arrival = direction * parking_radius(scene, target, time);
+        let end = center + arrival;
+        let control = detour(scene, target, ship.position, end, time);
+
+        let align_time = 1.0;
+        let warp_speed = 12.0;
+        let length = (control - ship.position).magnitude() + (end - control).magnitude();
+        Autopilot {
+            target,
+            phase: AutopilotPhase::Aligning,
+            align_time,
+            warp_speed,
+            start: ship.position,
+            start_orientation: ship.orientation,
+            control,
+            arrival,
+            elapsed: 0.0,
+            duration: (length / warp_speed).max(2.0),
+        }
+    }
+
+    /// Punto de control de la ruta; a mitad de camino la curva pasa por
+    /// `(inicio + 2 * control + fin) / 4`.
+    pub fn control_point(&self) -> Vec3 {
+        self.control
+    }
+
+    /// Punto de la ruta para `s` entre 0 y 1, con el objetivo en el instante `time`.
+    pub fn path_point(&self, scene: &Scene, s: f32, time: u32) -> Vec3 {
+        let end = self.end(scene, time);
+        let u = 1.0 - s;
+        self.start * (u * u) + self.control * (2.0 * u * s) + end * (s * s)
+    }
+
+    /// Qué tan fuerte es el efecto warp, de 0 a 1: crece al acelerar y se apaga al frenar.
+    pub fn warp_intensity(&self) -> f32 {
+        if self.phase != AutopilotPhase::Warp {
+            return 0.0;
+        }
+        let t = (self.elapsed / self.duration).clamp(0.0, 1.0);
+        // Proporcional a la velocidad: la derivada de `ease_in_out` normalizada
+        let ramp = 2.0 * t.min(1.0 - t);
+        ramp * ramp
+    }
+
+    /// Avanza `dt` segundos; mueve y orienta la nave directamente, sin física.
+    pub fn update(&mut self, scene: &mut Scene, time: u32, dt: f32) {
+        match self.phase {
+            AutopilotPhase::Aligning => {
+                self.elapsed += dt;
+                let t = (self.elapsed / self.align_time).min(1.0);
+                let facing = look_along(self.tangent(scene, 0.0, time), &self.start_orientation);
+                let ship = &mut scene.ship;
+                ship.position = self.start;
+                ship.velocity = Vec3::new(0.0, 0.0, 0.0);
+                ship.angular_velocity = Vec3::new(0.0, 0.0, 0.0);
+                ship.orientation = quat_slerp(&self.start_orientation, &facing, ease_in_out(t));
+                if t >= 1.0 {
+                    self.phase = AutopilotPhase::Warp;
+                    self.elapsed = 0.0;
+                }
+            }
+            AutopilotPhase::Warp => {
+                self.elapsed += dt;
+                let t = (self.elapsed / self.duration).min(1.0);
+                let s = ease_in_out(t);
+                let tangent = self.tangent(scene, s, time);
+                let position = self.path_point(scene, s, time);
+                let orientation = look_along(tangent, &scene.ship.orientation);
+                let ship = &mut scene.ship;
+                // La velocidad refleja el salto, por si se consulta o se corta el viaje
+                ship.velocity = (position - ship.position) / dt.max(1e-3);
+                ship.position = position;
+                ship.orientation = orientation;
+                if t >= 1.0 {
+                    self.park(scene, tangent);
+                }
+            }
+            AutopilotPhase::Arrived => {}
+        }
+    }
+
+    /// Deja la nave con la velocidad de una órbita circular, medida en el marco del
+    /// objetivo, y apuntando hacia adelante en ella.
+    fn park(&mut self, scene: &mut Scene, tangent: Vec3) {
+        let mu = scene.bodies[self.target].gravity;
+        let ship = &mut scene.ship;
+        ship.velocity = circular_velocity(mu, self.arrival, tangent);
+        ship.angular_velocity = Vec3::new(0.0, 0.0, 0.0);
+        if let Some(direction) = ship.velocity.try_normalize(1e-6) {
+            ship.orientation = look_along(direction, &ship.orientation);
+        }
+        self.phase = AutopilotPhase::Arrived;
+    }
+
+    fn end(&self, scene: &Scene, time: u32) -> Vec3 {
+        scene.body_bounds(self.target, time).center + self.arrival
+    }
+
+    fn tangent(&self, scene: &Scene, s: f32, time: u32) -> Vec3 {
+        let end = self.end(scene, time);
+        let tangent = (self.control - self.start) * (1.0 - s) + (end - self.control) * s;
+        tangent.try_normalize(1e-6).unwrap_or(Vec3::new(0.0, 0.0, -1.0))
+    }
+}
+
+/// Distancia segura para orbitar `index`: varios radios del cuerpo, pero dentro de su
+/// esfera de influencia para que su gravedad siga mandando.
+pub fn parking_radius(scene: &Scene, index: usize, time: u32) -> f32 {
+    let radius = scene.body_bounds(index, time).radius * PARKING_FACTOR;
+    radius.min(scene.bodies[index].influence_radius * 0.8)
+}
+
+/// Punto de control que hace que la ruta de `start` a `end` rodee al cuerpo que más
+/// se interpone; el punto medio entre ambos si el camino está libre.
+fn detour(scene: &Scene, target: usize, start: Vec3, end: Vec3, time: u32) -> Vec3 {
+    let midpoint = (start + end) / 2.0;
+    let segment = end - start;
+    let mut worst: Option<(f32, Vec3)> = None;
+
+    for (index, body) in scene.bodies.iter().enumerate() {
+        if index == target || !body.solid {
+            continue;
+        }
+        let bounds = scene.body_bounds(index, time);
+        let clearance = bounds.radius * CLEARANCE_FACTOR;
+        let along = (bounds.center - start).dot(&segment) / segment.magnitude_squared().max(1e-6);
+        let closest = start + segment * along.clamp(0.0, 1.0);
+        let away = closest - bounds.center;
+        let intrusion = clearance - away.magnitude();
+        if intrusion <= 0.0 || worst.is_some_and(|(deepest, _)| deepest >= intrusion) {
+            continue;
+        }
+        // Si la ruta pasa justo por el centro se rodea por arriba
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let side = away
+            .try_normalize(1e-4)
+            .or_else(|| (up - segment * (up.dot(&segment) / segment.magnitude_squared()))
+                .try_normalize(1e-4))
+            .unwrap_or(Vec3::new(1.0, 0.0, 0.0));
+        let passing = bounds.center + side * clearance;
+        worst = Some((intrusion, passing * 2.0 - midpoint));
+    }
+
+    worst.map_or(midpoint, |(_, control)| control)
+}
+
+/// Orientación con la proa (`-z`) hacia `direction`, conservando en lo posible el
+/// "arriba" de `reference`.
+fn look_along(direction: Vec3, reference: &Quat) -> Quat {
+    let up = quat_rotate_vec3(reference, &Vec3::new(0.0, 1.0, 0.0));
+    let right = direction
+        .cross(&up)
+        .try_normalize(1e-4)
+        .or_else(|| direction.cross(&Vec3::new(0.0, 1.0, 0.0)).try_normalize(1e-4))
+        .unwrap_or(Vec3::new(1.0, 0.0, 0.0));
+    let up = right.cross(&direction);
+    let basis = Mat3::from_columns(&[right, up, -direction]);
+    quat_normalize(&mat3_to_quat(&basis))
+}
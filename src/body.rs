@@ -0,0 +1,333 @@
+use nalgebra_glm::Vec3;
+use crate::atmosphere::Atmosphere;
+use crate::color::{BlendMode, Color};
+use crate::orbit::Trail;
+use crate::planet::{Biome, PlanetGenerator};
+use crate::shaders::{
+    earth_shader, jupiter_shader, mars_shader, moon_shader, rocky_planet_shader,
+    saturn_rings_shader, saturn_shader, sun_shader, venus_shader, FragmentShader,
+};
+
+pub const TRAIL_LENGTH: usize = 120;
+
+/// Un cuerpo del sistema: su modelo, shader y órbita circular alrededor de `parent`.
+pub struct Body {
+    pub name: String,
+    pub mesh: String,
+    pub shader: FragmentShader,
+    /// Superficie generada por semilla; si está, se usa en lugar de `shader`.
+    pub surface: Option<PlanetGenerator>,
+    /// Cómo se mezcla con lo que está detrás; los cuerpos transparentes se dibujan al
+    /// final, del más lejano al más cercano.
+    pub blend: BlendMode,
+    /// Capa de gas que se dibuja alrededor del cuerpo; `None` si no tiene.
+    pub atmosphere: Option<Atmosphere>,
+    pub scale: f32,
+    pub orbit_radius: f32,
+    pub orbit_speed: f32,
+    pub rotation_speed: f32,
+    pub offset: Vec3,
+    pub parent: Option<usize>,
+    pub orbit_color: Color,
+    pub show_orbit: bool,
+    pub trail: Trail,
+    /// Si la nave y la cámara chocan con el cuerpo.
+    pub solid: bool,
+    /// Parámetro gravitacional G·M en unidades³/s²; 0 si no atrae a la nave.
+    pub gravity: f32,
+    /// Radio de la esfera de influencia: dentro de ella manda la gravedad de este cuerpo.
+    pub influence_radius: f32,
+    /// Datos reales del cuerpo para el panel de información; `None` si no los tiene.
+    pub data: Option<PhysicalData>,
+}
+
+/// Datos físicos reales de un cuerpo, independientes de la escala de la simulación.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhysicalData {
+    /// Radio medio en km.
+    pub radius: f32,
+    /// Masa en kg.
+    pub mass: f64,
+    /// Período orbital en días terrestres; `None` para el Sol.
+    pub orbital_period: Option<f32>,
+    /// Período de rotación sideral en horas; negativo si gira al revés.
+    pub rotation_period: f32,
+    /// Temperatura media de la superficie (o de la cima de las nubes) en °C.
+    pub temperature: f32,
+    /// Lunas conocidas.
+    pub moons: u32,
+}
+
+impl Body {
+    pub fn new(name: &str, mesh: &str, shader: FragmentShader, scale: f32) -> Self {
+        Body {
+            name: name.to_string(),
+            mesh: mesh.to_string(),
+            shader,
+            surface: None,
+            blend: BlendMode::Opaque,
+            atmosphere: None,
+            scale,
+            orbit_radius: 0.0,
+            orbit_speed: 0.0,
+            rotation_speed: 0.0,
+            offset: Vec3::new(0.0, 0.0, 0.0),
+            parent: None,
+            orbit_color: Color::new(255, 255, 255),
+            show_orbit: true,
+            trail: Trail::new(TRAIL_LENGTH),
+            solid: true,
+            gravity: 0.0,
+            influence_radius: 0.0,
+            data: None,
+        }
+    }
+
+    pub fn with_orbit(mut self, radius: f32, speed: f32, color: Color) -> Self {
+        self.orbit_radius = radius;
+        self.orbit_speed = speed;
+        self.orbit_color = color;
+        self
+    }
+
+    pub fn with_rotation(mut self, speed: f32) -> Self {
+        self.rotation_speed = speed;
+        self
+    }
+
+    pub fn with_parent(mut self, parent: usize, offset: Vec3) -> Self {
+        self.parent = Some(parent);
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_gravity(mut self, mu: f32, influence_radius: f32) -> Self {
+        self.gravity = mu;
+        self.influence_radius = influence_radius;
+        self
+    }
+
+    pub fn with_data(mut self, data: PhysicalData) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    pub fn with_blend(mut self, blend: BlendMode) -> Self {
+        self.blend = blend;
+        self
+    }
+
+    pub fn with_surface(mut self, surface: PlanetGenerator) -> Self {
+        self.surface = Some(surface);
+        self
+    }
+
+    pub fn with_atmosphere(mut self, atmosphere: Atmosphere) -> Self {
+        self.atmosphere = Some(atmosphere);
+        self
+    }
+
+    /// Para cuerpos decorativos, como los anillos, que se pueden atravesar.
+    pub fn without_collision(mut self) -> Self {
+        self.solid = false;
+        self
+    }
+
+    pub fn has_orbit(&self) -> bool {
+        self.orbit_radius > 0.0 && self.orbit_speed != 0.0
+    }
+
+    /// Posición relativa al cuerpo padre (o al origen si no tiene)
+    pub fn local_position(&self, time: f32) -> Vec3 {
+        let angle = time * self.orbit_speed;
+        Vec3::new(
+            self.orbit_radius * angle.cos(),
+            0.0,
+            self.orbit_radius * angle.sin(),
+        ) + self.offset
+    }
+
+    pub fn rotation(&self, time: f32) -> Vec3 {
+        Vec3::new(0.0, time * self.rotation_speed, 0.0)
+    }
+}
+
+/// Posición en mundo del cuerpo `index`, sumando la de sus padres.
+pub fn body_position(bodies: &[Body], index: usize, time: f32) -> Vec3 {
+    let body = &bodies[index];
+    let parent_position = match body.parent {
+        Some(parent) => body_position(bodies, parent, time),
+        None => Vec3::new(0.0, 0.0, 0.0),
+    };
+    parent_position + body.local_position(time)
+}
+
+/// Sol, planetas, Luna y anillos de Saturno con los parámetros del programa; Venus, la
+/// Tierra y los gigantes gaseosos tienen atmósfera.
+pub fn solar_system(distance_scale: f32) -> Vec<Body> {
+    let planet = "assets/models/planet.obj";
+    // Radio (km), masa (kg), período orbital (días), rotación (h), temperatura (°C), lunas
+    let data = |radius, mass, orbital_period, rotation_period, temperature, moons| PhysicalData {
+        radius,
+        mass,
+        orbital_period,
+        rotation_period,
+        temperature,
+        moons,
+    };
+
+    vec![
+        Body::new("Sol", "assets/models/sun.obj", sun_shader, 1.5)
+            .with_gravity(34.0, f32::INFINITY)
+            .with_data(data(695_700.0, 1.989e30, None, 609.1, 5_505.0, 0)),
+        Body::new("Mercurio", planet, rocky_planet_shader, 0.4)
+            .with_orbit(10.0 * distance_scale, 0.02, Color::new(169, 169, 169))
+            .with_rotation(0.05)
+            .with_gravity(0.1, 1.5)
+            .with_data(data(2_439.7, 3.301e23, Some(87.97), 1_407.6, 167.0, 0)),
+        Body::new("Venus", planet, venus_shader, 0.55)
+            .with_orbit(18.0 * distance_scale, 0.015, Color::new(255, 228, 181))
+            .with_rotation(0.03)
+            .with_gravity(0.25, 2.0)
+            .with_data(data(6_051.8, 4.867e24, Some(224.70), -5_832.5, 464.0, 0))
+            // Nubes espesas de ácido sulfúrico: casi no deja ver el disco
+            .with_atmosphere(
+                Atmosphere::new(Color::new(230, 190, 110), 0.12, 0.9)
+                    .with_haze(Color::new(255, 230, 170), 1.5)
+                    .with_falloff(1.5),
+            ),
+        Body::new("Tierra", planet, earth_shader, 0.6)
+            .with_orbit(25.0 * distance_scale, 0.01, Color::new(0, 105, 148))
+            .with_rotation(0.02)
+            .with_gravity(0.3, 4.0)
+            .with_data(data(6_371.0, 5.972e24, Some(365.26), 23.93, 15.0, 1))
+            .with_atmosphere(
+                Atmosphere::new(Color::new(80, 150, 255), 0.08, 0.06)
+                    .with_haze(Color::new(255, 240, 220), 1.0),
+            ),
+        Body::new("Luna", planet, moon_shader, 0.15)
+            .with_orbit(2.5, 0.03, Color::new(200, 200, 200))
+            .with_rotation(0.05)
+            .with_parent(3, Vec3::new(0.0, 2.0, 0.0))
+            .with_gravity(0.02, 0.6)
+            .with_data(data(1_737.4, 7.342e22, Some(27.32), 655.7, -20.0, 0)),
+        Body::new("Marte", planet, mars_shader, 0.5)
+            .with_orbit(35.0 * distance_scale, 0.008, Color::new(205, 92, 92))
+            .with_rotation(0.03)
+            .with_gravity(0.2, 2.0)
+            .with_data(data(3_389.5, 6.417e23, Some(686.98), 24.62, -65.0, 2)),
+        Body::new("Júpiter", planet, jupiter_shader, 1.2)
+            .with_orbit(50.0 * distance_scale, 0.005, Color::new(210, 180, 140))
+            .with_rotation(0.02)
+            .with_gravity(2.0, 5.0)
+            .with_data(data(69_911.0, 1.898e27, Some(4_332.59), 9.93, -110.0, 95))
+            .with_atmosphere(
+                Atmosphere::new(Color::new(235, 215, 180), 0.05, 0.1).with_falloff(4.0),
+            ),
+        Body::new("Saturno", planet, saturn_shader, 1.0)
+            .with_orbit(70.0 * distance_scale, 0.003, Color::new(245, 222, 179))
+            .with_rotation(0.015)
+            .with_gravity(1.2, 5.0)
+            .with_data(data(58_232.0, 5.683e26, Some(10_759.22), 10.66, -140.0, 146))
+            .with_atmosphere(
+                Atmosphere::new(Color::new(245, 230, 190), 0.05, 0.1).with_falloff(4.0),
+            ),
+        // rings.obj comparte el centro de planet.obj: con la escala y la rotación de
+        // Saturno queda alrededor de su ecuador
+        Body::new("Anillos de Saturno", "assets/models/rings.obj", saturn_rings_shader, 1.0)
+            .with_rotation(0.015)
+            .with_parent(7, Vec3::new(0.0, 0.0, 0.0))
+            .with_blend(BlendMode::Normal)
+            .without_collision(),
+    ]
+}
+
+/// Sistema inventado a partir de `seed`: una estrella y de tres a seis planetas con
+/// superficies generadas. Los más cercanos a la estrella son volcánicos o áridos, los
+/// del medio templados, desérticos o alienígenas, y los más lejanos helados o áridos.
+pub fn fictional_system(seed: u32, distance_scale: f32) -> Vec<Body> {
+    // xorshift: números de 0 a 1 que se repiten con la misma semilla. Las primeras
+    // salidas se parecen entre semillas cercanas, así que se descartan
+    let mut state = (seed.wrapping_mul(0x9E37_79B9) ^ 0x5F37_59DF).max(1);
+    let mut random = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        (state >> 8) as f32 / (1 << 24) as f32
+    };
+    for _ in 0..8 {
+        random();
+    }
+
+    let mut bodies = vec![Body::new("Estrella", "assets/models/sun.obj", sun_shader, 1.5)
+        .with_gravity(34.0, f32::INFINITY)];
+    let count = 3 + (random() * 4.0) as usize;
+    let mut orbit = 6.0;
+    for i in 0..count {
+        orbit += 6.0 + random() * 8.0;
+        let zone = i as f32 / (count - 1) as f32;
+        let biomes: &[Biome] = if zone < 0.25 {
+            &[Biome::Volcanic, Biome::Barren]
+        } else if zone < 0.7 {
+            &[Biome::Temperate, Biome::Desert, Biome::Alien]
+        } else {
+            &[Biome::Frozen, Biome::Barren]
+        };
+        let biome = biomes[(random() * biomes.len() as f32) as usize];
+        let (ocean, clouds, craters, ice) = match biome {
+            Biome::Temperate | Biome::Alien => (
+                0.4 + 0.4 * random(),
+                0.2 + 0.4 * random(),
+                0.1 * random(),
+                60.0 + 20.0 * random(),
+            ),
+            Biome::Desert => (0.1 * random(), 0.1 * random(), 0.2 * random(), 80.0),
+            Biome::Volcanic => (0.2 + 0.3 * random(), 0.3 * random(), 0.5 * random(), 90.0),
+            Biome::Frozen => (0.3 + 0.5 * random(), 0.1 * random(), 0.5 * random(), 45.0),
+            Biome::Barren => (0.0, 0.0, 0.4 + 0.6 * random(), 90.0),
+        };
+        let surface = PlanetGenerator::new(seed.wrapping_mul(31).wrapping_add(i as u32 + 1))
+            .with_biome(biome)
+            .with_ocean_level(ocean)
+            .with_cloud_cover(clouds)
+            .with_craters(craters)
+            .with_ice_caps(ice);
+
+        // Como en el Sistema Solar: más lentos y con más gravedad cuanto más grandes
+        let scale = 0.35 + 0.6 * random();
+        let speed = 0.6 / orbit.powf(1.5);
+        let name = planet_name(&mut random);
+        let mut body = Body::new(&name, "assets/models/planet.obj", rocky_planet_shader, scale)
+            .with_orbit(orbit * distance_scale, speed, surface.palette.lowland)
+            .with_rotation(0.01 + 0.04 * random())
+            .with_gravity(1.2 * scale.powi(3), 1.5 + 3.0 * scale)
+            .with_surface(surface);
+        if biome == Biome::Temperate || biome == Biome::Alien {
+            let sky = if biome == Biome::Temperate {
+                Color::new(80, 150, 255)
+            } else {
+                Color::new(120, 255, 170)
+            };
+            body = body.with_atmosphere(Atmosphere::new(sky, 0.08, 0.06));
+        }
+        bodies.push(body);
+    }
+    bodies
+}
+
+/// Nombre inventado de dos o tres sílabas.
+fn planet_name(random: &mut impl FnMut() -> f32) -> String {
+    const SYLLABLES: [&str; 16] = [
+        "ka", "lo", "ri", "zen", "tha", "mu", "vor", "el", "sa", "qui", "dor", "an", "pe",
+        "xi", "ru", "nor",
+    ];
+    let count = 2 + (random() * 2.0) as usize;
+    let name: String = (0..count)
+        .map(|_| SYLLABLES[(random() * SYLLABLES.len() as f32) as usize])
+        .collect();
+    let mut letters = name.chars();
+    match letters.next() {
+        Some(first) => first.to_uppercase().chain(letters).collect(),
+        None => name,
+    }
+}
@@ -0,0 +1,51 @@
+use nalgebra_glm::{rotate_vec3, Vec3};
+use crate::vertex::Vertex;
+
+/// Esfera que envuelve un modelo, en el espacio del modelo o ya en mundo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingSphere {
+    pub center: Vec3,
+    pub radius: f32,
+}
+
+impl BoundingSphere {
+    /// Centro de la caja envolvente y la distancia al vértice más lejano.
+    ///
+    /// Los modelos no están centrados en el origen (`planet.obj` tiene el centro cerca
+    /// de `(0, 5.3, -0.6)`), por eso no basta con la posición del cuerpo.
+    pub fn from_vertices(vertices: &[Vertex]) -> Self {
+        if vertices.is_empty() {
+            return BoundingSphere {
+                center: Vec3::new(0.0, 0.0, 0.0),
+                radius: 0.0,
+            };
+        }
+
+        let mut min = vertices[0].position;
+        let mut max = vertices[0].position;
+        for vertex in vertices {
+            min = min.inf(&vertex.position);
+            max = max.sup(&vertex.position);
+        }
+        let center = (min + max) * 0.5;
+        let radius = vertices
+            .iter()
+            .map(|vertex| (vertex.position - center).magnitude())
+            .fold(0.0, f32::max);
+
+        BoundingSphere { center, radius }
+    }
+
+    /// La misma esfera tras `create_model_matrix(translation, scale, rotation)`.
+    pub fn transformed(&self, translation: Vec3, scale: f32, rotation: Vec3) -> Self {
+        let mut center = self.center * scale;
+        center = rotate_vec3(&center, rotation.x, &Vec3::new(1.0, 0.0, 0.0));
+        center = rotate_vec3(&center, rotation.y, &Vec3::new(0.0, 1.0, 0.0));
+        center = rotate_vec3(&center, rotation.z, &Vec3::new(0.0, 0.0, 1.0));
+
+        BoundingSphere {
+            center: translation + center,
+            radius: self.radius * scale.abs(),
+        }
+    }
+}
@@ -1,6 +1,36 @@
 use nalgebra_glm::{rotate_vec3, Vec3};
 use std::f32::consts::PI;
 
+/// Cómo se controla la cámara en la ventana.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraMode {
+    /// Detrás de la nave, que se pilota con el teclado (ver [`ChaseCamera`](crate::chase_camera::ChaseCamera)).
+    Chase,
+    /// Órbita alrededor de `center`, con zoom.
+    Orbit,
+    /// Vuelo libre con seis grados de libertad (ver [`FreeCamera`](crate::free_camera::FreeCamera)).
+    FreeFlight,
+}
+
+impl CameraMode {
+    pub fn next(self) -> Self {
+        match self {
+            CameraMode::Chase => CameraMode::Orbit,
+            CameraMode::Orbit => CameraMode::FreeFlight,
+            CameraMode::FreeFlight => CameraMode::Chase,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CameraMode::Chase => "Nave",
+            CameraMode::Orbit => "Órbita",
+            CameraMode::FreeFlight => "Vuelo libre",
+        }
+    }
+}
+
+/// Cámara que mira desde `eye` hacia `center`.
 pub struct Camera {
     pub eye: Vec3,
     pub center: Vec3,
@@ -40,6 +70,7 @@ impl Camera {
         rotated.normalize()
     }
 
+    /// Gira `eye` alrededor de `center` (en radianes); el pitch se limita cerca de los polos.
     pub fn orbit(&mut self, delta_yaw: f32, delta_pitch: f32) {
         let radius_vector = self.eye - self.center;
         let radius = radius_vector.magnitude();
@@ -64,12 +95,30 @@ impl Camera {
         self.has_changed = true;
     }
 
+    /// Acerca (`delta` positivo) o aleja `eye` de `center`.
     pub fn zoom(&mut self, delta: f32) {
         let direction = (self.center - self.eye).normalize();
         self.eye += direction * delta;
         self.has_changed = true;
     }
 
+    /// Multiplica la distancia entre `eye` y `center` por `factor`, sin pasar de `center`.
+    pub fn dolly(&mut self, factor: f32) {
+        let radius_vector = self.eye - self.center;
+        let radius = (radius_vector.magnitude() * factor).max(0.1);
+        self.eye = self.center + radius_vector.normalize() * radius;
+        self.has_changed = true;
+    }
+
+    /// Desplaza `eye` y `center` juntos en el plano de la pantalla.
+    pub fn pan(&mut self, delta_right: f32, delta_up: f32) {
+        let offset = self.right() * delta_right + self.up() * delta_up;
+        self.eye += offset;
+        self.center += offset;
+        self.has_changed = true;
+    }
+
+    /// Rota la dirección de vista alrededor de `eye`, moviendo `center`.
     pub fn move_center(&mut self, direction: Vec3) {
         let radius_vector = self.center - self.eye;
         let radius = radius_vector.magnitude();
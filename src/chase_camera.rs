@@ -0,0 +1,65 @@
+use nalgebra_glm::Vec3;
+use crate::camera::Camera;
+use crate::ship::Ship;
+
+/// Cámara de persecución: se queda detrás y un poco arriba de la nave y la
+/// alcanza con un retraso suave, así se nota cuando la nave gira o acelera.
+pub struct ChaseCamera {
+    /// Distancia detrás de la nave.
+    pub distance: f32,
+    /// Altura sobre la nave, en su eje "arriba".
+    pub height: f32,
+    /// Qué tan adelante de la nave se mira.
+    pub look_ahead: f32,
+    /// Qué tan rápido alcanza su posición ideal (1/segundos).
+    pub stiffness: f32,
+    last_target: Option<Vec3>,
+}
+
+impl Default for ChaseCamera {
+    fn default() -> Self {
+        ChaseCamera::new()
+    }
+}
+
+impl ChaseCamera {
+    pub fn new() -> Self {
+        ChaseCamera {
+            distance: 0.6,
+            height: 0.15,
+            look_ahead: 1.0,
+            stiffness: 8.0,
+            last_target: None,
+        }
+    }
+
+    /// Posición ideal de la cámara para la pose actual de la nave.
+    pub fn target_eye(&self, ship: &Ship) -> Vec3 {
+        ship.position - ship.forward() * self.distance + ship.up() * self.height
+    }
+
+    /// Coloca la cámara directamente detrás de la nave, sin transición.
+    pub fn snap(&mut self, camera: &mut Camera, ship: &Ship) {
+        let target = self.target_eye(ship);
+        camera.eye = target;
+        self.last_target = Some(target);
+        self.aim(camera, ship);
+    }
+
+    pub fn update(&mut self, camera: &mut Camera, ship: &Ship, dt: f32) {
+        // El desfase se mide contra la posición ideal del cuadro anterior: así la
+        // cámara acompaña la traslación de la nave sin retraso y solo se suavizan
+        // los giros, aunque la nave vaya muy rápido
+        let target = self.target_eye(ship);
+        let lag = camera.eye - self.last_target.unwrap_or(target);
+        camera.eye = target + lag * (-self.stiffness * dt).exp();
+        self.last_target = Some(target);
+        self.aim(camera, ship);
+    }
+
+    fn aim(&self, camera: &mut Camera, ship: &Ship) {
+        camera.center = ship.position + ship.forward() * self.look_ahead;
+        camera.up = ship.up();
+        camera.has_changed = true;
+    }
+}
@@ -0,0 +1,216 @@
+use nalgebra_glm::Vec3;
+use crate::bounds::BoundingSphere;
+use crate::ship::Ship;
+
+/// Punto de contacto entre una esfera y un cuerpo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact {
+    /// Punto de la superficie del cuerpo más cercano al centro de la esfera.
+    pub point: Vec3,
+    /// Normal de la superficie, apuntando desde el cuerpo hacia afuera.
+    pub normal: Vec3,
+    /// Cuánto hay que mover la esfera a lo largo de `normal` para separarla.
+    pub depth: f32,
+}
+
+/// Qué le pasa a la nave al tocar un cuerpo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionResponse {
+    /// Se detiene sobre la superficie y se queda acompañando al cuerpo.
+    Stop,
+    /// Rebota perdiendo parte de la velocidad (ver `CollisionSettings::restitution`).
+    Bounce,
+    /// Se detiene y el evento se marca como choque; el programa decide qué hacer.
+    Crash,
+}
+
+impl CollisionResponse {
+    pub fn next(self) -> Self {
+        match self {
+            CollisionResponse::Stop => CollisionResponse::Bounce,
+            CollisionResponse::Bounce => CollisionResponse::Crash,
+            CollisionResponse::Crash => CollisionResponse::Stop,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CollisionResponse::Stop => "Detener",
+            CollisionResponse::Bounce => "Rebotar",
+            CollisionResponse::Crash => "Choque",
+        }
+    }
+}
+
+pub struct CollisionSettings {
+    pub enabled: bool,
+    pub response: CollisionResponse,
+    /// Fracción de la velocidad normal que se conserva al rebotar.
+    pub restitution: f32,
+    /// Después de la prueba con esferas, probar contra los triángulos del modelo.
+    pub triangle_level: bool,
+}
+
+impl Default for CollisionSettings {
+    fn default() -> Self {
+        CollisionSettings {
+            enabled: true,
+            response: CollisionResponse::Bounce,
+            restitution: 0.6,
+            triangle_level: false,
+        }
+    }
+}
+
+/// Un impacto de la nave, para que el programa reaccione (sonido, partículas, HUD).
+/// Solo se emite cuando la nave empieza a tocar un cuerpo, no mientras sigue apoyada.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollisionEvent {
+    /// Índice del cuerpo en `Scene::bodies`.
+    pub body: usize,
+    pub point: Vec3,
+    pub normal: Vec3,
+    /// Velocidad con la que la nave se acercaba a la superficie.
+    pub impact_speed: f32,
+    pub crashed: bool,
+}
+
+/// Contacto entre dos esferas; `None` si no se tocan.
+pub fn sphere_contact(sphere: &BoundingSphere, obstacle: &BoundingSphere) -> Option<Contact> {
+    let offset = sphere.center - obstacle.center;
+    let distance = offset.magnitude();
+    let depth = sphere.radius + obstacle.radius - distance;
+    if depth <= 0.0 {
+        return None;
+    }
+
+    let normal = offset
+        .try_normalize(f32::EPSILON)
+        .unwrap_or(Vec3::new(0.0, 1.0, 0.0));
+    Some(Contact {
+        point: obstacle.center + normal * obstacle.radius,
+        normal,
+        depth,
+    })
+}
+
+/// Contacto contra una malla cerrada dada como triángulos en mundo.
+///
+/// Se usa el triángulo más cercano al centro de la esfera. Si el centro quedó dentro
+/// de la malla (por ejemplo, a mucha velocidad) también hay contacto, y la normal
+/// del triángulo saca a la esfera hacia afuera. `mesh_center` sirve para orientar las
+/// normales sin depender del sentido en que estén definidos los triángulos.
+pub fn mesh_contact(
+    sphere: &BoundingSphere,
+    triangles: &[[Vec3; 3]],
+    mesh_center: Vec3,
+) -> Option<Contact> {
+    let mut nearest: Option<(f32, Vec3, &[Vec3; 3])> = None;
+    for triangle in triangles {
+        let point = closest_point_on_triangle(sphere.center, triangle);
+        let distance = (sphere.center - point).magnitude();
+        if nearest.is_none_or(|(best, _, _)| distance < best) {
+            nearest = Some((distance, point, triangle));
+        }
+    }
+    let (distance, point, triangle) = nearest?;
+
+    let [a, b, c] = *triangle;
+    let mut face_normal = (b - a).cross(&(c - a)).try_normalize(f32::EPSILON)?;
+    if face_normal.dot(&((a + b + c) / 3.0 - mesh_center)) < 0.0 {
+        face_normal = -face_normal;
+    }
+
+    let outward = sphere.center - point;
+    if outward.dot(&face_normal) < 0.0 {
+        // El centro está del lado de adentro
+        return Some(Contact {
+            point,
+            normal: face_normal,
+            depth: sphere.radius + distance,
+        });
+    }
+    if distance >= sphere.radius {
+        return None;
+    }
+
+    Some(Contact {
+        point,
+        normal: outward.try_normalize(f32::EPSILON).unwrap_or(face_normal),
+        depth: sphere.radius - distance,
+    })
+}
+
+/// Punto del triángulo más cercano a `p` (Ericson, "Real-Time Collision Detection" 5.1.5).
+pub fn closest_point_on_triangle(p: Vec3, triangle: &[Vec3; 3]) -> Vec3 {
+    let [a, b, c] = *triangle;
+    let ab = b - a;
+    let ac = c - a;
+
+    let ap = p - a;
+    let d1 = ab.dot(&ap);
+    let d2 = ac.dot(&ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+
+    let bp = p - b;
+    let d3 = ab.dot(&bp);
+    let d4 = ac.dot(&bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = p - c;
+    let d5 = ab.dot(&cp);
+    let d6 = ac.dot(&cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    let denominator = 1.0 / (va + vb + vc);
+    a + ab * (vb * denominator) + ac * (vc * denominator)
+}
+
+/// Saca a la nave del cuerpo y ajusta su velocidad según `settings.response`.
+/// `body_velocity` es la velocidad del cuerpo, para responder al movimiento relativo.
+///
+/// Devuelve la velocidad con la que la nave se acercaba a la superficie.
+pub fn respond(
+    ship: &mut Ship,
+    contact: &Contact,
+    body_velocity: Vec3,
+    settings: &CollisionSettings,
+) -> f32 {
+    ship.position += contact.normal * contact.depth;
+
+    let relative = ship.velocity - body_velocity;
+    let approach = relative.dot(&contact.normal);
+    if approach >= 0.0 {
+        // Ya se está alejando
+        return 0.0;
+    }
+
+    ship.velocity = match settings.response {
+        CollisionResponse::Stop | CollisionResponse::Crash => body_velocity,
+        CollisionResponse::Bounce => {
+            body_velocity + relative - contact.normal * approach * (1.0 + settings.restitution)
+        }
+    };
+    -approach
+}
@@ -1,117 +1,244 @@
 use std::fmt;
-
-#[derive(Debug, Clone, Copy)]
+use std::ops::{Add, Mul};
+use std::sync::OnceLock;
+
+/// Color RGBA en luz lineal con un `f32` por canal.
+///
+/// Los canales de color pueden pasar de 1 (HDR): un material emisivo brilla más que el
+/// blanco y el tonemapping de [`Framebuffer::present`](crate::framebuffer::Framebuffer::present)
+/// decide cómo se ve. Los constructores de 8 bits (`new`, `from_hex`) reciben valores
+/// sRGB, como los de un selector de colores, y los pasan a luz lineal.
+///
+/// `a` es la opacidad, de 0 (transparente) a 1 (opaco, el valor de todos los
+/// constructores salvo [`rgba`](Color::rgba)). Las operaciones de luz (`+`, `*`, `map`)
+/// no la tocan; la usan los modos de [`BlendMode`] al mezclar con el framebuffer.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Color {
-    r: u8,
-    g: u8,
-    b: u8,
+    r: f32,
+    g: f32,
+    b: f32,
+    a: f32,
 }
 
 impl Color {
-    pub const fn new(r: u8, g: u8, b: u8) -> Self {
-        Color { r, g, b }
+    /// Color sRGB de 8 bits por canal.
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
+        Color {
+            r: srgb_to_linear(r),
+            g: srgb_to_linear(g),
+            b: srgb_to_linear(b),
+            a: 1.0,
+        }
+    }
+
+    /// Color ya en luz lineal; los canales no se limitan.
+    pub const fn rgb(r: f32, g: f32, b: f32) -> Self {
+        Color { r, g, b, a: 1.0 }
     }
 
-    pub fn get_r(&self) -> u8 {
+    /// Color en luz lineal con opacidad `a`.
+    pub const fn rgba(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Color { r, g, b, a }
+    }
+
+    /// Negro sin opacidad: un fragmento de este color se descarta.
+    pub const fn transparent() -> Self {
+        Color { r: 0.0, g: 0.0, b: 0.0, a: 0.0 }
+    }
+
+    pub fn get_r(&self) -> f32 {
         self.r
     }
 
-    pub fn get_g(&self) -> u8 {
+    pub fn get_g(&self) -> f32 {
         self.g
     }
 
-    pub fn get_b(&self) -> u8 {
+    pub fn get_b(&self) -> f32 {
         self.b
     }
 
-    pub const fn from_hex(hex: u32) -> Self {
-        let r = ((hex >> 16) & 0xFF) as u8;
-        let g = ((hex >> 8) & 0xFF) as u8;
-        let b = (hex & 0xFF) as u8;
-        Color { r, g, b }
+    pub fn get_a(&self) -> f32 {
+        self.a
+    }
+
+    /// El mismo color con opacidad `alpha`, limitada a [0, 1].
+    pub fn with_alpha(&self, alpha: f32) -> Self {
+        Color { a: alpha.clamp(0.0, 1.0), ..*self }
+    }
+
+    /// Color sRGB `0xRRGGBB`.
+    pub fn from_hex(hex: u32) -> Self {
+        Color::new((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
     }
 
     pub const fn black() -> Self {
-        Color { r: 0, g: 0, b: 0 }
+        Color { r: 0.0, g: 0.0, b: 0.0, a: 1.0 }
     }
 
+    /// `0xRRGGBB` en sRGB, recortando cada canal a [0, 1] sin tonemapping.
     pub fn to_hex(&self) -> u32 {
-        ((self.r as u32) << 16) | ((self.g as u32) << 8) | (self.b as u32)
+        let channel = |value: f32| linear_to_srgb(value) as u32;
+        (channel(self.r) << 16) | (channel(self.g) << 8) | channel(self.b)
+    }
+
+    /// Luminancia relativa (Rec. 709).
+    pub fn luminance(&self) -> f32 {
+        0.2126 * self.r + 0.7152 * self.g + 0.0722 * self.b
+    }
+
+    /// Aplica `f` a cada canal de color.
+    pub fn map(&self, f: impl Fn(f32) -> f32) -> Self {
+        Color { r: f(self.r), g: f(self.g), b: f(self.b), a: self.a }
     }
 
     // Linear interpolation between two colors
     pub fn lerp(&self, other: &Color, t: f32) -> Self {
         let t = t.clamp(0.0, 1.0);
         Color {
-            r: (self.r as f32 + (other.r as f32 - self.r as f32) * t).round() as u8,
-            g: (self.g as f32 + (other.g as f32 - self.g as f32) * t).round() as u8,
-            b: (self.b as f32 + (other.b as f32 - self.b as f32) * t).round() as u8,
+            r: self.r + (other.r - self.r) * t,
+            g: self.g + (other.g - self.g) * t,
+            b: self.b + (other.b - self.b) * t,
+            a: self.a + (other.a - self.a) * t,
         }
     }
 
     pub fn is_black(&self) -> bool {
-        self.r == 0 && self.g == 0 && self.b == 0 
+        self.r <= 0.0 && self.g <= 0.0 && self.b <= 0.0
     }
 
+    // Los modos de mezcla ponen `blend` sobre `self` según la opacidad de `blend`; el
+    // resultado conserva la opacidad de `self`.
+
+    /// `blend` encima de `self` (mezcla alfa habitual).
     pub fn blend_normal(&self, blend: &Color) -> Color {
-        if blend.is_black() { *self } else { *blend }
-      }
-    
+        Color { a: self.a, ..self.lerp(blend, blend.a) }
+    }
+
+    /// Oscurece `self` filtrándolo por `blend`, como un vidrio de color.
     pub fn blend_multiply(&self, blend: &Color) -> Color {
-        Color::new(
-            ((self.r as f32 * blend.r as f32) / 255.0) as u8,
-            ((self.g as f32 * blend.g as f32) / 255.0) as u8,
-            ((self.b as f32 * blend.b as f32) / 255.0) as u8
-        )
+        let filter = |base: f32, factor: f32| base * (1.0 + (factor - 1.0) * blend.a);
+        Color {
+            r: filter(self.r, blend.r),
+            g: filter(self.g, blend.g),
+            b: filter(self.b, blend.b),
+            a: self.a,
+        }
     }
-    
+
+    /// Suma la luz de `blend`, para brillos y resplandores.
     pub fn blend_add(&self, blend: &Color) -> Color {
-        Color::new(
-            (self.r as u16 + blend.r as u16).min(255) as u8,
-            (self.g as u16 + blend.g as u16).min(255) as u8,
-            (self.b as u16 + blend.b as u16).min(255) as u8
-        )
+        *self + *blend * blend.a
     }
-    
-    pub fn blend_subtract(&self, blend: &Color) -> Color {
-        let r = (self.r as i16 - blend.r as i16).max(0).min(255) as u8;
-        let g = (self.g as i16 - blend.g as i16).max(0).min(255) as u8;
-        let b = (self.b as i16 - blend.b as i16).max(0).min(255) as u8;
 
-        Color::new(r, g, b)
+    pub fn blend_subtract(&self, blend: &Color) -> Color {
+        let remove = |base: f32, amount: f32| (base - amount * blend.a).max(0.0);
+        Color {
+            r: remove(self.r, blend.r),
+            g: remove(self.g, blend.g),
+            b: remove(self.b, blend.b),
+            a: self.a,
+        }
     }
 }
 
-use std::ops::Add;
+/// Cómo se combina un fragmento con lo que ya hay en el framebuffer.
+///
+/// Los materiales opacos reemplazan el color y escriben la profundidad; el resto se
+/// mezcla con el modo `Color::blend_*` correspondiente y no escribe la profundidad, así
+/// que lo que está detrás debe dibujarse antes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    Opaque,
+    /// [`Color::blend_normal`]: transparencia según la opacidad.
+    Normal,
+    Multiply,
+    Add,
+    Subtract,
+}
+
+impl BlendMode {
+    pub fn is_opaque(self) -> bool {
+        self == BlendMode::Opaque
+    }
+
+    /// Color que queda al poner `source` sobre `destination`.
+    pub fn apply(self, destination: Color, source: Color) -> Color {
+        match self {
+            BlendMode::Opaque => source,
+            BlendMode::Normal => destination.blend_normal(&source),
+            BlendMode::Multiply => destination.blend_multiply(&source),
+            BlendMode::Add => destination.blend_add(&source),
+            BlendMode::Subtract => destination.blend_subtract(&source),
+        }
+    }
+}
 
 impl Add for Color {
     type Output = Color;
 
+    /// Suma la luz; la opacidad es la de `self`.
     fn add(self, other: Color) -> Color {
         Color {
-            r: self.r.saturating_add(other.r),
-            g: self.g.saturating_add(other.g),
-            b: self.b.saturating_add(other.b),
+            r: self.r + other.r,
+            g: self.g + other.g,
+            b: self.b + other.b,
+            a: self.a,
         }
     }
 }
 
-use std::ops::Mul;
-
 impl Mul<f32> for Color {
     type Output = Color;
 
+    /// Escala la intensidad sin cambiar la opacidad; un factor negativo da negro.
     fn mul(self, scalar: f32) -> Color {
+        let scalar = scalar.max(0.0);
         Color {
-            r: (self.r as f32 * scalar).clamp(0.0, 255.0) as u8,
-            g: (self.g as f32 * scalar).clamp(0.0, 255.0) as u8,
-            b: (self.b as f32 * scalar).clamp(0.0, 255.0) as u8,
+            r: self.r * scalar,
+            g: self.g * scalar,
+            b: self.b * scalar,
+            a: self.a,
         }
     }
 }
 
 impl fmt::Display for Color {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Color(r: {}, g: {}, b: {})", self.r, self.g, self.b)
+        write!(f, "Color(r: {:.3}, g: {:.3}, b: {:.3}, a: {:.3})", self.r, self.g, self.b, self.a)
     }
 }
+
+/// Pasos de la tabla de codificación sRGB; suficientes para no perder ningún valor de
+/// 8 bits en las sombras, donde la curva es más empinada.
+const ENCODE_STEPS: usize = 4096;
+
+/// Valor sRGB de 8 bits en luz lineal, de 0 a 1.
+pub fn srgb_to_linear(value: u8) -> f32 {
+    static TABLE: OnceLock<[f32; 256]> = OnceLock::new();
+    let table = TABLE.get_or_init(|| {
+        std::array::from_fn(|i| {
+            let c = i as f32 / 255.0;
+            if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+        })
+    });
+    table[value as usize]
+}
+
+/// Luz lineal a sRGB de 8 bits, recortando a [0, 1].
+pub fn linear_to_srgb(value: f32) -> u8 {
+    static TABLE: OnceLock<Vec<u8>> = OnceLock::new();
+    let table = TABLE.get_or_init(|| {
+        (0..=ENCODE_STEPS)
+            .map(|i| {
+                let c = i as f32 / ENCODE_STEPS as f32;
+                let encoded =
+                    if c <= 0.0031308 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 };
+                (encoded * 255.0).round() as u8
+            })
+            .collect()
+    });
+    // NaN también termina en 0
+    let index = (value.clamp(0.0, 1.0) * ENCODE_STEPS as f32).round() as usize;
+    table[index.min(ENCODE_STEPS)]
+}
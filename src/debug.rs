@@ -0,0 +1,166 @@
+use crate::color::Color;
+use crate::fragment::Fragment;
+use crate::framebuffer::Framebuffer;
+use crate::Uniforms;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    Shaded,
+    Wireframe,
+    Depth,
+    Normals,
+    Overdraw,
+}
+
+impl RenderMode {
+    pub fn next(self) -> Self {
+        match self {
+            RenderMode::Shaded => RenderMode::Wireframe,
+            RenderMode::Wireframe => RenderMode::Depth,
+            RenderMode::Depth => RenderMode::Normals,
+            RenderMode::Normals => RenderMode::Overdraw,
+            RenderMode::Overdraw => RenderMode::Shaded,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RenderMode::Shaded => "Shaded",
+            RenderMode::Wireframe => "Wireframe",
+            RenderMode::Depth => "Depth",
+            RenderMode::Normals => "Normals",
+            RenderMode::Overdraw => "Overdraw",
+        }
+    }
+}
+
+pub struct DebugView {
+    pub mode: RenderMode,
+    pub show_bounding_boxes: bool,
+    pub wireframe_color: Color,
+    pub bounding_box_color: Color,
+    bounding_boxes: Vec<(i32, i32, i32, i32)>,
+}
+
+impl DebugView {
+    pub fn new() -> Self {
+        DebugView {
+            mode: RenderMode::Shaded,
+            show_bounding_boxes: false,
+            wireframe_color: Color::new(255, 255, 255),
+            bounding_box_color: Color::new(255, 0, 255),
+            bounding_boxes: Vec::new(),
+        }
+    }
+
+    pub fn add_bounding_box(&mut self, bounding_box: (i32, i32, i32, i32)) {
+        if self.show_bounding_boxes {
+            self.bounding_boxes.push(bounding_box);
+        }
+    }
+
+    /// Divide entre `factor` las cajas guardadas, dibujadas en un framebuffer más
+    /// grande que el final (SSAA).
+    pub fn scale_bounding_boxes(&mut self, factor: usize) {
+        let factor = factor.max(1) as i32;
+        for (min_x, min_y, max_x, max_y) in self.bounding_boxes.iter_mut() {
+            *min_x = min_x.div_euclid(factor);
+            *min_y = min_y.div_euclid(factor);
+            *max_x = max_x.div_euclid(factor);
+            *max_y = max_y.div_euclid(factor);
+        }
+    }
+
+    /// Se llama una vez que toda la escena está en el framebuffer
+    pub fn finish_frame(&mut self, framebuffer: &mut Framebuffer) {
+        match self.mode {
+            RenderMode::Depth => visualize_depth(framebuffer),
+            RenderMode::Overdraw => visualize_overdraw(framebuffer),
+            _ => {}
+        }
+
+        let color = self.bounding_box_color.to_hex();
+        for (min_x, min_y, max_x, max_y) in self.bounding_boxes.drain(..) {
+            draw_rect_outline(framebuffer, min_x, min_y, max_x, max_y, color);
+        }
+    }
+}
+
+impl Default for DebugView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Normal en espacio de mundo mapeada de [-1, 1] a [0, 255]
+pub fn normal_shader(fragment: &Fragment, _uniforms: &Uniforms) -> Color {
+    let normal = fragment.normal;
+    let channel = |n: f32| ((n * 0.5 + 0.5).clamp(0.0, 1.0) * 255.0) as u8;
+    Color::new(channel(normal.x), channel(normal.y), channel(normal.z))
+}
+
+/// Escala de grises: blanco lo más cercano, negro lo más lejano de lo que hay en pantalla
+pub fn visualize_depth(framebuffer: &mut Framebuffer) {
+    let (mut min_depth, mut max_depth) = (f32::INFINITY, f32::NEG_INFINITY);
+    for &depth in framebuffer.zbuffer.iter().filter(|depth| depth.is_finite()) {
+        min_depth = min_depth.min(depth);
+        max_depth = max_depth.max(depth);
+    }
+    let range = (max_depth - min_depth).max(f32::EPSILON);
+    let background = framebuffer.get_background_color();
+
+    for (pixel, &depth) in framebuffer.buffer.iter_mut().zip(&framebuffer.zbuffer) {
+        *pixel = if depth.is_finite() {
+            let value = (255.0 * (1.0 - (depth - min_depth) / range)) as u8;
+            Color::new(value, value, value).to_hex()
+        } else {
+            background
+        };
+    }
+}
+
+/// Mapa de calor: negro sin fragmentos, azul 1, verde 2, amarillo 3, rojo 4 o más
+pub fn visualize_overdraw(framebuffer: &mut Framebuffer) {
+    let heat = [
+        Color::new(0, 0, 0),
+        Color::new(0, 0, 255),
+        Color::new(0, 255, 0),
+        Color::new(255, 255, 0),
+        Color::new(255, 0, 0),
+    ];
+
+    for (pixel, &count) in framebuffer.buffer.iter_mut().zip(&framebuffer.overdraw) {
+        let index = (count as usize).min(heat.len() - 1);
+        *pixel = heat[index].to_hex();
+    }
+}
+
+fn draw_rect_outline(
+    framebuffer: &mut Framebuffer,
+    min_x: i32,
+    min_y: i32,
+    max_x: i32,
+    max_y: i32,
+    color: u32,
+) {
+    let width = framebuffer.width as i32;
+    let height = framebuffer.height as i32;
+    if max_x < 0 || max_y < 0 || min_x >= width || min_y >= height {
+        return;
+    }
+
+    let mut plot = |x: i32, y: i32| {
+        if x >= 0 && y >= 0 && x < width && y < height {
+            framebuffer.buffer[y as usize * framebuffer.width + x as usize] = color;
+        }
+    };
+
+    for x in min_x.max(0)..=max_x.min(width - 1) {
+        plot(x, min_y);
+        plot(x, max_y);
+    }
+    for y in min_y.max(0)..=max_y.min(height - 1) {
+        plot(min_x, y);
+        plot(max_x, y);
+    }
+}
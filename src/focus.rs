@@ -0,0 +1,101 @@
+use nalgebra_glm::Vec3;
+use crate::camera::Camera;
+
+/// Hace que la cámara de órbita siga a un cuerpo: cada cuadro `center` se mueve a la
+/// posición del cuerpo y `eye` lo acompaña, conservando el ángulo elegido por el usuario.
+///
+/// Al cambiar de objetivo la cámara viaja al nuevo cuerpo con una interpolación suavizada
+/// de `eye` y `center` en lugar de saltar.
+pub struct Focus {
+    pub target: Option<usize>,
+    /// Duración del viaje entre objetivos, en segundos.
+    pub transition_time: f32,
+    /// Distancia de la cámara al cuerpo, en múltiplos de su radio.
+    pub distance_factor: f32,
+    transition: Option<Transition>,
+}
+
+struct Transition {
+    from_eye: Vec3,
+    from_center: Vec3,
+    /// `eye - center` al terminar el viaje.
+    offset: Vec3,
+    progress: f32,
+}
+
+impl Default for Focus {
+    fn default() -> Self {
+        Focus::new()
+    }
+}
+
+impl Focus {
+    pub fn new() -> Self {
+        Focus {
+            target: None,
+            transition_time: 1.5,
+            distance_factor: 4.0,
+            transition: None,
+        }
+    }
+
+    /// Cambia de objetivo; `radius` es el radio en mundo del nuevo cuerpo.
+    /// Con `None` la cámara se queda donde está y deja de seguir.
+    pub fn set_target(&mut self, target: Option<usize>, camera: &Camera, radius: f32) {
+        self.target = target;
+        self.transition = target.map(|_| {
+            // Se conserva la dirección desde la que se mira
+            let direction = (camera.eye - camera.center)
+                .try_normalize(f32::EPSILON)
+                .unwrap_or(Vec3::new(0.0, 0.0, 1.0));
+            Transition {
+                from_eye: camera.eye,
+                from_center: camera.center,
+                offset: direction * radius * self.distance_factor,
+                progress: 0.0,
+            }
+        });
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// Mueve la cámara hacia `target_center`, la posición actual del objetivo.
+    pub fn update(&mut self, camera: &mut Camera, target_center: Vec3, dt: f32) {
+        if self.target.is_none() {
+            return;
+        }
+
+        match &mut self.transition {
+            Some(transition) => {
+                transition.progress += dt / self.transition_time.max(f32::EPSILON);
+                let t = ease_in_out(transition.progress.min(1.0));
+                camera.center = transition.from_center.lerp(&target_center, t);
+                camera.eye = transition
+                    .from_eye
+                    .lerp(&(target_center + transition.offset), t);
+                if transition.progress >= 1.0 {
+                    self.transition = None;
+                }
+            }
+            None => {
+                // El usuario puede seguir orbitando y haciendo zoom alrededor del cuerpo
+                let offset = camera.eye - camera.center;
+                camera.center = target_center;
+                camera.eye = target_center + offset;
+            }
+        }
+        camera.has_changed = true;
+    }
+}
+
+/// Curva cúbica que acelera al inicio y frena al final; `t` va de 0 a 1.
+pub fn ease_in_out(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+    }
+}
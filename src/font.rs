@@ -0,0 +1,250 @@
+use crate::framebuffer::Framebuffer;
+
+/// Ancho de cada glifo en píxeles.
+pub const GLYPH_WIDTH: usize = 5;
+/// Alto de cada glifo en píxeles.
+pub const GLYPH_HEIGHT: usize = 7;
+/// Distancia horizontal entre el inicio de dos caracteres.
+pub const CHAR_ADVANCE: usize = GLYPH_WIDTH + 1;
+/// Distancia vertical entre dos líneas de texto.
+pub const LINE_HEIGHT: usize = GLYPH_HEIGHT + 3;
+
+/// Cómo se dibuja un texto: color, opacidad y tamaño.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextStyle {
+    pub color: u32,
+    /// 1 es opaco y 0 invisible; en medio se mezcla con lo que ya está dibujado.
+    pub alpha: f32,
+    /// Cada píxel del glifo se dibuja como un cuadrado de `size` x `size`.
+    pub size: usize,
+}
+
+impl TextStyle {
+    /// Texto opaco del tamaño base.
+    pub fn new(color: u32) -> Self {
+        TextStyle {
+            color,
+            alpha: 1.0,
+            size: 1,
+        }
+    }
+
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn with_size(mut self, size: usize) -> Self {
+        self.size = size.max(1);
+        self
+    }
+}
+
+/// Dibuja `text` opaco y del tamaño base; ver [`draw_styled_text`].
+pub fn draw_text(framebuffer: &mut Framebuffer, x: i32, y: i32, text: &str, color: u32) {
+    draw_styled_text(framebuffer, x, y, text, &TextStyle::new(color));
+}
+
+/// Dibuja `text` con la esquina superior izquierda en `(x, y)`, encima de lo que ya
+/// haya en `buffer` y sin tocar el z-buffer. `\n` empieza una línea nueva; los
+/// caracteres sin glifo se dibujan como `?`.
+pub fn draw_styled_text(
+    framebuffer: &mut Framebuffer,
+    x: i32,
+    y: i32,
+    text: &str,
+    style: &TextStyle,
+) {
+    if style.alpha <= 0.0 {
+        return;
+    }
+    let size = style.size.max(1) as i32;
+    let mut cursor_x = x;
+    let mut cursor_y = y;
+    for ch in text.chars() {
+        if ch == '\n' {
+            cursor_x = x;
+            cursor_y += LINE_HEIGHT as i32 * size;
+            continue;
+        }
+        draw_glyph(framebuffer, cursor_x, cursor_y, glyph(ch), style);
+        cursor_x += CHAR_ADVANCE as i32 * size;
+    }
+}
+
+/// Ancho en píxeles de la línea más larga de `text` en el tamaño base.
+pub fn text_width(text: &str) -> usize {
+    text.lines()
+        .map(|line| line.chars().count() * CHAR_ADVANCE)
+        .max()
+        .unwrap_or(0)
+        .saturating_sub(CHAR_ADVANCE - GLYPH_WIDTH)
+}
+
+/// Ancho y alto en píxeles que ocupa `text` dibujado con tamaño `size`.
+pub fn text_size(text: &str, size: usize) -> (usize, usize) {
+    let lines = text.split('\n').count();
+    let height = (lines * LINE_HEIGHT).saturating_sub(LINE_HEIGHT - GLYPH_HEIGHT);
+    (text_width(text) * size, height * size)
+}
+
+fn draw_glyph(
+    framebuffer: &mut Framebuffer,
+    x: i32,
+    y: i32,
+    rows: &[u8; GLYPH_HEIGHT],
+    style: &TextStyle,
+) {
+    let size = style.size.max(1) as i32;
+    for (row, bits) in rows.iter().enumerate() {
+        for column in 0..GLYPH_WIDTH {
+            if bits & (1 << (GLYPH_WIDTH - 1 - column)) == 0 {
+                continue;
+            }
+            // Cada bit se agranda a un bloque de `size` x `size` píxeles
+            for dy in 0..size {
+                let py = y + row as i32 * size + dy;
+                for dx in 0..size {
+                    let px = x + column as i32 * size + dx;
+                    if px < 0 || py < 0 {
+                        continue;
+                    }
+                    framebuffer.blend_pixel(px as usize, py as usize, style.color, style.alpha);
+                }
+            }
+        }
+    }
+}
+
+fn glyph(ch: char) -> &'static [u8; GLYPH_HEIGHT] {
+    if (' '..='~').contains(&ch) {
+        return &ASCII[ch as usize - ' ' as usize];
+    }
+    EXTRA
+        .iter()
+        .find(|(extra, _)| *extra == ch)
+        .map(|(_, rows)| rows)
+        .unwrap_or(&ASCII['?' as usize - ' ' as usize])
+}
+
+// Cada glifo son 7 filas de 5 bits, el bit más alto es la columna izquierda
+#[rustfmt::skip]
+const ASCII: [[u8; GLYPH_HEIGHT]; 95] = [
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000], // espacio
+    [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100], // !
+    [0b01010, 0b01010, 0b01010, 0b00000, 0b00000, 0b00000, 0b00000], // "
+    [0b01010, 0b01010, 0b11111, 0b01010, 0b11111, 0b01010, 0b01010], // #
+    [0b00100, 0b01111, 0b10100, 0b01110, 0b00101, 0b11110, 0b00100], // $
+    [0b11000, 0b11001, 0b00010, 0b00100, 0b01000, 0b10011, 0b00011], // %
+    [0b01100, 0b10010, 0b10100, 0b01000, 0b10101, 0b10010, 0b01101], // &
+    [0b00100, 0b00100, 0b01000, 0b00000, 0b00000, 0b00000, 0b00000], // '
+    [0b00010, 0b00100, 0b01000, 0b01000, 0b01000, 0b00100, 0b00010], // (
+    [0b01000, 0b00100, 0b00010, 0b00010, 0b00010, 0b00100, 0b01000], // )
+    [0b00000, 0b00100, 0b10101, 0b01110, 0b10101, 0b00100, 0b00000], // *
+    [0b00000, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0b00000], // +
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b00100, 0b01000], // ,
+    [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000], // -
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100], // .
+    [0b00000, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000], // /
+    [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110], // 0
+    [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110], // 1
+    [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111], // 2
+    [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110], // 3
+    [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010], // 4
+    [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110], // 5
+    [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110], // 6
+    [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000], // 7
+    [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110], // 8
+    [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100], // 9
+    [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000], // :
+    [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b00100, 0b01000], // ;
+    [0b00010, 0b00100, 0b01000, 0b10000, 0b01000, 0b00100, 0b00010], // <
+    [0b00000, 0b00000, 0b11111, 0b00000, 0b11111, 0b00000, 0b00000], // =
+    [0b01000, 0b00100, 0b00010, 0b00001, 0b00010, 0b00100, 0b01000], // >
+    [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100], // ?
+    [0b01110, 0b10001, 0b00001, 0b01101, 0b10101, 0b10101, 0b01110], // @
+    [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001], // A
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110], // B
+    [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110], // C
+    [0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100], // D
+    [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111], // E
+    [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000], // F
+    [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111], // G
+    [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001], // H
+    [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110], // I
+    [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100], // J
+    [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001], // K
+    [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111], // L
+    [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001], // M
+    [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001], // N
+    [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110], // O
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000], // P
+    [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101], // Q
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001], // R
+    [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110], // S
+    [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100], // T
+    [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110], // U
+    [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100], // V
+    [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010], // W
+    [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001], // X
+    [0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100], // Y
+    [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111], // Z
+    [0b01110, 0b01000, 0b01000, 0b01000, 0b01000, 0b01000, 0b01110], // [
+    [0b00000, 0b10000, 0b01000, 0b00100, 0b00010, 0b00001, 0b00000], // \
+    [0b01110, 0b00010, 0b00010, 0b00010, 0b00010, 0b00010, 0b01110], // ]
+    [0b00100, 0b01010, 0b10001, 0b00000, 0b00000, 0b00000, 0b00000], // ^
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b11111], // _
+    [0b01000, 0b00100, 0b00010, 0b00000, 0b00000, 0b00000, 0b00000], // `
+    [0b00000, 0b00000, 0b01110, 0b00001, 0b01111, 0b10001, 0b01111], // a
+    [0b10000, 0b10000, 0b10110, 0b11001, 0b10001, 0b10001, 0b11110], // b
+    [0b00000, 0b00000, 0b01110, 0b10000, 0b10000, 0b10001, 0b01110], // c
+    [0b00001, 0b00001, 0b01101, 0b10011, 0b10001, 0b10001, 0b01111], // d
+    [0b00000, 0b00000, 0b01110, 0b10001, 0b11111, 0b10000, 0b01110], // e
+    [0b00110, 0b01001, 0b01000, 0b11100, 0b01000, 0b01000, 0b01000], // f
+    [0b00000, 0b01111, 0b10001, 0b10001, 0b01111, 0b00001, 0b01110], // g
+    [0b10000, 0b10000, 0b10110, 0b11001, 0b10001, 0b10001, 0b10001], // h
+    [0b00100, 0b00000, 0b01100, 0b00100, 0b00100, 0b00100, 0b01110], // i
+    [0b00010, 0b00000, 0b00110, 0b00010, 0b00010, 0b10010, 0b01100], // j
+    [0b10000, 0b10000, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010], // k
+    [0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110], // l
+    [0b00000, 0b00000, 0b11010, 0b10101, 0b10101, 0b10001, 0b10001], // m
+    [0b00000, 0b00000, 0b10110, 0b11001, 0b10001, 0b10001, 0b10001], // n
+    [0b00000, 0b00000, 0b01110, 0b10001, 0b10001, 0b10001, 0b01110], // o
+    [0b00000, 0b00000, 0b11110, 0b10001, 0b11110, 0b10000, 0b10000], // p
+    [0b00000, 0b00000, 0b01101, 0b10011, 0b01111, 0b00001, 0b00001], // q
+    [0b00000, 0b00000, 0b10110, 0b11001, 0b10000, 0b10000, 0b10000], // r
+    [0b00000, 0b00000, 0b01110, 0b10000, 0b01110, 0b00001, 0b11110], // s
+    [0b01000, 0b01000, 0b11100, 0b01000, 0b01000, 0b01001, 0b00110], // t
+    [0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b10011, 0b01101], // u
+    [0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100], // v
+    [0b00000, 0b00000, 0b10001, 0b10001, 0b10101, 0b10101, 0b01010], // w
+    [0b00000, 0b00000, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001], // x
+    [0b00000, 0b00000, 0b10001, 0b10001, 0b01111, 0b00001, 0b01110], // y
+    [0b00000, 0b00000, 0b11111, 0b00010, 0b00100, 0b01000, 0b11111], // z
+    [0b00010, 0b00100, 0b00100, 0b01000, 0b00100, 0b00100, 0b00010], // {
+    [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100], // |
+    [0b01000, 0b00100, 0b00100, 0b00010, 0b00100, 0b00100, 0b01000], // }
+    [0b00000, 0b00000, 0b01000, 0b10101, 0b00010, 0b00000, 0b00000], // ~
+];
+
+// Letras del español y símbolos que no están en ASCII
+#[rustfmt::skip]
+const EXTRA: [(char, [u8; GLYPH_HEIGHT]); 17] = [
+    ('á', [0b00010, 0b00100, 0b01110, 0b00001, 0b01111, 0b10001, 0b01111]),
+    ('é', [0b00010, 0b00100, 0b01110, 0b10001, 0b11111, 0b10000, 0b01110]),
+    ('í', [0b00010, 0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('ó', [0b00010, 0b00100, 0b01110, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('ú', [0b00010, 0b00100, 0b10001, 0b10001, 0b10001, 0b10011, 0b01101]),
+    ('ñ', [0b01010, 0b10100, 0b10110, 0b11001, 0b10001, 0b10001, 0b10001]),
+    ('ü', [0b01010, 0b00000, 0b10001, 0b10001, 0b10001, 0b10011, 0b01101]),
+    ('Á', [0b00010, 0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001]),
+    ('É', [0b00010, 0b11111, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111]),
+    ('Í', [0b00010, 0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('Ó', [0b00010, 0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('Ú', [0b00010, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('Ñ', [0b01010, 0b10100, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001]),
+    ('¿', [0b00100, 0b00000, 0b00100, 0b01000, 0b10000, 0b10001, 0b01110]),
+    ('¡', [0b00100, 0b00000, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100]),
+    ('°', [0b01100, 0b10010, 0b10010, 0b01100, 0b00000, 0b00000, 0b00000]),
+    ('·', [0b00000, 0b00000, 0b00000, 0b00100, 0b00000, 0b00000, 0b00000]),
+];
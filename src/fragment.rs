@@ -1,7 +1,12 @@
 
 use nalgebra_glm::{Vec2, Vec3};
 use crate::color::Color;
+use crate::framebuffer::MAX_SAMPLES;
 
+/// Muestra producida al rasterizar, antes de pasar por el fragment shader.
+///
+/// `vertex_position` es la posición interpolada en espacio de modelo, que usan los shaders
+/// procedurales; `normal` está en espacio de mundo.
 pub struct Fragment {
     pub position: Vec2,
     pub color: Color,
@@ -9,6 +14,10 @@ pub struct Fragment {
     pub normal: Vec3,
     pub intensity: f32,
     pub vertex_position: Vec3,
+    /// Muestras del píxel que cubre el triángulo, un bit por muestra (MSAA).
+    pub coverage: u32,
+    /// Profundidad en cada muestra del píxel.
+    pub sample_depths: [f32; MAX_SAMPLES],
 }
 
 impl Fragment {
@@ -19,7 +28,9 @@ impl Fragment {
             depth,
             normal,
             intensity,
-            vertex_position
+            vertex_position,
+            coverage: 1,
+            sample_depths: [depth; MAX_SAMPLES],
         }
     }
 }
\ No newline at end of file
@@ -1,50 +1,250 @@
+use crate::color::{BlendMode, Color};
+use crate::tonemap::ToneMapping;
 
+/// Máximo de muestras por píxel con MSAA.
+pub const MAX_SAMPLES: usize = 4;
+
+/// Posiciones de las muestras dentro del píxel, en una grilla rotada que cubre bien
+/// tanto los bordes casi horizontales como los casi verticales.
+const MSAA_PATTERN: [(f32, f32); MAX_SAMPLES] =
+    [(0.375, 0.125), (0.875, 0.375), (0.125, 0.625), (0.625, 0.875)];
+
+/// Destino del renderizado: un color HDR en luz lineal y una profundidad por píxel.
+///
+/// La escena se dibuja en `color`; [`present`](Framebuffer::present) la comprime con
+/// tonemapping y la codifica en sRGB `0xRRGGBB` en `buffer`, que se puede pasar
+/// directo a `minifb::Window::update_with_buffer`. Las capas 2D (texto, HUD, ayuda)
+/// se dibujan después, directamente en `buffer`.
+///
+/// Con varias muestras por píxel (MSAA, ver [`set_samples`](Framebuffer::set_samples))
+/// cada muestra guarda su color y su profundidad, y
+/// [`resolve`](Framebuffer::resolve) las promedia en `color` y `zbuffer`.
 pub struct Framebuffer {
     pub width: usize,
     pub height: usize,
     pub buffer: Vec<u32>,
+    /// Imagen de la escena en luz lineal, sin límite de brillo.
+    pub color: Vec<Color>,
     pub zbuffer: Vec<f32>,
+    pub overdraw: Vec<u32>,
     background_color: u32,
-    current_color: u32,
+    current_color: Color,
+    blend_mode: BlendMode,
+    samples: usize,
+    sample_color: Vec<Color>,
+    sample_depth: Vec<f32>,
 }
 
 impl Framebuffer {
+    /// Crea un framebuffer negro con el z-buffer en infinito.
     pub fn new(width: usize, height: usize) -> Self {
         Framebuffer {
             width,
             height,
             buffer: vec![0; width * height],
+            color: vec![Color::black(); width * height],
             zbuffer: vec![f32::INFINITY; width * height],
+            overdraw: vec![0; width * height],
             background_color: 0x000000,
-            current_color: 0xFFFFFF
+            current_color: Color::rgb(1.0, 1.0, 1.0),
+            blend_mode: BlendMode::Opaque,
+            samples: 1,
+            sample_color: Vec::new(),
+            sample_depth: Vec::new(),
+        }
+    }
+
+    /// Muestras por píxel: 1 (sin MSAA) o [`MAX_SAMPLES`]; otros valores se redondean
+    /// a uno de los dos. Las muestras empiezan vacías hasta el próximo `clear`.
+    pub fn set_samples(&mut self, samples: usize) {
+        let samples = if samples > 1 { MAX_SAMPLES } else { 1 };
+        if samples == self.samples {
+            return;
         }
+        self.samples = samples;
+        let count = if samples > 1 { self.width * self.height * samples } else { 0 };
+        self.sample_color = vec![Color::black(); count];
+        self.sample_depth = vec![f32::INFINITY; count];
+    }
+
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Dónde se prueba la cobertura dentro de cada píxel, desde su esquina superior
+    /// izquierda.
+    pub fn sample_positions(&self) -> &'static [(f32, f32)] {
+        if self.samples > 1 { &MSAA_PATTERN } else { &[(0.5, 0.5)] }
     }
 
+    /// Llena con el color de fondo y reinicia profundidad y conteo de sobredibujado.
     pub fn clear(&mut self) {
         for pixel in self.buffer.iter_mut() {
             *pixel = self.background_color;
         }
+        let background = Color::from_hex(self.background_color);
+        for pixel in self.color.iter_mut() {
+            *pixel = background;
+        }
         for depth in self.zbuffer.iter_mut() {
             *depth = f32::INFINITY;
         }
+        for count in self.overdraw.iter_mut() {
+            *count = 0;
+        }
+        for pixel in self.sample_color.iter_mut() {
+            *pixel = background;
+        }
+        for depth in self.sample_depth.iter_mut() {
+            *depth = f32::INFINITY;
+        }
     }
 
+    /// Escribe el color actual en la imagen HDR en `(x, y)` si `depth` está más cerca que
+    /// lo ya dibujado, combinándolo según el modo de mezcla actual. Con MSAA cubre todas
+    /// las muestras del píxel.
     pub fn point(&mut self, x: usize, y: usize, depth: f32) {
+        if self.samples > 1 {
+            self.point_samples(x, y, u32::MAX, &[depth; MAX_SAMPLES]);
+            return;
+        }
         if x < self.width && y < self.height {
             let index = y * self.width + x;
+            self.overdraw[index] += 1;
 
             if self.zbuffer[index] > depth {
-                self.buffer[index] = self.current_color;
-                self.zbuffer[index] = depth;
+                self.color[index] = self.blend_mode.apply(self.color[index], self.current_color);
+                if self.blend_mode.is_opaque() {
+                    self.zbuffer[index] = depth;
+                }
             }
         }
     }
 
+    /// Escribe el color actual en las muestras de `(x, y)` marcadas en `coverage` (un
+    /// bit por muestra), cada una con su profundidad de `depths`. Sin MSAA solo cuenta
+    /// la muestra 0, en el centro del píxel.
+    pub fn point_samples(&mut self, x: usize, y: usize, coverage: u32, depths: &[f32]) {
+        if self.samples == 1 {
+            if coverage & 1 != 0 {
+                self.point(x, y, depths[0]);
+            }
+            return;
+        }
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let pixel = y * self.width + x;
+        self.overdraw[pixel] += 1;
+        for (sample, &depth) in depths.iter().enumerate().take(self.samples) {
+            let index = pixel * self.samples + sample;
+            if coverage & (1 << sample) != 0 && self.sample_depth[index] > depth {
+                self.sample_color[index] =
+                    self.blend_mode.apply(self.sample_color[index], self.current_color);
+                if self.blend_mode.is_opaque() {
+                    self.sample_depth[index] = depth;
+                }
+            }
+        }
+    }
+
+    /// Junta las muestras de cada píxel en `color` y `zbuffer` (la más cercana). No
+    /// hace nada sin MSAA.
+    pub fn resolve(&mut self) {
+        if self.samples == 1 {
+            return;
+        }
+        let samples = self.samples;
+        for (pixel, (colors, depths)) in self
+            .sample_color
+            .chunks_exact(samples)
+            .zip(self.sample_depth.chunks_exact(samples))
+            .enumerate()
+        {
+            self.color[pixel] = resolve_average(colors);
+            self.zbuffer[pixel] = depths.iter().copied().fold(f32::INFINITY, f32::min);
+        }
+    }
+
+    /// Lleva la imagen HDR a `buffer`: exposición y tonemapping, y luego sRGB.
+    pub fn present(&mut self, tone_mapping: &ToneMapping) {
+        for (pixel, color) in self.buffer.iter_mut().zip(&self.color) {
+            *pixel = tone_mapping.map(*color).to_hex();
+        }
+    }
+
+    /// Mezcla `color` con lo que hay en `(x, y)` según `alpha` (1 opaco, 0 invisible),
+    /// sin tocar el z-buffer. Los puntos fuera de la imagen se ignoran.
+    pub fn blend_pixel(&mut self, x: usize, y: usize, color: u32, alpha: f32) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let pixel = &mut self.buffer[y * self.width + x];
+        let alpha = alpha.clamp(0.0, 1.0);
+        if alpha >= 1.0 {
+            *pixel = color;
+            return;
+        }
+        let mix = |shift: u32| {
+            let source = ((color >> shift) & 0xFF) as f32;
+            let destination = ((*pixel >> shift) & 0xFF) as f32;
+            ((source * alpha + destination * (1.0 - alpha)).round() as u32) << shift
+        };
+        *pixel = mix(16) | mix(8) | mix(0);
+    }
+
+    pub fn get_background_color(&self) -> u32 {
+        self.background_color
+    }
+
     pub fn set_background_color(&mut self, color: u32) {
         self.background_color = color;
     }
 
-    pub fn set_current_color(&mut self, color: u32) {
+    pub fn set_current_color(&mut self, color: Color) {
         self.current_color = color;
     }
+
+    /// Cómo se combinan los próximos puntos con lo ya dibujado; con cualquier modo que no
+    /// sea [`BlendMode::Opaque`] no se escribe la profundidad.
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    /// Guarda `buffer` como imagen; el formato se deduce de la extensión de `path`.
+    pub fn save_png(&self, path: &str) -> image::ImageResult<()> {
+        let mut bytes = Vec::with_capacity(self.width * self.height * 3);
+        for pixel in &self.buffer {
+            bytes.push(((pixel >> 16) & 0xFF) as u8);
+            bytes.push(((pixel >> 8) & 0xFF) as u8);
+            bytes.push((pixel & 0xFF) as u8);
+        }
+        image::save_buffer(
+            path,
+            &bytes,
+            self.width as u32,
+            self.height as u32,
+            image::ColorType::Rgb8,
+        )
+    }
+}
+
+/// Promedio de muestras HDR que pesa cada una por `1 / (1 + luminancia)`.
+///
+/// Con un promedio simple, un borde entre el Sol (varias veces el blanco) y el espacio
+/// negro sigue viéndose blanco con una sola muestra cubierta, y el escalonado vuelve
+/// tras el tonemapping. Así el borde se degrada como si se promediara después.
+pub fn resolve_average(samples: &[Color]) -> Color {
+    let mut sum = Color::black();
+    let mut total = 0.0;
+    for sample in samples {
+        let weight = 1.0 / (1.0 + sample.luminance().max(0.0));
+        sum = sum + *sample * weight;
+        total += weight;
+    }
+    if total > 0.0 { sum * (1.0 / total) } else { sum }
 }
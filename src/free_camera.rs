@@ -0,0 +1,106 @@
+use nalgebra_glm::{
+    mat3_to_quat, quat_angle_axis, quat_normalize, quat_rotate_vec3, Mat3, Quat, Vec3,
+};
+use crate::camera::Camera;
+use crate::collision::Contact;
+
+/// Cámara de vuelo libre con seis grados de libertad.
+///
+/// La orientación es un cuaternión, así que no hay gimbal lock: se puede pasar por
+/// los polos y girar sobre el eje de vista (roll). En espacio local `-z` es el frente,
+/// `+y` arriba y `+x` la derecha.
+pub struct FreeCamera {
+    pub position: Vec3,
+    pub orientation: Quat,
+    pub velocity: Vec3,
+    /// Velocidad angular en espacio local (pitch, yaw, roll) en radianes por segundo.
+    pub angular_velocity: Vec3,
+    /// Aceleración lineal al mantener una tecla de movimiento.
+    pub acceleration: f32,
+    /// Aceleración angular al mantener una tecla de giro.
+    pub angular_acceleration: f32,
+    /// Fracción de la velocidad que se pierde por segundo (0 = sin frenado).
+    pub damping: f32,
+    pub angular_damping: f32,
+}
+
+impl FreeCamera {
+    pub fn new(position: Vec3, orientation: Quat) -> Self {
+        FreeCamera {
+            position,
+            orientation,
+            velocity: Vec3::new(0.0, 0.0, 0.0),
+            angular_velocity: Vec3::new(0.0, 0.0, 0.0),
+            acceleration: 20.0,
+            angular_acceleration: 6.0,
+            damping: 2.0,
+            angular_damping: 6.0,
+        }
+    }
+
+    /// Toma la posición y dirección de vista de otra cámara.
+    pub fn from_camera(camera: &Camera) -> Self {
+        let forward = camera.forward();
+        let right = forward.cross(&camera.up).normalize();
+        let up = right.cross(&forward);
+        let basis = Mat3::from_columns(&[right, up, -forward]);
+        FreeCamera::new(camera.eye, quat_normalize(&mat3_to_quat(&basis)))
+    }
+
+    pub fn forward(&self) -> Vec3 {
+        quat_rotate_vec3(&self.orientation, &Vec3::new(0.0, 0.0, -1.0))
+    }
+
+    pub fn right(&self) -> Vec3 {
+        quat_rotate_vec3(&self.orientation, &Vec3::new(1.0, 0.0, 0.0))
+    }
+
+    pub fn up(&self) -> Vec3 {
+        quat_rotate_vec3(&self.orientation, &Vec3::new(0.0, 1.0, 0.0))
+    }
+
+    /// Integra un paso de `dt` segundos.
+    ///
+    /// `thrust` es el empuje local (x derecha, y arriba, z adelante) y `torque` el giro
+    /// local (x pitch, y yaw, z roll), ambos con componentes entre -1 y 1.
+    pub fn update(&mut self, thrust: Vec3, torque: Vec3, dt: f32) {
+        let local_thrust = Vec3::new(thrust.x, thrust.y, -thrust.z);
+        self.velocity += quat_rotate_vec3(&self.orientation, &local_thrust) * self.acceleration * dt;
+        self.velocity *= (-self.damping * dt).exp();
+        self.position += self.velocity * dt;
+
+        self.angular_velocity += torque * self.angular_acceleration * dt;
+        self.angular_velocity *= (-self.angular_damping * dt).exp();
+
+        // Los giros se aplican en el marco local de la cámara
+        let delta = self.angular_velocity * dt;
+        let pitch = quat_angle_axis(delta.x, &Vec3::new(1.0, 0.0, 0.0));
+        let yaw = quat_angle_axis(delta.y, &Vec3::new(0.0, 1.0, 0.0));
+        let roll = quat_angle_axis(delta.z, &Vec3::new(0.0, 0.0, 1.0));
+        self.orientation = quat_normalize(&(self.orientation * yaw * pitch * roll));
+    }
+
+    /// Gira la cámara directamente (por ejemplo con el mouse), sin inercia.
+    pub fn look(&mut self, delta_yaw: f32, delta_pitch: f32) {
+        let yaw = quat_angle_axis(delta_yaw, &Vec3::new(0.0, 1.0, 0.0));
+        let pitch = quat_angle_axis(delta_pitch, &Vec3::new(1.0, 0.0, 0.0));
+        self.orientation = quat_normalize(&(self.orientation * yaw * pitch));
+    }
+
+    /// Saca la cámara de un cuerpo y anula la velocidad que la llevaba hacia adentro.
+    pub fn push_out(&mut self, contact: &Contact) {
+        self.position += contact.normal * contact.depth;
+        let approach = self.velocity.dot(&contact.normal);
+        if approach < 0.0 {
+            self.velocity -= contact.normal * approach;
+        }
+    }
+
+    /// Copia la vista a `camera` para renderizar con ella.
+    pub fn apply_to(&self, camera: &mut Camera) {
+        camera.eye = self.position;
+        camera.center = self.position + self.forward();
+        camera.up = self.up();
+        camera.has_changed = true;
+    }
+}
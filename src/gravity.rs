@@ -0,0 +1,49 @@
+use nalgebra_glm::Vec3;
+
+/// Aceleración gravitatoria en `offset` (posición relativa al centro del cuerpo) para
+/// un cuerpo con parámetro gravitacional `mu` (G·M, en unidades³/s²).
+pub fn acceleration(mu: f32, offset: Vec3) -> Vec3 {
+    let distance_squared = offset.magnitude_squared().max(1e-6);
+    -offset * (mu / (distance_squared * distance_squared.sqrt()))
+}
+
+/// Velocidad de una órbita circular en `offset`, en el plano que ya define `velocity`
+/// y en su mismo sentido. Si la velocidad es casi radial se usa el plano horizontal.
+pub fn circular_velocity(mu: f32, offset: Vec3, velocity: Vec3) -> Vec3 {
+    let radius = offset.magnitude().max(1e-3);
+    let normal = offset
+        .cross(&velocity)
+        .try_normalize(1e-4)
+        .or_else(|| offset.cross(&Vec3::new(0.0, 1.0, 0.0)).try_normalize(1e-4))
+        .unwrap_or(Vec3::new(1.0, 0.0, 0.0));
+    let tangent = normal.cross(&offset).normalize();
+    tangent * (mu / radius).sqrt()
+}
+
+/// Trayectoria futura relativa al cuerpo, integrando solo su gravedad con pasos de
+/// `step` segundos. Se corta si toca la superficie (`surface_radius`).
+pub fn predict_trajectory(
+    offset: Vec3,
+    velocity: Vec3,
+    mu: f32,
+    surface_radius: f32,
+    step: f32,
+    steps: usize,
+) -> Vec<Vec3> {
+    let mut position = offset;
+    let mut velocity = velocity;
+    let mut points = Vec::with_capacity(steps + 1);
+    points.push(position);
+
+    for _ in 0..steps {
+        // Euler semi-implícito, el mismo integrador que usa la nave
+        velocity += acceleration(mu, position) * step;
+        position += velocity * step;
+        points.push(position);
+        if position.magnitude() <= surface_radius {
+            break;
+        }
+    }
+
+    points
+}
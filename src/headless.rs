@@ -0,0 +1,203 @@
+use nalgebra_glm::Vec3;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+use crate::antialias::AntiAliasing;
+use crate::camera::Camera;
+use crate::framebuffer::Framebuffer;
+use crate::recorder::Recorder;
+use crate::scene::Scene;
+use crate::tonemap::{ToneMapper, ToneMapping};
+
+pub struct HeadlessOptions {
+    pub frames: u32,
+    pub time: u32,
+    pub step: u32,
+    pub width: usize,
+    pub height: usize,
+    pub output: String,
+    pub tone_mapping: ToneMapping,
+    pub anti_aliasing: AntiAliasing,
+    /// En vez de grabar, dibuja los cuadros con cada modo de antialiasing y compara
+    /// tiempos.
+    pub compare_anti_aliasing: bool,
+    /// Semilla de un sistema inventado para dibujar en lugar del Sistema Solar.
+    pub system_seed: Option<u32>,
+}
+
+impl Default for HeadlessOptions {
+    fn default() -> Self {
+        HeadlessOptions {
+            frames: 1,
+            time: 0,
+            step: 1,
+            width: 800,
+            height: 600,
+            output: String::from("frames"),
+            tone_mapping: ToneMapping::new(),
+            anti_aliasing: AntiAliasing::Off,
+            compare_anti_aliasing: false,
+            system_seed: None,
+        }
+    }
+}
+
+impl HeadlessOptions {
+    /// Devuelve `Ok(None)` si no se pidió el modo sin ventana
+    pub fn from_args(args: &[String]) -> Result<Option<Self>, String> {
+        if !args.iter().any(|arg| arg == "--headless") {
+            return Ok(None);
+        }
+
+        let mut options = HeadlessOptions::default();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let mut value = || {
+                iter.next()
+                    .ok_or_else(|| format!("Missing value for {}", arg))
+            };
+            match arg.as_str() {
+                "--headless" => {}
+                "--frames" => options.frames = parse_number(arg, value()?)?,
+                "--time" => options.time = parse_number(arg, value()?)?,
+                "--step" => options.step = parse_number(arg, value()?)?,
+                "--width" => options.width = parse_number(arg, value()?)?,
+                "--height" => options.height = parse_number(arg, value()?)?,
+                "--output" => options.output = value()?.clone(),
+                "--tonemap" => {
+                    let name = value()?;
+                    options.tone_mapping.operator = ToneMapper::from_name(name)
+                        .ok_or_else(|| format!("Invalid value for {}: {}", arg, name))?;
+                }
+                "--exposure" => options.tone_mapping.exposure = parse_number(arg, value()?)?,
+                "--aa" => {
+                    let name = value()?;
+                    options.anti_aliasing = AntiAliasing::from_name(name)
+                        .ok_or_else(|| format!("Invalid value for {}: {}", arg, name))?;
+                }
+                "--compare-aa" => options.compare_anti_aliasing = true,
+                "--system" => options.system_seed = Some(parse_number(arg, value()?)?),
+                _ => return Err(format!("Unknown argument: {}", arg)),
+            }
+        }
+
+        if options.width == 0 || options.height == 0 {
+            return Err(String::from("--width and --height must be greater than 0"));
+        }
+
+        Ok(Some(options))
+    }
+
+    /// El Sistema Solar, o el sistema inventado de `system_seed` si se pidió.
+    pub fn scene(&self, distance_scale: f32) -> Scene {
+        Scene::load_system(self.system_seed, distance_scale)
+    }
+}
+
+/// Semilla de `--system`, si está entre los argumentos. La ventana la usa para abrir un
+/// sistema inventado igual que el modo sin ventana.
+pub fn system_seed(args: &[String]) -> Result<Option<u32>, String> {
+    match args.iter().position(|arg| arg == "--system") {
+        Some(index) => {
+            let value = args
+                .get(index + 1)
+                .ok_or_else(|| String::from("Missing value for --system"))?;
+            parse_number("--system", value).map(Some)
+        }
+        None => Ok(None),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(arg: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {}: {}", arg, value))
+}
+
+pub fn run(options: &HeadlessOptions, distance_scale: f32) -> Result<(), String> {
+    if options.compare_anti_aliasing {
+        return compare_anti_aliasing(options, distance_scale);
+    }
+
+    let mut framebuffer = Framebuffer::new(options.width, options.height);
+    framebuffer.set_background_color(0x000000);
+
+    let mut scene = options.scene(distance_scale);
+    scene.tone_mapping = options.tone_mapping;
+    scene.anti_aliasing = options.anti_aliasing;
+    let camera = headless_camera();
+
+    let mut time = options.time;
+    scene.seed_trails(time);
+
+    let mut recorder = Recorder::new(&options.output, options.step);
+    recorder.start(time)?;
+
+    while recorder.frames() < options.frames {
+        if recorder.is_due(time) {
+            scene.render(&mut framebuffer, &camera, time);
+            if let Some(path) = recorder.capture(&framebuffer, time)? {
+                println!("{}", path);
+            }
+        }
+
+        time += 1;
+        scene.update(time);
+    }
+
+    Ok(())
+}
+
+/// Dibuja `frames` cuadros desde `time` con cada modo de antialiasing, imprime cuánto
+/// tardó cada uno por cuadro (y cuántas veces más que sin antialiasing) y guarda el
+/// último cuadro de cada modo en `output` para comparar la calidad.
+pub fn compare_anti_aliasing(options: &HeadlessOptions, distance_scale: f32) -> Result<(), String> {
+    fs::create_dir_all(&options.output)
+        .map_err(|err| format!("Failed to create {}: {}", options.output, err))?;
+    let mut framebuffer = Framebuffer::new(options.width, options.height);
+    framebuffer.set_background_color(0x000000);
+
+    let mut scene = options.scene(distance_scale);
+    scene.tone_mapping = options.tone_mapping;
+    let camera = headless_camera();
+    scene.seed_trails(options.time);
+    let frames = options.frames.max(1);
+
+    let mut baseline = None;
+    for mode in AntiAliasing::all() {
+        scene.anti_aliasing = mode;
+        // Un cuadro sin medir para reservar los framebuffers del modo
+        scene.render(&mut framebuffer, &camera, options.time);
+
+        let start = Instant::now();
+        for frame in 0..frames {
+            scene.render(&mut framebuffer, &camera, options.time + frame * options.step);
+        }
+        let milliseconds = start.elapsed().as_secs_f64() * 1000.0 / frames as f64;
+        let baseline = *baseline.get_or_insert(milliseconds);
+
+        let file = format!("aa_{}.png", mode.name().to_lowercase().replace(' ', "_"));
+        let path = Path::new(&options.output).join(file);
+        let path = path.to_string_lossy().into_owned();
+        framebuffer
+            .save_png(&path)
+            .map_err(|err| format!("Failed to write {}: {}", path, err))?;
+        println!(
+            "{:<10} {:>8.2} ms/cuadro  {:>5.2}x  {}",
+            mode.name(),
+            milliseconds,
+            milliseconds / baseline,
+            path
+        );
+    }
+
+    Ok(())
+}
+
+fn headless_camera() -> Camera {
+    Camera::new(
+        Vec3::new(0.0, 0.0, 10.0),
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    )
+}
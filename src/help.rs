@@ -0,0 +1,117 @@
+use crate::camera::CameraMode;
+use crate::font::{draw_text, text_width, GLYPH_HEIGHT, LINE_HEIGHT};
+use crate::framebuffer::Framebuffer;
+use crate::input::{Action, ActionGroup, Bindings};
+
+const PADDING: usize = 10;
+const COLUMN_GAP: usize = 18;
+const TITLE_COLOR: u32 = 0xFFD27F;
+const TEXT_COLOR: u32 = 0xE0E0E0;
+const KEY_COLOR: u32 = 0x7FC8FF;
+const BORDER_COLOR: u32 = 0x808080;
+
+/// Panel con las teclas asignadas a cada acción: primero las de la cámara activa y
+/// luego las generales. Las acciones numeradas (seguir a un cuerpo, la órbita de
+/// cada cuerpo) se agrupan en una sola línea.
+pub fn draw_help(framebuffer: &mut Framebuffer, bindings: &Bindings, mode: CameraMode) {
+    let camera_group = match mode {
+        CameraMode::Chase => ActionGroup::Ship,
+        CameraMode::Orbit => ActionGroup::Orbit,
+        CameraMode::FreeFlight => ActionGroup::FreeFlight,
+    };
+
+    let mut lines: Vec<(String, String)> = Vec::new();
+    let mut sections: Vec<(usize, String)> = Vec::new();
+    for (group, title) in [
+        (camera_group, format!("Cámara: {}", mode.name())),
+        (ActionGroup::General, String::from("General")),
+    ] {
+        sections.push((lines.len(), title));
+        let mut previous: Option<&str> = None;
+        for action in Action::all().into_iter().filter(|action| action.group() == group) {
+            let keys = bindings.describe(action);
+            if keys.is_empty() {
+                continue;
+            }
+            match (previous, lines.last_mut()) {
+                (Some(description), Some((_, last_keys)))
+                    if description == action.description() =>
+                {
+                    last_keys.push(' ');
+                    last_keys.push_str(&keys);
+                }
+                _ => lines.push((action.description().to_string(), keys)),
+            }
+            previous = Some(action.description());
+        }
+    }
+    sections.push((lines.len(), String::from("Mouse")));
+    for (description, input) in [
+        ("Orbitar / mirar", "Arrastrar izq."),
+        ("Desplazar", "Arrastrar der."),
+        ("Zoom", "Rueda"),
+    ] {
+        lines.push((description.to_string(), input.to_string()));
+    }
+
+    let description_width = lines.iter().map(|(text, _)| text_width(text)).max().unwrap_or(0);
+    let keys_width = lines.iter().map(|(_, keys)| text_width(keys)).max().unwrap_or(0);
+    let width = PADDING * 2 + description_width + COLUMN_GAP + keys_width;
+    let rows = lines.len() + sections.len();
+    let height = PADDING * 2 + rows * LINE_HEIGHT - (LINE_HEIGHT - GLYPH_HEIGHT);
+    let left = framebuffer.width.saturating_sub(width) / 2;
+    let top = framebuffer.height.saturating_sub(height) / 2;
+
+    darken_rect(framebuffer, left, top, width, height);
+    outline_rect(framebuffer, left, top, width, height, BORDER_COLOR);
+
+    let x = (left + PADDING) as i32;
+    let keys_x = x + (description_width + COLUMN_GAP) as i32;
+    let mut y = (top + PADDING) as i32;
+    let mut sections = sections.into_iter().peekable();
+    for (index, (description, keys)) in lines.iter().enumerate() {
+        while let Some((_, title)) = sections.next_if(|(start, _)| *start == index) {
+            draw_text(framebuffer, x, y, &title, TITLE_COLOR);
+            y += LINE_HEIGHT as i32;
+        }
+        draw_text(framebuffer, x, y, description, TEXT_COLOR);
+        draw_text(framebuffer, keys_x, y, keys, KEY_COLOR);
+        y += LINE_HEIGHT as i32;
+    }
+}
+
+/// Oscurece el fondo para que el texto se lea sobre la escena
+fn darken_rect(
+    framebuffer: &mut Framebuffer,
+    left: usize,
+    top: usize,
+    width: usize,
+    height: usize,
+) {
+    for y in top..(top + height).min(framebuffer.height) {
+        for x in left..(left + width).min(framebuffer.width) {
+            let pixel = &mut framebuffer.buffer[y * framebuffer.width + x];
+            *pixel = (*pixel >> 2) & 0x3F3F3F;
+        }
+    }
+}
+
+fn outline_rect(
+    framebuffer: &mut Framebuffer,
+    left: usize,
+    top: usize,
+    width: usize,
+    height: usize,
+    color: u32,
+) {
+    let right = (left + width).min(framebuffer.width) - 1;
+    let bottom = (top + height).min(framebuffer.height) - 1;
+    for x in left..=right {
+        framebuffer.buffer[top * framebuffer.width + x] = color;
+        framebuffer.buffer[bottom * framebuffer.width + x] = color;
+    }
+    for y in top..=bottom {
+        framebuffer.buffer[y * framebuffer.width + left] = color;
+        framebuffer.buffer[y * framebuffer.width + right] = color;
+    }
+}
@@ -0,0 +1,319 @@
+use nalgebra_glm::{Mat4, Vec3};
+use std::f32::consts::TAU;
+use crate::camera::Camera;
+use crate::font::{draw_styled_text, text_size, TextStyle, GLYPH_HEIGHT, LINE_HEIGHT};
+use crate::body::PhysicalData;
+use crate::framebuffer::Framebuffer;
+use crate::pipeline::{create_uniforms, project_to_screen};
+use crate::scene::Scene;
+
+/// Días simulados por tick: la Tierra (0.01 rad por tick) da una vuelta en un año.
+pub const DAYS_PER_TICK: f32 = 365.25 * 0.01 / TAU;
+/// Día 0 de la simulación.
+pub const EPOCH: (i32, u32, u32) = (2000, 1, 1);
+
+const LABEL_COLOR: u32 = 0xFFFFFF;
+const HIGHLIGHT_COLOR: u32 = 0x7FC8FF;
+const DISTANCE_COLOR: u32 = 0xB0B0B0;
+const PANEL_TITLE_COLOR: u32 = 0xFFD27F;
+const PANEL_TEXT_COLOR: u32 = 0xE0E0E0;
+const PANEL_PADDING: usize = 8;
+/// Separación máxima entre el centro proyectado de un cuerpo y su etiqueta.
+const MAX_LABEL_OFFSET: f32 = 40.0;
+
+/// Capa de información sobre la imagen: etiquetas con nombre y distancia junto a cada
+/// cuerpo, un panel de telemetría de la nave y otro con los datos del cuerpo elegido.
+pub struct Hud {
+    pub show_labels: bool,
+    pub show_telemetry: bool,
+    pub show_info: bool,
+    /// Segundos reales que dura un tick, suavizado para que la escala no parpadee.
+    seconds_per_tick: f32,
+}
+
+impl Default for Hud {
+    fn default() -> Self {
+        Hud::new()
+    }
+}
+
+impl Hud {
+    pub fn new() -> Self {
+        Hud {
+            show_labels: true,
+            show_telemetry: true,
+            show_info: true,
+            seconds_per_tick: 1.0 / 60.0,
+        }
+    }
+
+    /// Registra que el último tick duró `dt` segundos reales.
+    pub fn update(&mut self, dt: f32) {
+        self.seconds_per_tick += (dt - self.seconds_per_tick) * 0.1;
+    }
+
+    /// Días simulados por segundo real.
+    pub fn time_scale(&self) -> f32 {
+        DAYS_PER_TICK / self.seconds_per_tick.max(1e-4)
+    }
+
+    /// `highlight` es el cuerpo seleccionado, que se etiqueta con otro color y cuyos
+    /// datos se muestran en el panel de información.
+    pub fn draw(
+        &self,
+        framebuffer: &mut Framebuffer,
+        scene: &Scene,
+        camera: &Camera,
+        time: u32,
+        highlight: Option<usize>,
+    ) {
+        if self.show_labels {
+            draw_labels(framebuffer, scene, camera, time, highlight);
+        }
+        if self.show_telemetry {
+            self.draw_telemetry(framebuffer, scene, time);
+        }
+        if let Some(index) = highlight.filter(|_| self.show_info) {
+            self.draw_info(framebuffer, scene, index, time);
+        }
+    }
+
+    /// Secciones del panel de información de `index`: los datos reales, si el cuerpo
+    /// los tiene, y su estado en la simulación.
+    pub fn body_info(&self, scene: &Scene, index: usize, time: u32) -> Vec<(String, Vec<String>)> {
+        let body = &scene.bodies[index];
+        let mut sections = Vec::new();
+        if let Some(data) = &body.data {
+            sections.push((String::from("Datos reales"), physical_lines(data)));
+        }
+
+        let mut lines = Vec::new();
+        if body.has_orbit() {
+            let center = body.parent.map_or(&scene.bodies[0].name, |parent| {
+                &scene.bodies[parent].name
+            });
+            let angle = (time as f32 * body.orbit_speed).rem_euclid(TAU).to_degrees();
+            let speed = body.orbit_radius * body.orbit_speed.abs() / self.seconds_per_tick;
+            let period = TAU / body.orbit_speed.abs() * DAYS_PER_TICK;
+            lines.push(format!("Centro de la órbita: {}", center));
+            lines.push(format!("Radio orbital: {:.1} u", body.orbit_radius));
+            lines.push(format!("Ángulo: {:.0}°", angle));
+            lines.push(format!("Velocidad: {:.2} u/s", speed));
+            lines.push(format!("Período: {:.1} días", period));
+        } else if body.parent.is_none() {
+            lines.push(String::from("Centro del sistema"));
+        }
+        if body.rotation_speed != 0.0 {
+            let day = TAU / body.rotation_speed.abs() * DAYS_PER_TICK;
+            lines.push(format!("Rotación: {:.1} días", day));
+        }
+        let bounds = scene.body_bounds(index, time);
+        let distance = (bounds.center - scene.ship.position).magnitude() - bounds.radius;
+        lines.push(format!("Distancia: {:.1} u", distance.max(0.0)));
+        sections.push((String::from("Simulación"), lines));
+        sections
+    }
+
+    fn draw_info(&self, framebuffer: &mut Framebuffer, scene: &Scene, index: usize, time: u32) {
+        let sections = self.body_info(scene, index, time);
+        let title = &scene.bodies[index].name;
+        let mut width = text_size(title, 2).0;
+        let mut height = text_size(title, 2).1 + LINE_HEIGHT / 2;
+        for (heading, lines) in &sections {
+            width = width.max(text_size(heading, 1).0);
+            for line in lines {
+                width = width.max(text_size(line, 1).0);
+            }
+            height += (lines.len() + 1) * LINE_HEIGHT + LINE_HEIGHT / 2;
+        }
+        let width = width + PANEL_PADDING * 2;
+        let height = height + PANEL_PADDING * 2 - LINE_HEIGHT / 2;
+        let left = PANEL_PADDING;
+        let top = PANEL_PADDING;
+        shade_rect(framebuffer, left, top, width, height, 0.6);
+
+        let x = (left + PANEL_PADDING) as i32;
+        let mut y = (top + PANEL_PADDING) as i32;
+        let title_style = TextStyle::new(HIGHLIGHT_COLOR).with_size(2);
+        draw_styled_text(framebuffer, x, y, title, &title_style);
+        y += (text_size(title, 2).1 + LINE_HEIGHT / 2) as i32;
+        let heading_style = TextStyle::new(PANEL_TITLE_COLOR);
+        let style = TextStyle::new(PANEL_TEXT_COLOR);
+        for (heading, lines) in &sections {
+            draw_styled_text(framebuffer, x, y, heading, &heading_style);
+            y += LINE_HEIGHT as i32;
+            for line in lines {
+                draw_styled_text(framebuffer, x, y, line, &style);
+                y += LINE_HEIGHT as i32;
+            }
+            y += (LINE_HEIGHT / 2) as i32;
+        }
+    }
+
+    fn draw_telemetry(&self, framebuffer: &mut Framebuffer, scene: &Scene, time: u32) {
+        let ship = &scene.ship;
+        let (azimuth, elevation) = heading(ship.forward());
+        let (year, month, day) = simulation_date(time);
+        let frame = scene
+            .ship_frame()
+            .unwrap_or_else(|| scene.dominant_body(ship.position, time));
+        let lines = [
+            format!("Velocidad: {:.2} u/s", ship.speed()),
+            format!("Rumbo: {:03.0}° {:+03.0}°", azimuth, elevation),
+            format!("Gravedad: {}", scene.bodies[frame].name),
+            format!("Fecha: {:02}/{:02}/{}", day, month, year),
+            format!("Escala: 1 s = {:.1} días", self.time_scale()),
+        ];
+        let title = "Telemetría";
+        let text = lines.join("\n");
+
+        let (text_width, text_height) = text_size(&text, 1);
+        let width = text_width.max(text_size(title, 1).0) + PANEL_PADDING * 2;
+        let height = text_height + LINE_HEIGHT + PANEL_PADDING * 2;
+        let left = PANEL_PADDING;
+        let top = framebuffer.height.saturating_sub(height + PANEL_PADDING);
+        shade_rect(framebuffer, left, top, width, height, 0.6);
+
+        let x = (left + PANEL_PADDING) as i32;
+        let y = (top + PANEL_PADDING) as i32;
+        draw_styled_text(framebuffer, x, y, title, &TextStyle::new(PANEL_TITLE_COLOR));
+        let style = TextStyle::new(PANEL_TEXT_COLOR);
+        draw_styled_text(framebuffer, x, y + LINE_HEIGHT as i32, &text, &style);
+    }
+}
+
+/// Renglones con los datos reales de un cuerpo.
+fn physical_lines(data: &PhysicalData) -> Vec<String> {
+    let mut lines = vec![
+        format!("Radio: {:.0} km", data.radius),
+        format!("Masa: {:.3e} kg", data.mass),
+    ];
+    if let Some(period) = data.orbital_period {
+        let mut text = format!("Período orbital: {:.1} días", period);
+        if period >= 730.0 {
+            text.push_str(&format!(" ({:.1} años)", period / 365.25));
+        }
+        lines.push(text);
+    }
+    let hours = data.rotation_period.abs();
+    let rotation = if hours >= 48.0 {
+        format!("{:.1} días", hours / 24.0)
+    } else {
+        format!("{:.1} h", hours)
+    };
+    let retrograde = if data.rotation_period < 0.0 { " (retrógrada)" } else { "" };
+    lines.push(format!("Rotación: {}{}", rotation, retrograde));
+    lines.push(format!("Temperatura media: {:.0} °C", data.temperature));
+    lines.push(format!("Lunas: {}", data.moons));
+    lines
+}
+
+/// Nombre y distancia a la nave junto a cada cuerpo visible.
+fn draw_labels(
+    framebuffer: &mut Framebuffer,
+    scene: &Scene,
+    camera: &Camera,
+    time: u32,
+    highlight: Option<usize>,
+) {
+    let uniforms = create_uniforms(framebuffer, camera, Mat4::identity(), time);
+    let width = framebuffer.width as f32;
+    let height = framebuffer.height as f32;
+
+    for (index, body) in scene.bodies.iter().enumerate() {
+        // Los anillos comparten posición con Saturno
+        if !body.solid {
+            continue;
+        }
+        let bounds = scene.body_bounds(index, time);
+        let Some(center) = project_to_screen(&uniforms, bounds.center) else {
+            continue;
+        };
+        if center.x < 0.0 || center.y < 0.0 || center.x >= width || center.y >= height {
+            continue;
+        }
+        // Oculto si algo quedó dibujado delante de su cara visible
+        let toward_camera = (camera.eye - bounds.center).normalize();
+        let front = project_to_screen(&uniforms, bounds.center + toward_camera * bounds.radius);
+        let pixel = center.y as usize * framebuffer.width + center.x as usize;
+        if front.is_some_and(|front| framebuffer.zbuffer[pixel] < front.z) {
+            continue;
+        }
+        // Radio aparente, para poner la etiqueta junto al borde del cuerpo
+        let edge = project_to_screen(&uniforms, bounds.center + camera.right() * bounds.radius);
+        let radius = edge.map_or(0.0, |edge| (edge.x - center.x).abs());
+        let x = (center.x + radius.min(MAX_LABEL_OFFSET) + 4.0) as i32;
+        let y = center.y as i32 - GLYPH_HEIGHT as i32;
+
+        let distance = (bounds.center - scene.ship.position).magnitude() - bounds.radius;
+        let color = if highlight == Some(index) { HIGHLIGHT_COLOR } else { LABEL_COLOR };
+        draw_label(framebuffer, x, y, &body.name, &TextStyle::new(color).with_alpha(0.9));
+        let distance_style = TextStyle::new(DISTANCE_COLOR).with_alpha(0.8);
+        let text = format!("{:.1} u", distance.max(0.0));
+        draw_label(framebuffer, x, y + LINE_HEIGHT as i32, &text, &distance_style);
+    }
+}
+
+/// Texto con una sombra oscura debajo, para que se lea sobre cuerpos brillantes.
+fn draw_label(framebuffer: &mut Framebuffer, x: i32, y: i32, text: &str, style: &TextStyle) {
+    let shadow = TextStyle::new(0x000000).with_alpha(style.alpha * 0.8);
+    draw_styled_text(framebuffer, x + 1, y + 1, text, &shadow);
+    draw_styled_text(framebuffer, x, y, text, style);
+}
+
+/// Rumbo de `forward` en grados: azimut de 0 a 360 medido desde `-z` (hacia el Sol
+/// al empezar) girando hacia `+x`, y elevación sobre el plano de las órbitas.
+pub fn heading(forward: Vec3) -> (f32, f32) {
+    let azimuth = forward.x.atan2(-forward.z).to_degrees().rem_euclid(360.0);
+    let elevation = forward.y.clamp(-1.0, 1.0).asin().to_degrees();
+    (azimuth, elevation)
+}
+
+/// Fecha (año, mes, día) del tick `time`, contando desde [`EPOCH`].
+pub fn simulation_date(time: u32) -> (i32, u32, u32) {
+    let (year, month, day) = EPOCH;
+    let days = days_from_civil(year, month, day) + (time as f64 * DAYS_PER_TICK as f64) as i64;
+    civil_from_days(days)
+}
+
+// Conversión entre fechas del calendario gregoriano y días desde 1970-01-01
+// (algoritmos de Howard Hinnant)
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year } as i64;
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as i32, month, day)
+}
+
+/// Oscurece un rectángulo para que el texto se lea sobre la escena.
+fn shade_rect(
+    framebuffer: &mut Framebuffer,
+    left: usize,
+    top: usize,
+    width: usize,
+    height: usize,
+    alpha: f32,
+) {
+    for y in top..(top + height).min(framebuffer.height) {
+        for x in left..(left + width).min(framebuffer.width) {
+            framebuffer.blend_pixel(x, y, 0x000000, alpha);
+        }
+    }
+}
@@ -0,0 +1,479 @@
+use minifb::{Key, KeyRepeat, Window};
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use crate::mouse::MouseController;
+
+/// Archivo de controles que se busca en el directorio de trabajo.
+pub const CONTROLS_FILE: &str = "controls.cfg";
+
+/// Todo lo que se puede hacer desde el teclado. Cada acción se asocia a una o más
+/// teclas en [`Bindings`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    // Cámara de órbita
+    OrbitLeft,
+    OrbitRight,
+    OrbitUp,
+    OrbitDown,
+    LookLeft,
+    LookRight,
+    LookUp,
+    LookDown,
+    ZoomIn,
+    ZoomOut,
+    // Vuelo libre
+    FlyForward,
+    FlyBackward,
+    FlyLeft,
+    FlyRight,
+    FlyUp,
+    FlyDown,
+    PitchUp,
+    PitchDown,
+    YawLeft,
+    YawRight,
+    RollLeft,
+    RollRight,
+    // Nave
+    ShipForward,
+    ShipBackward,
+    ShipLeft,
+    ShipRight,
+    ShipUp,
+    ShipDown,
+    ShipPitchUp,
+    ShipPitchDown,
+    ShipYawLeft,
+    ShipYawRight,
+    ShipRollLeft,
+    ShipRollRight,
+    ShipBrake,
+    ShipBoost,
+    ShipCircularize,
+    // Generales
+    ToggleCameraMode,
+    NextTarget,
+    ClearTarget,
+    /// Viajar con el piloto automático al cuerpo seguido.
+    Autopilot,
+    /// Seguir al cuerpo `n` (desde 0) de la escena.
+    FocusBody(usize),
+    ToggleOrbits,
+    ToggleTrails,
+    ToggleTrajectory,
+    ToggleHud,
+    ToggleMinimap,
+    /// Panel con los datos del cuerpo seguido.
+    ToggleBodyInfo,
+    /// Mostrar/ocultar la órbita del `n`-ésimo cuerpo que orbita (desde 0).
+    ToggleBodyOrbit(usize),
+    CycleRenderMode,
+    ToggleWireframe,
+    ToggleBoundingBoxes,
+    CycleToneMapping,
+    ToggleBloom,
+    CycleAntiAliasing,
+    /// Exposición medio paso (EV) arriba o abajo.
+    ExposureUp,
+    ExposureDown,
+    CycleCollisionResponse,
+    ToggleMeshCollisions,
+    Screenshot,
+    ToggleRecording,
+    InvertMouseY,
+    ToggleHelp,
+    Quit,
+}
+
+/// Bajo qué cámara tiene efecto una acción; la ayuda solo muestra las que aplican.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionGroup {
+    General,
+    Orbit,
+    FreeFlight,
+    Ship,
+}
+
+/// Cuántas acciones `FocusBody` y `ToggleBodyOrbit` existen.
+pub const NUMBERED_ACTIONS: usize = 9;
+
+impl Action {
+    /// Todas las acciones, en el orden en que aparecen en la ayuda.
+    pub fn all() -> Vec<Action> {
+        use Action::*;
+        let mut actions = vec![
+            OrbitLeft, OrbitRight, OrbitUp, OrbitDown, LookLeft, LookRight, LookUp, LookDown,
+            ZoomIn, ZoomOut, FlyForward, FlyBackward, FlyLeft, FlyRight, FlyUp, FlyDown, PitchUp,
+            PitchDown, YawLeft, YawRight, RollLeft, RollRight, ShipForward, ShipBackward,
+            ShipLeft, ShipRight, ShipUp, ShipDown, ShipPitchUp, ShipPitchDown, ShipYawLeft,
+            ShipYawRight, ShipRollLeft, ShipRollRight, ShipBrake, ShipBoost, ShipCircularize,
+            ToggleCameraMode, NextTarget, ClearTarget, Autopilot,
+        ];
+        actions.extend((0..NUMBERED_ACTIONS).map(FocusBody));
+        actions.extend([
+            ToggleOrbits,
+            ToggleTrails,
+            ToggleTrajectory,
+            ToggleHud,
+            ToggleMinimap,
+            ToggleBodyInfo,
+        ]);
+        actions.extend((0..NUMBERED_ACTIONS).map(ToggleBodyOrbit));
+        actions.extend([
+            CycleRenderMode,
+            ToggleWireframe,
+            ToggleBoundingBoxes,
+            CycleToneMapping,
+            ToggleBloom,
+            CycleAntiAliasing,
+            ExposureUp,
+            ExposureDown,
+            CycleCollisionResponse,
+            ToggleMeshCollisions,
+            Screenshot,
+            ToggleRecording,
+            InvertMouseY,
+            ToggleHelp,
+            Quit,
+        ]);
+        actions
+    }
+
+    /// Nombre usado en el archivo de controles, p. ej. `ZoomIn` o `FocusBody3`.
+    pub fn name(self) -> String {
+        match self {
+            Action::FocusBody(n) => format!("FocusBody{}", n + 1),
+            Action::ToggleBodyOrbit(n) => format!("ToggleBodyOrbit{}", n + 1),
+            _ => format!("{:?}", self),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::all()
+            .into_iter()
+            .find(|action| action.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Descripción para la ayuda; las acciones numeradas comparten una sola.
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::OrbitLeft => "Orbitar a la izquierda",
+            Action::OrbitRight => "Orbitar a la derecha",
+            Action::OrbitUp => "Orbitar hacia arriba",
+            Action::OrbitDown => "Orbitar hacia abajo",
+            Action::LookLeft => "Mover el centro a la izquierda",
+            Action::LookRight => "Mover el centro a la derecha",
+            Action::LookUp => "Mover el centro hacia arriba",
+            Action::LookDown => "Mover el centro hacia abajo",
+            Action::ZoomIn => "Acercar",
+            Action::ZoomOut => "Alejar",
+            Action::FlyForward => "Avanzar",
+            Action::FlyBackward => "Retroceder",
+            Action::FlyLeft => "Desplazarse a la izquierda",
+            Action::FlyRight => "Desplazarse a la derecha",
+            Action::FlyUp => "Subir",
+            Action::FlyDown => "Bajar",
+            Action::PitchUp => "Inclinar hacia arriba",
+            Action::PitchDown => "Inclinar hacia abajo",
+            Action::YawLeft => "Girar a la izquierda",
+            Action::YawRight => "Girar a la derecha",
+            Action::RollLeft => "Rotar a la izquierda (roll)",
+            Action::RollRight => "Rotar a la derecha (roll)",
+            Action::ShipForward => "Empuje hacia adelante",
+            Action::ShipBackward => "Empuje hacia atrás",
+            Action::ShipLeft => "Empuje a la izquierda",
+            Action::ShipRight => "Empuje a la derecha",
+            Action::ShipUp => "Empuje hacia arriba",
+            Action::ShipDown => "Empuje hacia abajo",
+            Action::ShipPitchUp => "Subir la proa (pitch)",
+            Action::ShipPitchDown => "Bajar la proa (pitch)",
+            Action::ShipYawLeft => "Girar a la izquierda (yaw)",
+            Action::ShipYawRight => "Girar a la derecha (yaw)",
+            Action::ShipRollLeft => "Rotar a la izquierda (roll)",
+            Action::ShipRollRight => "Rotar a la derecha (roll)",
+            Action::ShipBrake => "Frenar",
+            Action::ShipBoost => "Turbo",
+            Action::ShipCircularize => "Entrar en órbita circular",
+            Action::ToggleCameraMode => "Nave / órbita / vuelo libre",
+            Action::NextTarget => "Seguir al siguiente cuerpo",
+            Action::ClearTarget => "Dejar de seguir",
+            Action::Autopilot => "Piloto automático al cuerpo seguido",
+            Action::FocusBody(_) => "Seguir a un cuerpo",
+            Action::ToggleOrbits => "Mostrar/ocultar órbitas",
+            Action::ToggleTrails => "Mostrar/ocultar estelas",
+            Action::ToggleTrajectory => "Trayectoria de la nave",
+            Action::ToggleHud => "Etiquetas y telemetría",
+            Action::ToggleMinimap => "Minimapa",
+            Action::ToggleBodyInfo => "Datos del cuerpo seguido",
+            Action::ToggleBodyOrbit(_) => "Órbita de cada cuerpo",
+            Action::CycleRenderMode => "Cambiar modo de visualización",
+            Action::ToggleWireframe => "Wireframe sí/no",
+            Action::ToggleBoundingBoxes => "Cajas envolventes",
+            Action::CycleToneMapping => "Cambiar tonemapping",
+            Action::ToggleBloom => "Bloom sí/no",
+            Action::CycleAntiAliasing => "Cambiar antialiasing",
+            Action::ExposureUp => "Subir exposición",
+            Action::ExposureDown => "Bajar exposición",
+            Action::CycleCollisionResponse => "Respuesta a colisiones",
+            Action::ToggleMeshCollisions => "Colisiones por triángulo",
+            Action::Screenshot => "Captura de pantalla",
+            Action::ToggleRecording => "Iniciar/detener grabación",
+            Action::InvertMouseY => "Invertir eje Y del mouse",
+            Action::ToggleHelp => "Mostrar/ocultar esta ayuda",
+            Action::Quit => "Salir",
+        }
+    }
+
+    pub fn group(self) -> ActionGroup {
+        use Action::*;
+        match self {
+            OrbitLeft | OrbitRight | OrbitUp | OrbitDown | LookLeft | LookRight | LookUp
+            | LookDown | ZoomIn | ZoomOut => ActionGroup::Orbit,
+            FlyForward | FlyBackward | FlyLeft | FlyRight | FlyUp | FlyDown | PitchUp
+            | PitchDown | YawLeft | YawRight | RollLeft | RollRight => ActionGroup::FreeFlight,
+            ShipForward | ShipBackward | ShipLeft | ShipRight | ShipUp | ShipDown | ShipPitchUp
+            | ShipPitchDown | ShipYawLeft | ShipYawRight | ShipRollLeft | ShipRollRight
+            | ShipBrake | ShipBoost | ShipCircularize => ActionGroup::Ship,
+            _ => ActionGroup::General,
+        }
+    }
+
+    fn default_keys(self) -> Vec<Key> {
+        use Action::*;
+        let digits = [
+            Key::Key1, Key::Key2, Key::Key3, Key::Key4, Key::Key5, Key::Key6, Key::Key7,
+            Key::Key8, Key::Key9,
+        ];
+        let functions = [
+            Key::F1, Key::F2, Key::F3, Key::F4, Key::F5, Key::F6, Key::F7, Key::F8, Key::F9,
+        ];
+        match self {
+            OrbitLeft | YawLeft | ShipYawLeft => vec![Key::Left],
+            OrbitRight | YawRight | ShipYawRight => vec![Key::Right],
+            OrbitUp | FlyForward | ShipForward => vec![Key::W],
+            OrbitDown | FlyBackward | ShipBackward => vec![Key::S],
+            LookLeft | FlyLeft | ShipLeft => vec![Key::A],
+            LookRight | FlyRight | ShipRight => vec![Key::D],
+            LookUp | RollLeft | ShipRollLeft => vec![Key::Q],
+            LookDown | RollRight | ShipRollRight => vec![Key::E],
+            ZoomIn | PitchUp | ShipPitchUp => vec![Key::Up],
+            ZoomOut | PitchDown | ShipPitchDown => vec![Key::Down],
+            FlyUp | ShipUp => vec![Key::Space],
+            FlyDown | ShipDown => vec![Key::LeftShift],
+            ShipBrake => vec![Key::Z],
+            ShipBoost => vec![Key::LeftCtrl],
+            ShipCircularize => vec![Key::G],
+            ToggleCameraMode => vec![Key::C],
+            NextTarget => vec![Key::Tab],
+            ClearTarget => vec![Key::Key0],
+            Autopilot => vec![Key::Enter],
+            FocusBody(n) => digits.get(n).copied().into_iter().collect(),
+            ToggleOrbits => vec![Key::O],
+            ToggleTrails => vec![Key::T],
+            ToggleTrajectory => vec![Key::Y],
+            ToggleHud => vec![Key::U],
+            ToggleMinimap => vec![Key::M],
+            ToggleBodyInfo => vec![Key::N],
+            ToggleBodyOrbit(n) => functions.get(n).copied().into_iter().collect(),
+            CycleRenderMode => vec![Key::V],
+            ToggleWireframe => vec![Key::X],
+            ToggleBoundingBoxes => vec![Key::B],
+            CycleToneMapping => vec![Key::L],
+            ToggleBloom => vec![Key::F],
+            CycleAntiAliasing => vec![Key::F10],
+            ExposureUp => vec![Key::Equal],
+            ExposureDown => vec![Key::Minus],
+            CycleCollisionResponse => vec![Key::K],
+            ToggleMeshCollisions => vec![Key::J],
+            Screenshot => vec![Key::P],
+            ToggleRecording => vec![Key::R],
+            InvertMouseY => vec![Key::I],
+            ToggleHelp => vec![Key::H, Key::F12],
+            Quit => vec![Key::Escape],
+        }
+    }
+}
+
+/// Las teclas asignadas a cada acción.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bindings {
+    keys: HashMap<Action, Vec<Key>>,
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Bindings {
+            keys: Action::all()
+                .into_iter()
+                .map(|action| (action, action.default_keys()))
+                .collect(),
+        }
+    }
+}
+
+impl Bindings {
+    pub fn keys(&self, action: Action) -> &[Key] {
+        self.keys.get(&action).map_or(&[], |keys| keys.as_slice())
+    }
+
+    /// Reemplaza las teclas de `action`; una lista vacía la deja sin asignar.
+    pub fn bind(&mut self, action: Action, keys: Vec<Key>) {
+        self.keys.insert(action, keys);
+    }
+
+    /// Alguna de las teclas de `action` está presionada.
+    pub fn is_down(&self, window: &Window, action: Action) -> bool {
+        self.keys(action).iter().any(|key| window.is_key_down(*key))
+    }
+
+    /// Alguna de las teclas de `action` se acaba de presionar (sin repetición).
+    pub fn is_pressed(&self, window: &Window, action: Action) -> bool {
+        self.keys(action)
+            .iter()
+            .any(|key| window.is_key_pressed(*key, KeyRepeat::No))
+    }
+
+    /// `1.0`, `-1.0` o `0.0` según cuál de las dos acciones esté presionada.
+    pub fn axis(&self, window: &Window, positive: Action, negative: Action) -> f32 {
+        let mut value = 0.0;
+        if self.is_down(window, positive) {
+            value += 1.0;
+        }
+        if self.is_down(window, negative) {
+            value -= 1.0;
+        }
+        value
+    }
+
+    /// Texto para la ayuda, p. ej. `"Up, NumPadPlus"`.
+    pub fn describe(&self, action: Action) -> String {
+        let names: Vec<&str> = self.keys(action).iter().map(|key| key_name(*key)).collect();
+        names.join(", ")
+    }
+}
+
+/// Controles del usuario: teclas y ajustes del mouse.
+///
+/// El archivo tiene una asignación por línea, `Acción = Tecla, Tecla`, y los ajustes
+/// del mouse como `mouse.invert_y = true`. Las acciones que no aparecen conservan sus
+/// teclas por defecto, una acción sin teclas (`Quit =`) queda deshabilitada y `#`
+/// inicia un comentario.
+#[derive(Default)]
+pub struct Controls {
+    pub bindings: Bindings,
+    pub mouse: MouseController,
+}
+
+impl Controls {
+    /// Lee `path`; si el archivo no existe se usan los controles por defecto.
+    pub fn load(path: &str) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Controls::parse(&text).map_err(|err| format!("{}: {}", path, err)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Controls::default()),
+            Err(err) => Err(format!("Failed to read {}: {}", path, err)),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut controls = Controls::default();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let error = |message: String| format!("line {}: {}", number + 1, message);
+
+            let (name, value) = line
+                .split_once('=')
+                .ok_or_else(|| error(format!("expected `name = value`, found `{}`", line)))?;
+            let (name, value) = (name.trim(), value.trim());
+
+            if let Some(setting) = name.strip_prefix("mouse.") {
+                controls.set_mouse(setting, value).map_err(error)?;
+                continue;
+            }
+
+            let action = Action::from_name(name)
+                .ok_or_else(|| error(format!("unknown action `{}`", name)))?;
+            let keys = value
+                .split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(|key| parse_key(key).ok_or_else(|| error(format!("unknown key `{}`", key))))
+                .collect::<Result<Vec<Key>, String>>()?;
+            controls.bindings.bind(action, keys);
+        }
+
+        Ok(controls)
+    }
+
+    fn set_mouse(&mut self, setting: &str, value: &str) -> Result<(), String> {
+        let number = || {
+            value
+                .parse::<f32>()
+                .map_err(|_| format!("invalid number for mouse.{}: `{}`", setting, value))
+        };
+        match setting {
+            "rotate_sensitivity" => self.mouse.rotate_sensitivity = number()?,
+            "pan_sensitivity" => self.mouse.pan_sensitivity = number()?,
+            "zoom_sensitivity" => self.mouse.zoom_sensitivity = number()?,
+            "invert_y" => {
+                self.mouse.invert_y = value
+                    .parse()
+                    .map_err(|_| format!("invalid value for mouse.invert_y: `{}`", value))?
+            }
+            _ => return Err(format!("unknown mouse setting `{}`", setting)),
+        }
+        Ok(())
+    }
+}
+
+/// Nombre de la tecla en el archivo de controles: el de `minifb::Key`, salvo los
+/// dígitos, que se escriben `0`..`9`.
+pub fn key_name(key: Key) -> &'static str {
+    KEYS.iter()
+        .find(|(_, candidate)| *candidate == key)
+        .map_or("?", |(name, _)| name)
+}
+
+/// Busca una tecla por nombre sin distinguir mayúsculas.
+pub fn parse_key(name: &str) -> Option<Key> {
+    KEYS.iter()
+        .find(|(candidate, _)| candidate.eq_ignore_ascii_case(name))
+        .map(|(_, key)| *key)
+}
+
+#[rustfmt::skip]
+const KEYS: &[(&str, Key)] = &[
+    ("0", Key::Key0), ("1", Key::Key1), ("2", Key::Key2), ("3", Key::Key3), ("4", Key::Key4),
+    ("5", Key::Key5), ("6", Key::Key6), ("7", Key::Key7), ("8", Key::Key8), ("9", Key::Key9),
+    ("A", Key::A), ("B", Key::B), ("C", Key::C), ("D", Key::D), ("E", Key::E), ("F", Key::F),
+    ("G", Key::G), ("H", Key::H), ("I", Key::I), ("J", Key::J), ("K", Key::K), ("L", Key::L),
+    ("M", Key::M), ("N", Key::N), ("O", Key::O), ("P", Key::P), ("Q", Key::Q), ("R", Key::R),
+    ("S", Key::S), ("T", Key::T), ("U", Key::U), ("V", Key::V), ("W", Key::W), ("X", Key::X),
+    ("Y", Key::Y), ("Z", Key::Z),
+    ("F1", Key::F1), ("F2", Key::F2), ("F3", Key::F3), ("F4", Key::F4), ("F5", Key::F5),
+    ("F6", Key::F6), ("F7", Key::F7), ("F8", Key::F8), ("F9", Key::F9), ("F10", Key::F10),
+    ("F11", Key::F11), ("F12", Key::F12), ("F13", Key::F13), ("F14", Key::F14), ("F15", Key::F15),
+    ("Up", Key::Up), ("Down", Key::Down), ("Left", Key::Left), ("Right", Key::Right),
+    ("Apostrophe", Key::Apostrophe), ("Backquote", Key::Backquote), ("Backslash", Key::Backslash),
+    ("Comma", Key::Comma), ("Equal", Key::Equal), ("LeftBracket", Key::LeftBracket),
+    ("Minus", Key::Minus), ("Period", Key::Period), ("RightBracket", Key::RightBracket),
+    ("Semicolon", Key::Semicolon), ("Slash", Key::Slash), ("Backspace", Key::Backspace),
+    ("Delete", Key::Delete), ("End", Key::End), ("Enter", Key::Enter), ("Escape", Key::Escape),
+    ("Home", Key::Home), ("Insert", Key::Insert), ("Menu", Key::Menu), ("PageDown", Key::PageDown),
+    ("PageUp", Key::PageUp), ("Pause", Key::Pause), ("Space", Key::Space), ("Tab", Key::Tab),
+    ("NumLock", Key::NumLock), ("CapsLock", Key::CapsLock), ("ScrollLock", Key::ScrollLock),
+    ("LeftShift", Key::LeftShift), ("RightShift", Key::RightShift),
+    ("LeftCtrl", Key::LeftCtrl), ("RightCtrl", Key::RightCtrl),
+    ("LeftAlt", Key::LeftAlt), ("RightAlt", Key::RightAlt),
+    ("LeftSuper", Key::LeftSuper), ("RightSuper", Key::RightSuper),
+    ("NumPad0", Key::NumPad0), ("NumPad1", Key::NumPad1), ("NumPad2", Key::NumPad2),
+    ("NumPad3", Key::NumPad3), ("NumPad4", Key::NumPad4), ("NumPad5", Key::NumPad5),
+    ("NumPad6", Key::NumPad6), ("NumPad7", Key::NumPad7), ("NumPad8", Key::NumPad8),
+    ("NumPad9", Key::NumPad9), ("NumPadDot", Key::NumPadDot), ("NumPadSlash", Key::NumPadSlash),
+    ("NumPadAsterisk", Key::NumPadAsterisk), ("NumPadMinus", Key::NumPadMinus),
+    ("NumPadPlus", Key::NumPadPlus), ("NumPadEnter", Key::NumPadEnter),
+];
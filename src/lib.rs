@@ -0,0 +1,78 @@
+//! Renderizador por software del Sistema Solar.
+//!
+//! Todo el pipeline corre en CPU y escribe en un [`Framebuffer`](framebuffer::Framebuffer)
+//! de `u32` (`0xRRGGBB`), así que se puede usar con o sin ventana:
+//!
+//! - [`obj::Obj`] carga modelos y produce el arreglo de [`vertex::Vertex`].
+//! - [`shaders::vertex_shader`] transforma cada vértice con los [`Uniforms`].
+//! - [`triangle::triangle`] rasteriza y genera [`fragment::Fragment`]s; con MSAA,
+//!   [`triangle::triangle_samples`] prueba la cobertura en varias muestras por píxel.
+//! - Un [`shaders::FragmentShader`] decide el [`color::Color`] de cada fragmento, en luz
+//!   lineal y sin límite de brillo.
+//! - [`atmosphere::Atmosphere`] envuelve a los cuerpos con una capa translúcida que
+//!   dispersa la luz del Sol.
+//! - [`post::PostStack`] aplica efectos, como el bloom, a la imagen HDR completa.
+//! - [`framebuffer::Framebuffer::present`] comprime esa imagen HDR con un
+//!   [`tonemap::ToneMapping`] y la codifica en sRGB.
+//! - [`pipeline::render`] encadena todo lo anterior para un modelo.
+//! - [`scene::Scene`] arma el Sistema Solar completo y lo dibuja desde una [`camera::Camera`],
+//!   con el [`antialias::AntiAliasing`] elegido.
+//! - [`font::draw_styled_text`] escribe texto encima de la imagen, con color,
+//!   transparencia y tamaño, para ayudas, etiquetas y paneles.
+//!
+//! ```no_run
+//! use nalgebra_glm::Vec3;
+//! use space_traver::camera::Camera;
+//! use space_traver::framebuffer::Framebuffer;
+//! use space_traver::scene::{Scene, DISTANCE_SCALE};
+//!
+//! let mut framebuffer = Framebuffer::new(320, 240);
+//! let mut scene = Scene::load(DISTANCE_SCALE);
+//! let camera = Camera::new(
+//!     Vec3::new(0.0, 30.0, 45.0),
+//!     Vec3::new(0.0, 0.0, 0.0),
+//!     Vec3::new(0.0, 1.0, 0.0),
+//! );
+//! scene.render(&mut framebuffer, &camera, 100);
+//! framebuffer.save_png("frame.png").unwrap();
+//! ```
+
+pub mod antialias;
+pub mod atmosphere;
+pub mod autopilot;
+pub mod body;
+pub mod bounds;
+pub mod camera;
+pub mod chase_camera;
+pub mod collision;
+pub mod color;
+pub mod debug;
+pub mod focus;
+pub mod font;
+pub mod fragment;
+pub mod framebuffer;
+pub mod free_camera;
+pub mod headless;
+pub mod gravity;
+pub mod help;
+pub mod hud;
+pub mod input;
+pub mod line;
+pub mod minimap;
+pub mod mouse;
+pub mod noise;
+pub mod obj;
+pub mod orbit;
+pub mod pipeline;
+pub mod planet;
+pub mod post;
+pub mod recorder;
+pub mod scene;
+pub mod shaders;
+pub mod ship;
+pub mod tonemap;
+pub mod triangle;
+pub mod vertex;
+pub mod warp;
+
+pub use pipeline::Uniforms;
@@ -1,6 +1,12 @@
+use nalgebra_glm::{Vec3, Vec4};
 use crate::fragment::Fragment;
+use crate::framebuffer::Framebuffer;
 use crate::vertex::Vertex;
 use crate::color::Color;
+use crate::Uniforms;
+
+// Los puntos con w menor a esto quedan detrás de la cámara
+const NEAR_W: f32 = 0.1;
 
 pub fn line(a: &Vertex, b: &Vertex) -> Vec<Fragment> {
     let mut fragments = Vec::new();
@@ -8,10 +14,10 @@ pub fn line(a: &Vertex, b: &Vertex) -> Vec<Fragment> {
     let start = a.transformed_position;
     let end = b.transformed_position;
 
-    let mut x0 = start.x as i32;
-    let mut y0 = start.y as i32;
-    let x1 = end.x as i32;
-    let y1 = end.y as i32;
+    let mut x0 = start.x.round() as i32;
+    let mut y0 = start.y.round() as i32;
+    let x1 = end.x.round() as i32;
+    let y1 = end.y.round() as i32;
 
     let dx = (x1 - x0).abs();
     let dy = (y1 - y0).abs();
@@ -21,9 +27,25 @@ pub fn line(a: &Vertex, b: &Vertex) -> Vec<Fragment> {
 
     let mut err = if dx > dy { dx / 2 } else { -dy / 2 };
 
+    // Se interpola a lo largo del eje dominante, así no se divide entre cero en líneas verticales
+    let steps = dx.max(dy).max(1) as f32;
+    let mut step = 0;
+
     loop {
-        let z = start.z + (end.z - start.z) * (x0 - start.x as i32) as f32 / (end.x - start.x) as f32;
-        fragments.push(Fragment::new(x0 as f32, y0 as f32, Color::new(255, 255, 255), z));
+        let t = step as f32 / steps;
+        let z = start.z + (end.z - start.z) * t;
+        let color = a.color.lerp(&b.color, t);
+        let vertex_position = a.position + (b.position - a.position) * t;
+
+        fragments.push(Fragment::new(
+            x0 as f32,
+            y0 as f32,
+            color,
+            z,
+            Vec3::new(0.0, 0.0, 0.0),
+            1.0,
+            vertex_position,
+        ));
 
         if x0 == x1 && y0 == y1 { break; }
 
@@ -36,7 +58,128 @@ pub fn line(a: &Vertex, b: &Vertex) -> Vec<Fragment> {
             err += dx;
             y0 += sy;
         }
+        step += 1;
     }
 
     fragments
 }
+
+/// Dibuja una polilínea en coordenadas de mundo con prueba de profundidad.
+/// `colors` debe tener un color por punto; el color se interpola en cada segmento.
+pub fn polyline(framebuffer: &mut Framebuffer, uniforms: &Uniforms, points: &[Vec3], colors: &[Color]) {
+    for i in 1..points.len().min(colors.len()) {
+        let segment = project_segment(
+            uniforms,
+            Vertex::new_with_color(points[i - 1], colors[i - 1]),
+            Vertex::new_with_color(points[i], colors[i]),
+        );
+
+        if let Some((a, b)) = segment {
+            draw_line(framebuffer, &a, &b);
+        }
+    }
+}
+
+/// Rasteriza un segmento cuyos vértices ya están en coordenadas de pantalla
+pub fn draw_line(framebuffer: &mut Framebuffer, a: &Vertex, b: &Vertex) {
+    if let Some((a, b)) = clip_to_screen(framebuffer, a, b) {
+        for fragment in line(&a, &b) {
+            let x = fragment.position.x as usize;
+            let y = fragment.position.y as usize;
+            framebuffer.set_current_color(fragment.color);
+            framebuffer.point(x, y, fragment.depth);
+        }
+    }
+}
+
+fn to_clip_space(uniforms: &Uniforms, position: &Vec3) -> Vec4 {
+    uniforms.projection_matrix
+        * uniforms.view_matrix
+        * uniforms.model_matrix
+        * Vec4::new(position.x, position.y, position.z, 1.0)
+}
+
+fn lerp_vertex(a: &Vertex, b: &Vertex, t: f32) -> Vertex {
+    let mut vertex = Vertex::new_with_color(
+        a.position + (b.position - a.position) * t,
+        a.color.lerp(&b.color, t),
+    );
+    vertex.set_transformed(
+        a.transformed_position + (b.transformed_position - a.transformed_position) * t,
+        Vec3::new(0.0, 0.0, 0.0),
+    );
+    vertex
+}
+
+// Recorta el segmento contra el plano cercano y lo lleva a coordenadas de pantalla
+fn project_segment(uniforms: &Uniforms, mut a: Vertex, mut b: Vertex) -> Option<(Vertex, Vertex)> {
+    let mut clip_a = to_clip_space(uniforms, &a.position);
+    let mut clip_b = to_clip_space(uniforms, &b.position);
+
+    if clip_a.w < NEAR_W && clip_b.w < NEAR_W {
+        return None;
+    }
+    if clip_a.w < NEAR_W || clip_b.w < NEAR_W {
+        let t = (NEAR_W - clip_a.w) / (clip_b.w - clip_a.w);
+        let clip_near = clip_a + (clip_b - clip_a) * t;
+        let vertex_near = lerp_vertex(&a, &b, t);
+        if clip_a.w < NEAR_W {
+            clip_a = clip_near;
+            a = vertex_near;
+        } else {
+            clip_b = clip_near;
+            b = vertex_near;
+        }
+    }
+
+    let to_screen = |clip: Vec4| {
+        let ndc = Vec4::new(clip.x / clip.w, clip.y / clip.w, clip.z / clip.w, 1.0);
+        let screen = uniforms.viewport_matrix * ndc;
+        Vec3::new(screen.x, screen.y, screen.z)
+    };
+    a.set_transformed(to_screen(clip_a), Vec3::new(0.0, 0.0, 0.0));
+    b.set_transformed(to_screen(clip_b), Vec3::new(0.0, 0.0, 0.0));
+
+    Some((a, b))
+}
+
+// Recorta contra los bordes del framebuffer (Liang-Barsky), para no rasterizar
+// líneas que salen disparadas fuera de la pantalla.
+fn clip_to_screen(framebuffer: &Framebuffer, a: &Vertex, b: &Vertex) -> Option<(Vertex, Vertex)> {
+    let start = a.transformed_position;
+    let delta = b.transformed_position - start;
+    let max_x = framebuffer.width as f32 - 1.0;
+    let max_y = framebuffer.height as f32 - 1.0;
+
+    if !start.x.is_finite() || !start.y.is_finite() || !delta.x.is_finite() || !delta.y.is_finite() {
+        return None;
+    }
+
+    let mut t0: f32 = 0.0;
+    let mut t1: f32 = 1.0;
+    let edges = [
+        (-delta.x, start.x),
+        (delta.x, max_x - start.x),
+        (-delta.y, start.y),
+        (delta.y, max_y - start.y),
+    ];
+    for (p, q) in edges {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else {
+            let r = q / p;
+            if p < 0.0 {
+                t0 = t0.max(r);
+            } else {
+                t1 = t1.min(r);
+            }
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+
+    Some((lerp_vertex(a, b, t0), lerp_vertex(a, b, t1)))
+}
@@ -1,508 +1,83 @@
-use image::io::Reader as ImageReader;
-use minifb::{Key, Window, WindowOptions};
-use nalgebra_glm::{look_at, perspective, Mat4, Vec3, Vec2};
+use minifb::{MouseButton, MouseMode, Window, WindowOptions};
+use nalgebra_glm::Vec3;
 use std::f32::consts::PI;
-use std::time::Duration;
-
-mod camera;
-mod color;
-mod fragment;
-mod framebuffer;
-mod obj;
-mod shaders;
-mod triangle;
-mod vertex;
-
-use camera::Camera;
-use framebuffer::Framebuffer;
-use obj::Obj;
-use shaders::{
-    earth_shader, fragment_shader, jupiter_shader, mars_shader, moon_shader, rocky_planet_shader,
-    saturn_rings_shader, saturn_shader, spaceship_shader, sun_shader, venus_shader, vertex_shader,
-};
-use triangle::triangle;
-use vertex::Vertex;
-
-pub struct Uniforms {
-    model_matrix: Mat4,
-    view_matrix: Mat4,
-    projection_matrix: Mat4,
-    viewport_matrix: Mat4,
-    time: u32,
-}
-fn create_model_matrix(translation: Vec3, scale: f32, rotation: Vec3) -> Mat4 {
-    // Transformación para rotación y escala
-    let transform_matrix = Mat4::new(
-        scale,
-        0.0,
-        0.0,
-        translation.x,
-        0.0,
-        scale,
-        0.0,
-        translation.y,
-        0.0,
-        0.0,
-        scale,
-        translation.z,
-        0.0,
-        0.0,
-        0.0,
-        1.0,
-    );
-
-    // Crear matriz de rotación
-    let rotation_x = Mat4::new_rotation(Vec3::new(rotation.x, 0.0, 0.0));
-    let rotation_y = Mat4::new_rotation(Vec3::new(0.0, rotation.y, 0.0));
-    let rotation_z = Mat4::new_rotation(Vec3::new(0.0, 0.0, rotation.z));
-    let rotation_matrix = rotation_z * rotation_y * rotation_x;
+use std::time::{Duration, Instant};
+
+use space_traver::autopilot::{Autopilot, AutopilotPhase};
+use space_traver::body::Body;
+use space_traver::bounds::BoundingSphere;
+use space_traver::camera::{Camera, CameraMode};
+use space_traver::chase_camera::ChaseCamera;
+use space_traver::debug::{DebugView, RenderMode};
+use space_traver::focus::Focus;
+use space_traver::framebuffer::Framebuffer;
+use space_traver::free_camera::FreeCamera;
+use space_traver::headless::{self, system_seed, HeadlessOptions};
+use space_traver::help::draw_help;
+use space_traver::hud::Hud;
+use space_traver::input::{Action, Bindings, Controls, CONTROLS_FILE, NUMBERED_ACTIONS};
+use space_traver::minimap::Minimap;
+use space_traver::mouse::MouseState;
+use space_traver::recorder::{recording_directory, save_screenshot, Recorder};
+use space_traver::scene::{Scene, DISTANCE_SCALE};
+use space_traver::ship::ShipInput;
+use space_traver::tonemap::ToneMapping;
+use space_traver::warp::StarStreaks;
+
+const SCREENSHOT_DIR: &str = "screenshots";
+const RECORDINGS_DIR: &str = "recordings";
+const RECORD_STEP: u32 = 2;
+// Radio de la cámara libre para las colisiones
+const FREE_CAMERA_RADIUS: f32 = 0.05;
+// Estrellas del efecto warp
+const STREAK_COUNT: usize = 160;
+// Paso y límite de la exposición, en EV
+const EXPOSURE_STEP: f32 = 0.5;
+const MAX_EXPOSURE: f32 = 4.0;
+// Lado del minimapa en píxeles
+const MINIMAP_SIZE: usize = 160;
+// Cuánto dura un mensaje en el título de la ventana
+const STATUS_DURATION: Duration = Duration::from_secs(3);
 
-    transform_matrix * rotation_matrix
-}
-fn create_view_matrix(eye: Vec3, center: Vec3, up: Vec3) -> Mat4 {
-    look_at(&eye, &center, &up)
-}
-fn create_perspective_matrix(window_width: f32, window_height: f32) -> Mat4 {
-    let fov = 45.0 * PI / 180.0;
-    let aspect_ratio = window_width / window_height;
-    let near = 0.1;
-    let far = 1000.0;
-
-    perspective(fov, aspect_ratio, near, far)
-}
-fn create_viewport_matrix(width: f32, height: f32) -> Mat4 {
-    Mat4::new(
-        width / 2.0,
-        0.0,
-        0.0,
-        width / 2.0,
-        0.0,
-        -height / 2.0,
-        0.0,
-        height / 2.0,
-        0.0,
-        0.0,
-        1.0,
-        0.0,
-        0.0,
-        0.0,
-        0.0,
-        1.0,
-    )
-}
-fn render(framebuffer: &mut Framebuffer, uniforms: &Uniforms, vertex_array: &[Vertex]) {
-    let mut transformed_vertices = Vec::with_capacity(vertex_array.len());
-    for vertex in vertex_array {
-        let transformed = vertex_shader(vertex, uniforms);
-        transformed_vertices.push(transformed);
-    }
-
-    let mut triangles = Vec::new();
-    for i in (0..transformed_vertices.len()).step_by(3) {
-        if i + 2 < transformed_vertices.len() {
-            triangles.push([
-                transformed_vertices[i].clone(),
-                transformed_vertices[i + 1].clone(),
-                transformed_vertices[i + 2].clone(),
-            ]);
-        }
-    }
-
-    let mut fragments = Vec::new();
-    for tri in &triangles {
-        fragments.extend(triangle(&tri[0], &tri[1], &tri[2]));
-    }
-
-    for fragment in fragments {
-        let x = fragment.position.x as usize;
-        let y = fragment.position.y as usize;
-
-        if x < framebuffer.width && y < framebuffer.height {
-            let shaded_color = sun_shader(&fragment, uniforms);
-            let color = shaded_color.to_hex();
-            framebuffer.set_current_color(color);
-            framebuffer.point(x, y, fragment.depth);
-        }
-    }
-}
-fn render_rocky_planet(
-    framebuffer: &mut Framebuffer,
-    uniforms: &Uniforms,
-    vertex_array: &[Vertex],
-) {
-    // Vertex Shader
-    let mut transformed_vertices = Vec::with_capacity(vertex_array.len());
-    for vertex in vertex_array {
-        let transformed = vertex_shader(vertex, uniforms);
-        transformed_vertices.push(transformed);
-    }
-
-    // Primitive Assembly
-    let mut triangles = Vec::new();
-    for i in (0..transformed_vertices.len()).step_by(3) {
-        if i + 2 < transformed_vertices.len() {
-            triangles.push([
-                transformed_vertices[i].clone(),
-                transformed_vertices[i + 1].clone(),
-                transformed_vertices[i + 2].clone(),
-            ]);
-        }
-    }
-
-    // Rasterization
-    let mut fragments = Vec::new();
-    for tri in &triangles {
-        fragments.extend(triangle(&tri[0], &tri[1], &tri[2]));
-    }
-
-    // Fragment Processing
-    for fragment in fragments {
-        let x = fragment.position.x as usize;
-        let y = fragment.position.y as usize;
-
-        if x < framebuffer.width && y < framebuffer.height {
-            let shaded_color = rocky_planet_shader(&fragment, uniforms);
-            let color = shaded_color.to_hex();
-            framebuffer.set_current_color(color);
-            framebuffer.point(x, y, fragment.depth);
-        }
-    }
-}
-fn render_venus(framebuffer: &mut Framebuffer, uniforms: &Uniforms, vertex_array: &[Vertex]) {
-    // Vertex Shader
-    let mut transformed_vertices = Vec::with_capacity(vertex_array.len());
-    for vertex in vertex_array {
-        let transformed = vertex_shader(vertex, uniforms);
-        transformed_vertices.push(transformed);
-    }
-
-    // Primitive Assembly
-    let mut triangles = Vec::new();
-    for i in (0..transformed_vertices.len()).step_by(3) {
-        if i + 2 < transformed_vertices.len() {
-            triangles.push([
-                transformed_vertices[i].clone(),
-                transformed_vertices[i + 1].clone(),
-                transformed_vertices[i + 2].clone(),
-            ]);
-        }
-    }
-
-    // Rasterización y procesamiento de fragmentos
-    let mut fragments = Vec::new();
-    for tri in &triangles {
-        fragments.extend(triangle(&tri[0], &tri[1], &tri[2]));
-    }
-
-    // Fragment Shader específico de Venus
-    for fragment in fragments {
-        let x = fragment.position.x as usize;
-        let y = fragment.position.y as usize;
-
-        if x < framebuffer.width && y < framebuffer.height {
-            // Aplicar el shader específico para Venus
-            let shaded_color = venus_shader(&fragment, uniforms);
-            let color = shaded_color.to_hex();
-            framebuffer.set_current_color(color);
-            framebuffer.point(x, y, fragment.depth);
-        }
-    }
-}
-fn render_earth(framebuffer: &mut Framebuffer, uniforms: &Uniforms, vertex_array: &[Vertex]) {
-    // Vertex Shader
-    let mut transformed_vertices = Vec::with_capacity(vertex_array.len());
-    for vertex in vertex_array {
-        let transformed = vertex_shader(vertex, uniforms);
-        transformed_vertices.push(transformed);
-    }
-
-    // Ensamblado de Triángulos
-    let mut triangles = Vec::new();
-    for i in (0..transformed_vertices.len()).step_by(3) {
-        if i + 2 < transformed_vertices.len() {
-            triangles.push([
-                transformed_vertices[i].clone(),
-                transformed_vertices[i + 1].clone(),
-                transformed_vertices[i + 2].clone(),
-            ]);
-        }
-    }
-
-    // Rasterización y procesamiento de fragmentos
-    let mut fragments = Vec::new();
-    for tri in &triangles {
-        fragments.extend(triangle(&tri[0], &tri[1], &tri[2]));
-    }
-
-    // Aplicar `earth_shader` en cada fragmento
-    for fragment in fragments {
-        let x = fragment.position.x as usize;
-        let y = fragment.position.y as usize;
-
-        if x < framebuffer.width && y < framebuffer.height {
-            let shaded_color = earth_shader(&fragment, uniforms);
-            let color = shaded_color.to_hex();
-            framebuffer.set_current_color(color);
-            framebuffer.point(x, y, fragment.depth);
-        }
-    }
-}
-fn render_mars(framebuffer: &mut Framebuffer, uniforms: &Uniforms, vertex_array: &[Vertex]) {
-    let mut transformed_vertices = Vec::with_capacity(vertex_array.len());
-    for vertex in vertex_array {
-        let transformed = vertex_shader(vertex, uniforms);
-        transformed_vertices.push(transformed);
-    }
-
-    let mut triangles = Vec::new();
-    for i in (0..transformed_vertices.len()).step_by(3) {
-        if i + 2 < transformed_vertices.len() {
-            triangles.push([
-                transformed_vertices[i].clone(),
-                transformed_vertices[i + 1].clone(),
-                transformed_vertices[i + 2].clone(),
-            ]);
-        }
-    }
-
-    let mut fragments = Vec::new();
-    for tri in &triangles {
-        fragments.extend(triangle(&tri[0], &tri[1], &tri[2]));
-    }
-
-    for fragment in fragments {
-        let x = fragment.position.x as usize;
-        let y = fragment.position.y as usize;
-
-        if x < framebuffer.width && y < framebuffer.height {
-            let shaded_color = mars_shader(&fragment, uniforms);
-            let color = shaded_color.to_hex();
-            framebuffer.set_current_color(color);
-            framebuffer.point(x, y, fragment.depth);
-        }
-    }
-}
-fn render_jupiter(framebuffer: &mut Framebuffer, uniforms: &Uniforms, vertex_array: &[Vertex]) {
-    let mut transformed_vertices = Vec::with_capacity(vertex_array.len());
-    for vertex in vertex_array {
-        let transformed = vertex_shader(vertex, uniforms);
-        transformed_vertices.push(transformed);
-    }
-
-    let mut triangles = Vec::new();
-    for i in (0..transformed_vertices.len()).step_by(3) {
-        if i + 2 < transformed_vertices.len() {
-            triangles.push([
-                transformed_vertices[i].clone(),
-                transformed_vertices[i + 1].clone(),
-                transformed_vertices[i + 2].clone(),
-            ]);
-        }
-    }
-
-    let mut fragments = Vec::new();
-    for tri in &triangles {
-        fragments.extend(triangle(&tri[0], &tri[1], &tri[2]));
-    }
-
-    for fragment in fragments {
-        let x = fragment.position.x as usize;
-        let y = fragment.position.y as usize;
-
-        if x < framebuffer.width && y < framebuffer.height {
-            let shaded_color = jupiter_shader(&fragment, uniforms);
-            let color = shaded_color.to_hex();
-            framebuffer.set_current_color(color);
-            framebuffer.point(x, y, fragment.depth);
-        }
-    }
-}
-fn render_moon(framebuffer: &mut Framebuffer, uniforms: &Uniforms, vertex_array: &[Vertex]) {
-    let mut transformed_vertices = Vec::with_capacity(vertex_array.len());
-    for vertex in vertex_array {
-        let transformed = vertex_shader(vertex, uniforms);
-        transformed_vertices.push(transformed);
-    }
-
-    let mut triangles = Vec::new();
-    for i in (0..transformed_vertices.len()).step_by(3) {
-        if i + 2 < transformed_vertices.len() {
-            triangles.push([
-                transformed_vertices[i].clone(),
-                transformed_vertices[i + 1].clone(),
-                transformed_vertices[i + 2].clone(),
-            ]);
-        }
-    }
-
-    let mut fragments = Vec::new();
-    for tri in &triangles {
-        fragments.extend(triangle(&tri[0], &tri[1], &tri[2]));
-    }
-
-    for fragment in fragments {
-        let x = fragment.position.x as usize;
-        let y = fragment.position.y as usize;
-
-        if x < framebuffer.width && y < framebuffer.height {
-            let shaded_color = moon_shader(&fragment, uniforms);
-            let color = shaded_color.to_hex();
-            framebuffer.set_current_color(color);
-            framebuffer.point(x, y, fragment.depth);
-        }
-    }
-}
-fn render_saturn(framebuffer: &mut Framebuffer, uniforms: &Uniforms, vertex_array: &[Vertex]) {
-    let mut transformed_vertices = Vec::with_capacity(vertex_array.len());
-    for vertex in vertex_array {
-        let transformed = vertex_shader(vertex, uniforms);
-        transformed_vertices.push(transformed);
-    }
-
-    let mut triangles = Vec::new();
-    for i in (0..transformed_vertices.len()).step_by(3) {
-        if i + 2 < transformed_vertices.len() {
-            triangles.push([
-                transformed_vertices[i].clone(),
-                transformed_vertices[i + 1].clone(),
-                transformed_vertices[i + 2].clone(),
-            ]);
-        }
-    }
-
-    let mut fragments = Vec::new();
-    for tri in &triangles {
-        fragments.extend(triangle(&tri[0], &tri[1], &tri[2]));
-    }
-
-    for fragment in fragments {
-        let x = fragment.position.x as usize;
-        let y = fragment.position.y as usize;
-
-        if x < framebuffer.width && y < framebuffer.height {
-            let shaded_color = saturn_shader(&fragment, uniforms);
-            let color = shaded_color.to_hex();
-            framebuffer.set_current_color(color);
-            framebuffer.point(x, y, fragment.depth);
-        }
-    }
-}
-fn render_saturn_rings(
-    framebuffer: &mut Framebuffer,
-    uniforms: &Uniforms,
-    vertex_array: &[Vertex],
-) {
-    let mut transformed_vertices = Vec::with_capacity(vertex_array.len());
-    for vertex in vertex_array {
-        let transformed = vertex_shader(vertex, uniforms);
-        transformed_vertices.push(transformed);
-    }
-
-    let mut triangles = Vec::new();
-    for i in (0..transformed_vertices.len()).step_by(3) {
-        if i + 2 < transformed_vertices.len() {
-            triangles.push([
-                transformed_vertices[i].clone(),
-                transformed_vertices[i + 1].clone(),
-                transformed_vertices[i + 2].clone(),
-            ]);
-        }
-    }
-
-    let mut fragments = Vec::new();
-    for tri in &triangles {
-        fragments.extend(triangle(&tri[0], &tri[1], &tri[2]));
-    }
-
-    for fragment in fragments {
-        let x = fragment.position.x as usize;
-        let y = fragment.position.y as usize;
-
-        if x < framebuffer.width && y < framebuffer.height {
-            let shaded_color = saturn_rings_shader(&fragment, uniforms);
-            let color = shaded_color.to_hex();
-            framebuffer.set_current_color(color);
-            framebuffer.point(x, y, fragment.depth);
-        }
-    }
-}
-fn render_spaceship(framebuffer: &mut Framebuffer, uniforms: &Uniforms, vertex_array: &[Vertex]) {
-    let mut transformed_vertices = Vec::with_capacity(vertex_array.len());
-    for vertex in vertex_array {
-        let transformed = vertex_shader(vertex, uniforms);
-        transformed_vertices.push(transformed);
-    }
-
-    let mut triangles = Vec::new();
-    for i in (0..transformed_vertices.len()).step_by(3) {
-        if i + 2 < transformed_vertices.len() {
-            triangles.push([
-                transformed_vertices[i].clone(),
-                transformed_vertices[i + 1].clone(),
-                transformed_vertices[i + 2].clone(),
-            ]);
-        }
-    }
-
-    let mut fragments = Vec::new();
-    for tri in &triangles {
-        fragments.extend(triangle(&tri[0], &tri[1], &tri[2]));
-    }
-
-    for fragment in fragments {
-        let x = fragment.position.x as usize;
-        let y = fragment.position.y as usize;
-
-        if x < framebuffer.width && y < framebuffer.height {
-            // Usar un shader específico para la nave espacial
-            let shaded_color = spaceship_shader(&fragment, uniforms);
-            let color = shaded_color.to_hex();
-            framebuffer.set_current_color(color);
-            framebuffer.point(x, y, fragment.depth);
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match HeadlessOptions::from_args(&args) {
+        Ok(Some(options)) => {
+            if let Err(err) = headless::run(&options, DISTANCE_SCALE) {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            }
         }
-    }
-}
-fn render_skybox(
-    framebuffer: &mut Framebuffer,
-    uniforms: &Uniforms,
-    skybox_texture: &image::RgbaImage,
-    skybox_vertices: &[Vertex],
-) {
-    for vertex in skybox_vertices {
-        let transformed = vertex_shader(vertex, uniforms);
-
-        let x = transformed.position.x as usize;
-        let y = transformed.position.y as usize;
-
-        if x < framebuffer.width && y < framebuffer.height {
-            // Mapeo de coordenadas de textura
-            let u = (vertex.tex_coords.x.clamp(0.0, 1.0) * skybox_texture.width() as f32) as u32;
-            let v = (vertex.tex_coords.y.clamp(0.0, 1.0) * skybox_texture.height() as f32) as u32;
-
-            if u >= skybox_texture.width() || v >= skybox_texture.height() {
-                continue;
+        Ok(None) => match system_seed(&args) {
+            Ok(seed) => run_window(seed),
+            Err(err) => {
+                eprintln!("{}", err);
+                std::process::exit(2);
             }
-
-            let pixel = skybox_texture.get_pixel(u, v).0;
-            let color = color::Color::new(pixel[0], pixel[1], pixel[2]);
-            framebuffer.set_current_color(color.to_hex());
-            framebuffer.point(x, y, transformed.position.z);
+        },
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(2);
         }
     }
 }
 
-fn main() {
+/// `seed` elige un sistema inventado en lugar del Sistema Solar.
+fn run_window(seed: Option<u32>) {
     let window_width = 800;
     let window_height = 600;
     let framebuffer_width = 800;
     let framebuffer_height = 600;
     let frame_delay = Duration::from_millis(16);
-    let mut time = 0;
     let mut frame_count = 0;
     let mut fps = 0;
-    let mut last_time = std::time::Instant::now();
-    let window_title = format!("Sistema Solar - FPS: {}", fps);
-    const DISTANCE_SCALE: f32 = 0.6;
+    let mut last_time = Instant::now();
+    let mut last_frame = Instant::now();
+    let system_name = match seed {
+        Some(seed) => format!("Sistema {}", seed),
+        None => String::from("Sistema Solar"),
+    };
+    let window_title = format!("{} - FPS: {}", system_name, fps);
 
     let mut framebuffer = Framebuffer::new(framebuffer_width, framebuffer_height);
     let mut window = Window::new(
@@ -515,65 +90,18 @@ fn main() {
 
     framebuffer.set_background_color(0x000000);
 
-    // Configuración del Sol
-    let translation_sun = Vec3::new(0.0, 0.0, 0.0);
-    let scale_sun = 1.5f32;
-    let rotation_sun = Vec3::new(0.0, 0.0, 0.0);
-    let obj_sun = Obj::load("assets/models/sun.obj").expect("Failed to load sun");
-    let vertex_array_sun = obj_sun.get_vertex_array();
-
-    // Cargar textura del Skybox
-    let skybox_texture = ImageReader::open("assets/textures/skyblock.png")
-        .expect("Failed to load skybox texture")
-        .decode()
-        .expect("Failed to decode skybox texture")
-        .to_rgba8();
-    let (texture_width, texture_height) = skybox_texture.dimensions();
-
-    let skybox_vertices = vec![
-        // Cara frontal
-        Vertex::new(
-            Vec3::new(-1.0, -1.0, 1.0),
-            Vec3::new(0.0, 0.0, 1.0),
-            Vec2::new(0.25, 0.33),
-        ),
-        Vertex::new(
-            Vec3::new(1.0, -1.0, 1.0),
-            Vec3::new(0.0, 0.0, 1.0),
-            Vec2::new(0.5, 0.33),
-        ),
-        Vertex::new(
-            Vec3::new(1.0, 1.0, 1.0),
-            Vec3::new(0.0, 0.0, 1.0),
-            Vec2::new(0.5, 0.66),
-        ),
-        Vertex::new(
-            Vec3::new(-1.0, 1.0, 1.0),
-            Vec3::new(0.0, 0.0, 1.0),
-            Vec2::new(0.25, 0.66),
-        ),
-        // Cara trasera
-        Vertex::new(
-            Vec3::new(1.0, -1.0, -1.0),
-            Vec3::new(0.0, 0.0, -1.0),
-            Vec2::new(0.75, 0.33),
-        ),
-        Vertex::new(
-            Vec3::new(-1.0, -1.0, -1.0),
-            Vec3::new(0.0, 0.0, -1.0),
-            Vec2::new(1.0, 0.33),
-        ),
-        Vertex::new(
-            Vec3::new(-1.0, 1.0, -1.0),
-            Vec3::new(0.0, 0.0, -1.0),
-            Vec2::new(1.0, 0.66),
-        ),
-        Vertex::new(
-            Vec3::new(1.0, 1.0, -1.0),
-            Vec3::new(0.0, 0.0, -1.0),
-            Vec2::new(0.75, 0.66),
-        ),
-    ];
+    let mut scene = Scene::load_system(seed, DISTANCE_SCALE);
+
+    // Controles del usuario; si el archivo tiene errores se usan los de por defecto
+    let Controls {
+        bindings,
+        mut mouse,
+    } = Controls::load(CONTROLS_FILE).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        Controls::default()
+    });
+    let mut show_help = false;
+    let mut status: Option<(String, Instant)> = None;
 
     // Cámara inicial
     let mut camera = Camera::new(
@@ -581,11 +109,23 @@ fn main() {
         Vec3::new(0.0, 0.0, 0.0),
         Vec3::new(0.0, 1.0, 0.0),
     );
-
+    let mut camera_mode = CameraMode::Chase;
+    let mut free_camera = FreeCamera::from_camera(&camera);
+    let mut focus = Focus::new();
+    let mut chase = ChaseCamera::new();
+    chase.snap(&mut camera, &scene.ship);
+    let mut autopilot: Option<Autopilot> = None;
+    let mut streaks = StarStreaks::new(STREAK_COUNT, 7);
+    let mut hud = Hud::new();
+    let mut minimap = Minimap::new(MINIMAP_SIZE, &scene);
+    let mut minimap_grab = false;
+    let mut left_was_down = false;
+
+    let mut recorder = Recorder::new(&recording_directory(RECORDINGS_DIR), RECORD_STEP);
     let mut time = 0;
 
     while window.is_open() {
-        if window.is_key_down(Key::Escape) {
+        if bindings.is_down(&window, Action::Quit) {
             break;
         }
 
@@ -593,341 +133,246 @@ fn main() {
         frame_count += 1;
 
         // Calcular FPS cada segundo
-        let current_time = std::time::Instant::now();
+        let current_time = Instant::now();
         let elapsed = current_time.duration_since(last_time).as_secs_f32();
+        let dt = current_time.duration_since(last_frame).as_secs_f32().min(0.1);
+        last_frame = current_time;
 
         if elapsed >= 1.0 {
             fps = frame_count;
             frame_count = 0;
             last_time = current_time;
         }
-        let window_title = format!("Sistema Solar  - FPS: {}", fps * 4);
-        window.set_title(&window_title);
-
-        // Procesar entrada de la cámara
-        handle_input(&window, &mut camera);
-
-        // Actualizar la matriz de vista de la cámara
-        let view_matrix = create_view_matrix(camera.eye, camera.center, camera.up);
-
-        framebuffer.clear();
-
-        // Posición del skybox centrada en la cámara
-        let skybox_position = camera.eye;
-        let model_matrix_skybox =
-            create_model_matrix(skybox_position, 100.0, Vec3::new(0.0, 0.0, 0.0));
-        let uniforms_skybox = Uniforms {
-            model_matrix: model_matrix_skybox,
-            view_matrix: view_matrix.clone(),
-            projection_matrix: create_perspective_matrix(window_width as f32, window_height as f32),
-            viewport_matrix: create_viewport_matrix(
-                framebuffer_width as f32,
-                framebuffer_height as f32,
-            ),
-            time,
+        let recording = if recorder.is_recording() { " - REC" } else { "" };
+        let following = match focus.target {
+            Some(index) => format!(" ({})", scene.bodies[index].name),
+            None => String::new(),
         };
-        render_skybox(
-            &mut framebuffer,
-            &uniforms_skybox,
-            &skybox_texture,
-            &skybox_vertices,
-        );
-
-        // Renderizar el Sol
-        let model_matrix_sun = create_model_matrix(translation_sun, scale_sun, rotation_sun);
-        let uniforms_sun = Uniforms {
-            model_matrix: model_matrix_sun,
-            view_matrix: view_matrix.clone(),
-            projection_matrix: create_perspective_matrix(window_width as f32, window_height as f32),
-            viewport_matrix: create_viewport_matrix(
-                framebuffer_width as f32,
-                framebuffer_height as f32,
-            ),
-            time,
+        let collisions = if scene.collisions.triangle_level {
+            format!("{} (triángulos)", scene.collisions.response.name())
+        } else {
+            scene.collisions.response.name().to_string()
         };
-        render(&mut framebuffer, &uniforms_sun, &vertex_array_sun);
-
-        //MERCURIO
-        // Configuración de Mercurio
-        let scale_mercury = 0.4f32;
-        let orbit_speed_mercury = 0.02;
-        let rotation_speed_mercury = 0.05;
-        let orbit_radius_mercury = 10.0 * DISTANCE_SCALE;
-
-        // Cargar el modelo de Mercurio
-        let obj_mercury = Obj::load("assets/models/planet.obj").expect("Failed to load planet");
-        let vertex_array_mercury = obj_mercury.get_vertex_array();
-
-        // Cálculo de la órbita y rotación de Mercurio
-        let mercury_x = orbit_radius_mercury * (time as f32 * orbit_speed_mercury).cos();
-        let mercury_y = 0.0;
-        let mercury_z = orbit_radius_mercury * (time as f32 * orbit_speed_mercury).sin();
-        let translation_mercury = Vec3::new(mercury_x, mercury_y, mercury_z);
-        let rotation_mercury = Vec3::new(0.0, time as f32 * rotation_speed_mercury, 0.0);
-
-        // Crear la matriz de modelo para Mercurio
-        let model_matrix_mercury =
-            create_model_matrix(translation_mercury, scale_mercury, rotation_mercury);
-
-        // Crear los uniforms para Mercurio
-        let uniforms_mercury = Uniforms {
-            model_matrix: model_matrix_mercury,
-            view_matrix: view_matrix.clone(),
-            projection_matrix: create_perspective_matrix(window_width as f32, window_height as f32),
-            viewport_matrix: create_viewport_matrix(
-                framebuffer_width as f32,
-                framebuffer_height as f32,
-            ),
-            time,
+        let message = match &status {
+            Some((text, since)) if since.elapsed() < STATUS_DURATION => format!(" - {}", text),
+            _ => String::new(),
         };
+        let window_title = format!(
+            "{}  - FPS: {} - Cámara: {}{} - Modo: {} - AA: {} - Colisión: {}{}{}",
+            system_name,
+            fps * 4,
+            camera_mode.name(),
+            following,
+            scene.debug.mode.name(),
+            scene.anti_aliasing.name(),
+            collisions,
+            recording,
+            message
+        );
+        window.set_title(&window_title);
 
-        // Renderizar Mercurio
-        render_rocky_planet(&mut framebuffer, &uniforms_mercury, &vertex_array_mercury);
-
-        //VENUS
-        let scale_venus = 0.55f32;
-        let orbit_speed_venus = 0.015;
-        let rotation_speed_venus = 0.03;
-        let orbit_radius_venus = 18.0 * DISTANCE_SCALE;
-        let obj_venus = Obj::load("assets/models/planet.obj").expect("Failed to load planet");
-        let vertex_array_venus = obj_venus.get_vertex_array();
-
-        let venus_x = orbit_radius_venus * (time as f32 * orbit_speed_venus).cos();
-        let venus_y = 0.0;
-        let venus_z = orbit_radius_venus * (time as f32 * orbit_speed_venus).sin();
-        let translation_venus = Vec3::new(venus_x, venus_y, venus_z);
-
-        let rotation_venus = Vec3::new(0.0, time as f32 * rotation_speed_venus, 0.0);
-        let model_matrix_venus =
-            create_model_matrix(translation_venus, scale_venus, rotation_venus);
-        let uniforms_venus = Uniforms {
-            model_matrix: model_matrix_venus,
-            view_matrix: view_matrix.clone(),
-            projection_matrix: create_perspective_matrix(window_width as f32, window_height as f32),
-            viewport_matrix: create_viewport_matrix(
-                framebuffer_width as f32,
-                framebuffer_height as f32,
-            ),
-            time,
-        };
-        render_venus(&mut framebuffer, &uniforms_venus, &vertex_array_venus);
-
-        //TIERRA Y LUNA
-        // Configuración de la Tierra
-        let scale_earth = 0.6f32;
-        let orbit_speed_earth = 0.01;
-        let rotation_speed_earth = 0.02;
-        let orbit_radius_earth = 25.0 * DISTANCE_SCALE;
-
-        // Cargar el modelo de la Tierra
-        let obj_earth = Obj::load("assets/models/planet.obj").expect("Failed to load planet");
-        let vertex_array_earth = obj_earth.get_vertex_array();
-
-        // Configuración de la Luna
-        let scale_moon = 0.15f32;
-        let orbit_speed_moon = 0.03;
-        let rotation_speed_moon = 0.05;
-        let orbit_radius_moon = 2.5;
-
-        let obj_moon = Obj::load("assets/models/planet.obj").expect("Failed to load moon");
-        let vertex_array_moon = obj_moon.get_vertex_array();
-        let earth_x = orbit_radius_earth * (time as f32 * orbit_speed_earth).cos();
-        let earth_y = 0.0;
-        let earth_z = orbit_radius_earth * (time as f32 * orbit_speed_earth).sin();
-        let translation_earth = Vec3::new(earth_x, earth_y, earth_z);
-
-        let rotation_earth = Vec3::new(0.0, time as f32 * rotation_speed_earth, 0.0);
-        let moon_x =
-            translation_earth.x + orbit_radius_moon * (time as f32 * orbit_speed_moon).cos();
-        let moon_y = translation_earth.y + 2.0;
-        let moon_z =
-            translation_earth.z + orbit_radius_moon * (time as f32 * orbit_speed_moon).sin();
-        let translation_moon = Vec3::new(moon_x, moon_y, moon_z);
-
-        // Rotación de la Luna sobre su eje
-        let rotation_moon = Vec3::new(0.0, time as f32 * rotation_speed_moon, 0.0);
-
-        // Renderizar la Tierra
-        let model_matrix_earth =
-            create_model_matrix(translation_earth, scale_earth, rotation_earth);
-        let uniforms_earth = Uniforms {
-            model_matrix: model_matrix_earth,
-            view_matrix: view_matrix.clone(),
-            projection_matrix: create_perspective_matrix(window_width as f32, window_height as f32),
-            viewport_matrix: create_viewport_matrix(
-                framebuffer_width as f32,
-                framebuffer_height as f32,
-            ),
-            time,
-        };
-        render_earth(&mut framebuffer, &uniforms_earth, &vertex_array_earth);
-
-        // Renderizar la Luna
-        let model_matrix_moon = create_model_matrix(translation_moon, scale_moon, rotation_moon);
-        let uniforms_moon = Uniforms {
-            model_matrix: model_matrix_moon,
-            view_matrix: view_matrix.clone(),
-            projection_matrix: create_perspective_matrix(window_width as f32, window_height as f32),
-            viewport_matrix: create_viewport_matrix(
-                framebuffer_width as f32,
-                framebuffer_height as f32,
-            ),
-            time,
-        };
-        render_moon(&mut framebuffer, &uniforms_moon, &vertex_array_moon);
-
-        //MARTE
-        // Configuración de Marte
-        let scale_mars = 0.5f32;
-        let orbit_speed_mars = 0.008; // Velocidad de traslación de Marte
-        let rotation_speed_mars = 0.03; // Velocidad de rotación de Marte
-        let orbit_radius_mars = 35.0 * DISTANCE_SCALE; // Distancia de Marte al Sol
-
-        // Cargar el modelo de Marte
-        let obj_mars = Obj::load("assets/models/planet.obj").expect("Failed to load planet");
-        let vertex_array_mars = obj_mars.get_vertex_array();
-        // Calcular la posición de Marte en su órbita alrededor del Sol
-        let mars_x = orbit_radius_mars * (time as f32 * orbit_speed_mars).cos();
-        let mars_y = 0.0;
-        let mars_z = orbit_radius_mars * (time as f32 * orbit_speed_mars).sin();
-        let translation_mars = Vec3::new(mars_x, mars_y, mars_z);
-
-        // Rotación de Marte sobre su eje
-        let rotation_mars = Vec3::new(0.0, time as f32 * rotation_speed_mars, 0.0);
-
-        // Renderizar Marte
-        let model_matrix_mars = create_model_matrix(translation_mars, scale_mars, rotation_mars);
-        let uniforms_mars = Uniforms {
-            model_matrix: model_matrix_mars,
-            view_matrix: view_matrix.clone(),
-            projection_matrix: create_perspective_matrix(window_width as f32, window_height as f32),
-            viewport_matrix: create_viewport_matrix(
-                framebuffer_width as f32,
-                framebuffer_height as f32,
-            ),
-            time,
-        };
-        render_mars(&mut framebuffer, &uniforms_mars, &vertex_array_mars);
-
-        //JUPITER
-        // Configuración de Júpiter
-        let scale_jupiter = 1.2f32; // Tamaño más grande por ser un gigante gaseoso
-        let orbit_speed_jupiter = 0.005; // Velocidad de traslación de Júpiter
-        let rotation_speed_jupiter = 0.02; // Velocidad de rotación de Júpiter
-        let orbit_radius_jupiter = 50.0 * DISTANCE_SCALE; // Distancia de Júpiter al Sol
-
-        // Cargar el modelo de Júpiter
-        let obj_jupiter = Obj::load("assets/models/planet.obj").expect("Failed to load planet");
-        let vertex_array_jupiter = obj_jupiter.get_vertex_array();
-        // Calcular la posición de Júpiter en su órbita alrededor del Sol
-        let jupiter_x = orbit_radius_jupiter * (time as f32 * orbit_speed_jupiter).cos();
-        let jupiter_y = 0.0;
-        let jupiter_z = orbit_radius_jupiter * (time as f32 * orbit_speed_jupiter).sin();
-        let translation_jupiter = Vec3::new(jupiter_x, jupiter_y, jupiter_z);
-
-        // Rotación de Júpiter sobre su eje
-        let rotation_jupiter = Vec3::new(0.0, time as f32 * rotation_speed_jupiter, 0.0);
-
-        // Renderizar Júpiter
-        let model_matrix_jupiter =
-            create_model_matrix(translation_jupiter, scale_jupiter, rotation_jupiter);
-        let uniforms_jupiter = Uniforms {
-            model_matrix: model_matrix_jupiter,
-            view_matrix: view_matrix.clone(),
-            projection_matrix: create_perspective_matrix(window_width as f32, window_height as f32),
-            viewport_matrix: create_viewport_matrix(
-                framebuffer_width as f32,
-                framebuffer_height as f32,
-            ),
-            time,
-        };
-        render_jupiter(&mut framebuffer, &uniforms_jupiter, &vertex_array_jupiter);
-
-        //SATURNO
-        // Configuración de Saturno
-        let scale_saturn = 1.0f32; // Tamaño de Saturno
-        let orbit_speed_saturn = 0.003; // Velocidad de traslación de Saturno
-        let rotation_speed_saturn = 0.015; // Velocidad de rotación de Saturno
-        let orbit_radius_saturn = 70.0 * DISTANCE_SCALE; // Distancia de Saturno al Sol
-
-        // Cargar el modelo de Saturno
-        let obj_saturn = Obj::load("assets/models/planet.obj").expect("Failed to load planet");
-        let vertex_array_saturn = obj_saturn.get_vertex_array();
-
-        // Configuración de los Anillos de Saturno
-        let scale_rings = 2.5f32; // Tamaño más grande para los anillos
-        let ring_rotation_speed = 0.01; // Velocidad de rotación de los anillos (ligeramente distinta)
-
-        // Calcular la posición de Saturno en su órbita alrededor del Sol
-        let saturn_x = orbit_radius_saturn * (time as f32 * orbit_speed_saturn).cos();
-        let saturn_y = 0.0;
-        let saturn_z = orbit_radius_saturn * (time as f32 * orbit_speed_saturn).sin();
-        let translation_saturn = Vec3::new(saturn_x, saturn_y, saturn_z);
-
-        // Rotación de Saturno sobre su eje
-        let rotation_saturn = Vec3::new(0.0, time as f32 * rotation_speed_saturn, 0.0);
-
-        // Renderizar Saturno
-        let model_matrix_saturn =
-            create_model_matrix(translation_saturn, scale_saturn, rotation_saturn);
-        let uniforms_saturn = Uniforms {
-            model_matrix: model_matrix_saturn,
-            view_matrix: view_matrix.clone(),
-            projection_matrix: create_perspective_matrix(window_width as f32, window_height as f32),
-            viewport_matrix: create_viewport_matrix(
-                framebuffer_width as f32,
-                framebuffer_height as f32,
-            ),
-            time,
-        };
-        render_saturn(&mut framebuffer, &uniforms_saturn, &vertex_array_saturn);
-
-        // Rotación de los Anillos de Saturno
-        let ring_rotation = Vec3::new(0.0, time as f32 * ring_rotation_speed, 0.0);
-
-        // Renderizar los Anillos de Saturno con su propio shader
-        let model_matrix_rings =
-            create_model_matrix(translation_saturn, scale_rings, ring_rotation);
-        let uniforms_rings = Uniforms {
-            model_matrix: model_matrix_rings,
-            view_matrix: view_matrix.clone(),
-            projection_matrix: create_perspective_matrix(window_width as f32, window_height as f32),
-            viewport_matrix: create_viewport_matrix(
-                framebuffer_width as f32,
-                framebuffer_height as f32,
-            ),
-            time,
-        };
-        render_saturn_rings(&mut framebuffer, &uniforms_rings, &vertex_array_saturn);
-
-        // Configuración de la Nave Espacial
-        let scale_ship = 0.009f32;
-        let rotation_ship = Vec3::new(0.0, 0.0, 0.0);
-        let obj_ship =
-            Obj::load("assets/models/naveEspacial1.obj").expect("Failed to load spaceship");
-        let vertex_array_ship = obj_ship.get_vertex_array();
-
-        // Posicionar la nave espacial frente a la cámara
-        let ship_offset = 0.5; // Distancia de la nave a la cámara
-        let ship_position = camera.eye + camera.forward() * ship_offset;
-        let translation_ship = Vec3::new(ship_position.x, ship_position.y, ship_position.z);
-
-        // Renderizar la Nave Espacial
-        let model_matrix_ship = create_model_matrix(translation_ship, scale_ship, rotation_ship);
-        let uniforms_ship = Uniforms {
-            model_matrix: model_matrix_ship,
-            view_matrix: view_matrix.clone(),
-            projection_matrix: create_perspective_matrix(window_width as f32, window_height as f32),
-            viewport_matrix: create_viewport_matrix(
-                framebuffer_width as f32,
-                framebuffer_height as f32,
-            ),
-            time,
+        // Procesar entrada de la cámara
+        if bindings.is_pressed(&window, Action::ToggleCameraMode) {
+            camera_mode = camera_mode.next();
+            match camera_mode {
+                CameraMode::Chase => chase.snap(&mut camera, &scene.ship),
+                CameraMode::Orbit => {
+                    // Se empieza orbitando la nave, con el "arriba" del mundo
+                    camera.center = scene.ship.position;
+                    camera.up = Vec3::new(0.0, 1.0, 0.0);
+                }
+                CameraMode::FreeFlight => {
+                    free_camera = FreeCamera::from_camera(&camera);
+                    focus.set_target(None, &camera, 0.0);
+                }
+            }
+        }
+        let body_count = scene.bodies.len();
+        let mut mouse_state = read_mouse(&window);
+        let mut selection = handle_focus_keys(&window, &bindings, focus.target, body_count);
+        // Un clic en el minimapa elige objetivo y no se usa para mover la cámara
+        if mouse_state.left && !minimap_grab {
+            minimap_grab = mouse_state
+                .position
+                .is_some_and(|(x, y)| minimap.show && minimap.contains(&framebuffer, x, y));
+            if let Some((x, y)) = mouse_state.position.filter(|_| !left_was_down) {
+                if let Some(target) = minimap.pick(&framebuffer, &scene, time, x, y) {
+                    selection = Some(Some(target));
+                }
+            }
+        }
+        left_was_down = mouse_state.left;
+        if !mouse_state.left {
+            minimap_grab = false;
+        }
+        if minimap_grab {
+            mouse_state.left = false;
+        }
+        if let Some(target) = selection {
+            if camera_mode != CameraMode::Orbit {
+                camera.up = Vec3::new(0.0, 1.0, 0.0);
+                camera_mode = CameraMode::Orbit;
+            }
+            let radius = target.map_or(0.0, |index| scene.body_bounds(index, time).radius);
+            focus.set_target(target, &camera, radius);
+        }
+        if bindings.is_pressed(&window, Action::Autopilot) {
+            let text = match (autopilot.take(), focus.target) {
+                (Some(pilot), _) => {
+                    format!("Piloto automático a {} cancelado", scene.bodies[pilot.target].name)
+                }
+                (None, Some(target)) if scene.bodies[target].gravity > 0.0 => {
+                    // El viaje se ve desde la nave
+                    autopilot = Some(Autopilot::engage(&scene, target, time));
+                    camera_mode = CameraMode::Chase;
+                    chase.snap(&mut camera, &scene.ship);
+                    format!("Piloto automático hacia {}", scene.bodies[target].name)
+                }
+                (None, Some(target)) => {
+                    format!("No se puede orbitar {}", scene.bodies[target].name)
+                }
+                (None, None) => String::from("Elige un cuerpo para el piloto automático"),
+            };
+            status = Some((text, Instant::now()));
+        }
+        if bindings.is_pressed(&window, Action::InvertMouseY) {
+            mouse.invert_y = !mouse.invert_y;
+        }
+        let mut ship_input = ShipInput::default();
+        match camera_mode {
+            CameraMode::Chase => {
+                ship_input = read_ship_input(&window, &bindings);
+                mouse.update_chase(&mouse_state, &mut chase);
+            }
+            CameraMode::Orbit => {
+                handle_input(&window, &bindings, &mut camera);
+                // Desplazar la cámara con el mouse la separa del cuerpo que seguía
+                if mouse.update_orbit(&mouse_state, &mut camera).panned {
+                    focus.set_target(None, &camera, 0.0);
+                }
+                if let Some(index) = focus.target {
+                    focus.update(&mut camera, scene.body_bounds(index, time).center, dt);
+                }
+            }
+            CameraMode::FreeFlight => {
+                handle_free_flight_input(&window, &bindings, &mut free_camera, dt);
+                mouse.update_free_flight(&mouse_state, &mut free_camera);
+                let sphere = BoundingSphere {
+                    center: free_camera.position,
+                    radius: FREE_CAMERA_RADIUS,
+                };
+                for (_, contact) in scene.contacts(&sphere, time) {
+                    free_camera.push_out(&contact);
+                }
+                free_camera.apply_to(&mut camera);
+            }
+        }
+        // Con el piloto automático la nave no responde a los controles ni choca
+        if let Some(pilot) = &mut autopilot {
+            pilot.update(&mut scene, time, dt);
+            if pilot.phase == AutopilotPhase::Arrived {
+                let body = &scene.bodies[pilot.target].name;
+                let text = format!("En órbita de estacionamiento de {}", body);
+                status = Some((text, Instant::now()));
+                autopilot = None;
+            }
+        }
+        let warp = autopilot.as_ref().map_or(0.0, Autopilot::warp_intensity);
+        streaks.update(warp, dt);
+        // La nave sigue a la deriva (y cayendo) aunque se esté usando otra cámara
+        let previous_frame = scene.ship_frame();
+        if autopilot.is_none() {
+            scene.fly_ship(&ship_input, time, dt);
+        }
+        if let (Some(previous), Some(frame)) = (previous_frame, scene.ship_frame()) {
+            if previous != frame {
+                let text = format!("Gravedad dominante: {}", scene.bodies[frame].name);
+                status = Some((text, Instant::now()));
+            }
+        }
+        let impacts = if autopilot.is_none() {
+            scene.collide_ship(time, dt)
+        } else {
+            Vec::new()
         };
+        for event in impacts {
+            let body = &scene.bodies[event.body].name;
+            let text = if event.crashed {
+                format!("¡La nave se estrelló contra {}!", body)
+            } else {
+                format!("Impacto con {} a {:.2} u/s", body, event.impact_speed)
+            };
+            status = Some((text, Instant::now()));
+            if event.crashed {
+                scene.reset_ship();
+                if camera_mode == CameraMode::Chase {
+                    chase.snap(&mut camera, &scene.ship);
+                }
+                break;
+            }
+        }
+        if camera_mode == CameraMode::Chase {
+            chase.update(&mut camera, &scene.ship, dt);
+        }
+        handle_orbit_toggles(
+            &window,
+            &bindings,
+            &mut scene.bodies,
+            &mut scene.show_orbits,
+            &mut scene.show_trails,
+        );
+        if bindings.is_pressed(&window, Action::ToggleHud) {
+            let show = !(hud.show_labels || hud.show_telemetry || hud.show_info);
+            hud.show_labels = show;
+            hud.show_telemetry = show;
+            hud.show_info = show;
+        }
+        if bindings.is_pressed(&window, Action::ToggleBodyInfo) {
+            hud.show_info = !hud.show_info;
+        }
+        if bindings.is_pressed(&window, Action::ToggleMinimap) {
+            minimap.show = !minimap.show;
+        }
+        if bindings.is_pressed(&window, Action::ToggleTrajectory) {
+            scene.show_trajectory = !scene.show_trajectory;
+        }
+        handle_debug_toggles(&window, &bindings, &mut scene.debug);
+        if let Some(text) = handle_tone_mapping(&window, &bindings, &mut scene.tone_mapping) {
+            status = Some((text, Instant::now()));
+        }
+        if bindings.is_pressed(&window, Action::ToggleBloom) {
+            if let Some(enabled) = scene.post.toggle("Bloom") {
+                let text = format!("Bloom {}", if enabled { "activado" } else { "desactivado" });
+                status = Some((text, Instant::now()));
+            }
+        }
+        if bindings.is_pressed(&window, Action::CycleAntiAliasing) {
+            scene.anti_aliasing = scene.anti_aliasing.next();
+            let text = format!("Antialiasing: {}", scene.anti_aliasing.name());
+            status = Some((text, Instant::now()));
+        }
+        if bindings.is_pressed(&window, Action::CycleCollisionResponse) {
+            scene.collisions.response = scene.collisions.response.next();
+        }
+        if bindings.is_pressed(&window, Action::ToggleMeshCollisions) {
+            scene.collisions.triangle_level = !scene.collisions.triangle_level;
+        }
+        if bindings.is_pressed(&window, Action::ToggleHelp) {
+            show_help = !show_help;
+        }
 
-        render_spaceship(&mut framebuffer, &uniforms_ship, &vertex_array_ship);
-
-        //AQUI TERMINA
+        scene.update(time);
+        scene.render(&mut framebuffer, &camera, time);
+        streaks.draw(&mut framebuffer, warp);
+        hud.update(dt);
+        hud.draw(&mut framebuffer, &scene, &camera, time, focus.target);
+        minimap.draw(&mut framebuffer, &scene, camera.eye, time, focus.target);
+
+        // La ayuda no aparece en las capturas ni en las grabaciones
+        handle_capture(&window, &bindings, &framebuffer, &mut recorder, time);
+        if show_help {
+            draw_help(&mut framebuffer, &bindings, camera_mode);
+        }
 
         window
             .update_with_buffer(&framebuffer.buffer, framebuffer_width, framebuffer_height)
@@ -937,48 +382,199 @@ fn main() {
     }
 }
 
-fn handle_input(window: &Window, camera: &mut Camera) {
+fn handle_input(window: &Window, bindings: &Bindings, camera: &mut Camera) {
     let movement_speed = 1.0;
     let rotation_speed = PI / 50.0;
     let zoom_speed = 0.1;
 
     //  camera orbit controls
-    if window.is_key_down(Key::Left) {
-        camera.orbit(rotation_speed, 0.0);
+    let yaw = bindings.axis(window, Action::OrbitLeft, Action::OrbitRight);
+    let pitch = bindings.axis(window, Action::OrbitDown, Action::OrbitUp);
+    if yaw != 0.0 || pitch != 0.0 {
+        camera.orbit(yaw * rotation_speed, pitch * rotation_speed);
     }
-    if window.is_key_down(Key::Right) {
-        camera.orbit(-rotation_speed, 0.0);
+
+    // Camera movement controls
+    let movement = Vec3::new(
+        bindings.axis(window, Action::LookRight, Action::LookLeft),
+        bindings.axis(window, Action::LookUp, Action::LookDown),
+        0.0,
+    ) * movement_speed;
+    if movement.magnitude() > 0.0 {
+        camera.move_center(movement);
+    }
+
+    // Camera zoom controls
+    let zoom = bindings.axis(window, Action::ZoomIn, Action::ZoomOut);
+    if zoom != 0.0 {
+        camera.zoom(zoom * zoom_speed);
     }
-    if window.is_key_down(Key::W) {
-        camera.orbit(0.0, -rotation_speed);
+}
+
+fn read_mouse(window: &Window) -> MouseState {
+    MouseState {
+        // Pass sigue reportando la posición si se arrastra fuera de la ventana
+        position: window.get_mouse_pos(MouseMode::Pass),
+        left: window.get_mouse_down(MouseButton::Left),
+        right: window.get_mouse_down(MouseButton::Right),
+        scroll: window.get_scroll_wheel().map_or(0.0, |(_, y)| y),
     }
-    if window.is_key_down(Key::S) {
-        camera.orbit(0.0, rotation_speed);
+}
+
+fn read_ship_input(window: &Window, bindings: &Bindings) -> ShipInput {
+    ShipInput {
+        thrust: Vec3::new(
+            bindings.axis(window, Action::ShipRight, Action::ShipLeft),
+            bindings.axis(window, Action::ShipUp, Action::ShipDown),
+            bindings.axis(window, Action::ShipForward, Action::ShipBackward),
+        ),
+        torque: Vec3::new(
+            bindings.axis(window, Action::ShipPitchUp, Action::ShipPitchDown),
+            bindings.axis(window, Action::ShipYawLeft, Action::ShipYawRight),
+            bindings.axis(window, Action::ShipRollLeft, Action::ShipRollRight),
+        ),
+        brake: bindings.is_down(window, Action::ShipBrake),
+        boost: bindings.is_down(window, Action::ShipBoost),
+        circularize: bindings.is_down(window, Action::ShipCircularize),
     }
+}
 
-    // Camera movement controls
-    let mut movement = Vec3::new(0.0, 0.0, 0.0);
-    if window.is_key_down(Key::A) {
-        movement.x -= movement_speed;
+fn handle_free_flight_input(
+    window: &Window,
+    bindings: &Bindings,
+    free_camera: &mut FreeCamera,
+    dt: f32,
+) {
+    let thrust = Vec3::new(
+        bindings.axis(window, Action::FlyRight, Action::FlyLeft),
+        bindings.axis(window, Action::FlyUp, Action::FlyDown),
+        bindings.axis(window, Action::FlyForward, Action::FlyBackward),
+    );
+    let torque = Vec3::new(
+        bindings.axis(window, Action::PitchUp, Action::PitchDown),
+        bindings.axis(window, Action::YawLeft, Action::YawRight),
+        bindings.axis(window, Action::RollLeft, Action::RollRight),
+    );
+
+    free_camera.update(thrust, torque, dt);
+}
+
+/// Devuelve el nuevo objetivo si se pidió un cambio.
+fn handle_focus_keys(
+    window: &Window,
+    bindings: &Bindings,
+    current: Option<usize>,
+    body_count: usize,
+) -> Option<Option<usize>> {
+    if bindings.is_pressed(window, Action::NextTarget) {
+        let next = current.map_or(0, |index| (index + 1) % body_count);
+        return Some(Some(next));
     }
-    if window.is_key_down(Key::D) {
-        movement.x += movement_speed;
+    if bindings.is_pressed(window, Action::ClearTarget) {
+        return Some(None);
     }
-    if window.is_key_down(Key::Q) {
-        movement.y += movement_speed;
+
+    (0..body_count.min(NUMBERED_ACTIONS))
+        .find(|index| bindings.is_pressed(window, Action::FocusBody(*index)))
+        .map(Some)
+}
+
+fn handle_orbit_toggles(
+    window: &Window,
+    bindings: &Bindings,
+    bodies: &mut [Body],
+    show_orbits: &mut bool,
+    show_trails: &mut bool,
+) {
+    if bindings.is_pressed(window, Action::ToggleOrbits) {
+        *show_orbits = !*show_orbits;
     }
-    if window.is_key_down(Key::E) {
-        movement.y -= movement_speed;
+    if bindings.is_pressed(window, Action::ToggleTrails) {
+        *show_trails = !*show_trails;
     }
-    if movement.magnitude() > 0.0 {
-        camera.move_center(movement);
+
+    // Cada acción numerada corresponde a un cuerpo que orbita, en orden
+    let orbiting = bodies.iter_mut().filter(|body| body.has_orbit());
+    for (index, body) in orbiting.take(NUMBERED_ACTIONS).enumerate() {
+        if bindings.is_pressed(window, Action::ToggleBodyOrbit(index)) {
+            body.show_orbit = !body.show_orbit;
+        }
     }
+}
 
-    // Camera zoom controls
-    if window.is_key_down(Key::Up) {
-        camera.zoom(zoom_speed);
+fn handle_debug_toggles(window: &Window, bindings: &Bindings, debug: &mut DebugView) {
+    if bindings.is_pressed(window, Action::CycleRenderMode) {
+        debug.mode = debug.mode.next();
     }
-    if window.is_key_down(Key::Down) {
-        camera.zoom(-zoom_speed);
+    if bindings.is_pressed(window, Action::ToggleWireframe) {
+        debug.mode = match debug.mode {
+            RenderMode::Wireframe => RenderMode::Shaded,
+            _ => RenderMode::Wireframe,
+        };
+    }
+    if bindings.is_pressed(window, Action::ToggleBoundingBoxes) {
+        debug.show_bounding_boxes = !debug.show_bounding_boxes;
+    }
+}
+
+/// Curva y exposición; devuelve el mensaje a mostrar si algo cambió.
+fn handle_tone_mapping(
+    window: &Window,
+    bindings: &Bindings,
+    tone_mapping: &mut ToneMapping,
+) -> Option<String> {
+    let before = *tone_mapping;
+    if bindings.is_pressed(window, Action::CycleToneMapping) {
+        tone_mapping.operator = tone_mapping.operator.next();
+    }
+    if bindings.is_pressed(window, Action::ExposureUp) {
+        tone_mapping.exposure = (tone_mapping.exposure + EXPOSURE_STEP).min(MAX_EXPOSURE);
+    }
+    if bindings.is_pressed(window, Action::ExposureDown) {
+        tone_mapping.exposure = (tone_mapping.exposure - EXPOSURE_STEP).max(-MAX_EXPOSURE);
+    }
+    (*tone_mapping != before).then(|| {
+        format!(
+            "Tonemapping: {}, exposición {:+.1} EV",
+            tone_mapping.operator.name(),
+            tone_mapping.exposure
+        )
+    })
+}
+
+fn handle_capture(
+    window: &Window,
+    bindings: &Bindings,
+    framebuffer: &Framebuffer,
+    recorder: &mut Recorder,
+    time: u32,
+) {
+    if bindings.is_pressed(window, Action::Screenshot) {
+        match save_screenshot(framebuffer, SCREENSHOT_DIR) {
+            Ok(path) => println!("Captura guardada en {}", path),
+            Err(err) => eprintln!("{}", err),
+        }
+    }
+
+    if bindings.is_pressed(window, Action::ToggleRecording) {
+        if recorder.is_recording() {
+            recorder.stop();
+            println!(
+                "Grabación detenida: {} cuadros en {}",
+                recorder.frames(),
+                recorder.directory().display()
+            );
+        } else {
+            *recorder = Recorder::new(&recording_directory(RECORDINGS_DIR), RECORD_STEP);
+            match recorder.start(time) {
+                Ok(()) => println!("Grabando en {}", recorder.directory().display()),
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+    }
+
+    if let Err(err) = recorder.capture(framebuffer, time) {
+        eprintln!("{}", err);
+        recorder.stop();
     }
 }
@@ -0,0 +1,247 @@
+use nalgebra_glm::Vec3;
+use crate::body::body_position;
+use crate::color::Color;
+use crate::framebuffer::Framebuffer;
+use crate::line::polyline;
+use crate::orbit::predicted_orbit;
+use crate::pipeline::{create_top_down_uniforms, project_to_screen};
+use crate::scene::Scene;
+use crate::tonemap::ToneMapping;
+use crate::Uniforms;
+
+const BACKGROUND_COLOR: u32 = 0x05050F;
+const BORDER_COLOR: u32 = 0x808080;
+const SUN_COLOR: u32 = 0xFFD27F;
+const SHIP_COLOR: u32 = 0x78FFA0;
+const CAMERA_COLOR: u32 = 0xFFFFFF;
+const HIGHLIGHT_COLOR: u32 = 0x7FC8FF;
+/// Opacidad del recuadro sobre la escena.
+const OPACITY: f32 = 0.85;
+/// Radio mínimo en píxeles de un cuerpo, para que los pequeños se puedan ver y tocar.
+const MIN_BODY_RADIUS: f32 = 2.0;
+/// Distancia en píxeles a la que un clic todavía selecciona un cuerpo.
+const PICK_RADIUS: f32 = 6.0;
+
+/// Vista cenital ortográfica del sistema en una esquina de la pantalla: órbitas,
+/// posición actual de los cuerpos, la nave con su trayectoria y la cámara.
+///
+/// Se dibuja en su propio framebuffer y luego se mezcla sobre la imagen principal.
+pub struct Minimap {
+    pub show: bool,
+    /// Separación con los bordes de la pantalla en píxeles.
+    pub margin: usize,
+    /// Unidades del mundo visibles a cada lado del Sol.
+    pub extent: f32,
+    framebuffer: Framebuffer,
+}
+
+impl Minimap {
+    /// Minimapa cuadrado de `size` píxeles que abarca todas las órbitas de `scene`.
+    pub fn new(size: usize, scene: &Scene) -> Self {
+        let farthest = (0..scene.bodies.len())
+            .map(|index| {
+                body_position(&scene.bodies, index, 0.0).magnitude()
+                    + scene.body_bounds(index, 0).radius
+            })
+            .fold(0.0, f32::max);
+        let mut framebuffer = Framebuffer::new(size, size);
+        framebuffer.set_background_color(BACKGROUND_COLOR);
+        Minimap {
+            show: true,
+            margin: 10,
+            extent: farthest * 1.05,
+            framebuffer,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.framebuffer.width
+    }
+
+    /// Esquina superior izquierda del recuadro dentro de `screen` (arriba a la derecha).
+    pub fn origin(&self, screen: &Framebuffer) -> (usize, usize) {
+        let left = screen.width.saturating_sub(self.size() + self.margin);
+        (left, self.margin)
+    }
+
+    /// Si el punto `(x, y)` de la pantalla cae dentro del recuadro.
+    pub fn contains(&self, screen: &Framebuffer, x: f32, y: f32) -> bool {
+        let (left, top) = self.origin(screen);
+        let size = self.size() as f32;
+        x >= left as f32 && y >= top as f32 && x < left as f32 + size && y < top as f32 + size
+    }
+
+    /// Dibuja el minimapa encima de `screen`. `camera_position` se marca si no coincide
+    /// con la nave; `highlight` es el cuerpo seleccionado.
+    pub fn draw(
+        &mut self,
+        screen: &mut Framebuffer,
+        scene: &Scene,
+        camera_position: Vec3,
+        time: u32,
+        highlight: Option<usize>,
+    ) {
+        if !self.show {
+            return;
+        }
+        self.framebuffer.clear();
+        let uniforms = self.uniforms(time);
+
+        for (index, body) in scene.bodies.iter().enumerate() {
+            if body.show_orbit && body.has_orbit() {
+                let points = predicted_orbit(&scene.bodies, index, time as f32);
+                let colors = vec![body.orbit_color * 0.5; points.len()];
+                polyline(&mut self.framebuffer, &uniforms, &points, &colors);
+            }
+        }
+        let trajectory = scene.predicted_trajectory(time);
+        let colors = vec![Color::from_hex(SHIP_COLOR) * 0.5; trajectory.len()];
+        polyline(&mut self.framebuffer, &uniforms, &trajectory, &colors);
+        // Las líneas van a la imagen HDR; lo demás se dibuja ya en `buffer`
+        self.framebuffer.present(&ToneMapping::linear());
+
+        for (index, body) in scene.bodies.iter().enumerate() {
+            if !body.solid {
+                continue;
+            }
+            let Some((x, y, radius)) = self.body_disc(&uniforms, scene, index, time) else {
+                continue;
+            };
+            let color = if body.has_orbit() { body.orbit_color.to_hex() } else { SUN_COLOR };
+            fill_circle(&mut self.framebuffer, x, y, radius, color);
+            if highlight == Some(index) {
+                draw_circle(&mut self.framebuffer, x, y, radius + 3.0, HIGHLIGHT_COLOR);
+            }
+        }
+
+        if (camera_position - scene.ship.position).magnitude() > 1e-3 {
+            if let Some(camera) = project_to_screen(&uniforms, camera_position) {
+                draw_cross(&mut self.framebuffer, camera.x, camera.y, CAMERA_COLOR);
+            }
+        }
+        if let Some(ship) = project_to_screen(&uniforms, scene.ship.position) {
+            let forward = scene.ship.forward();
+            draw_arrow(&mut self.framebuffer, ship.x, ship.y, forward.x, forward.z, SHIP_COLOR);
+        }
+
+        self.blit(screen);
+    }
+
+    /// El cuerpo sólido bajo el punto `(x, y)` de la pantalla, o el más cercano a unos
+    /// pocos píxeles; `None` si el punto no está sobre el minimapa o no hay ninguno.
+    pub fn pick(
+        &self,
+        screen: &Framebuffer,
+        scene: &Scene,
+        time: u32,
+        x: f32,
+        y: f32,
+    ) -> Option<usize> {
+        if !self.show || !self.contains(screen, x, y) {
+            return None;
+        }
+        let (left, top) = self.origin(screen);
+        let (local_x, local_y) = (x - left as f32, y - top as f32);
+        let uniforms = self.uniforms(time);
+
+        let mut best: Option<(f32, usize)> = None;
+        for (index, body) in scene.bodies.iter().enumerate() {
+            if !body.solid {
+                continue;
+            }
+            let Some((cx, cy, radius)) = self.body_disc(&uniforms, scene, index, time) else {
+                continue;
+            };
+            let distance = (cx - local_x).hypot(cy - local_y) - radius;
+            if distance <= PICK_RADIUS && best.is_none_or(|(closest, _)| distance < closest) {
+                best = Some((distance, index));
+            }
+        }
+        best.map(|(_, index)| index)
+    }
+
+    fn uniforms(&self, time: u32) -> Uniforms {
+        create_top_down_uniforms(&self.framebuffer, self.extent, time)
+    }
+
+    /// Centro y radio en píxeles del minimapa del cuerpo `index`.
+    fn body_disc(
+        &self,
+        uniforms: &Uniforms,
+        scene: &Scene,
+        index: usize,
+        time: u32,
+    ) -> Option<(f32, f32, f32)> {
+        let bounds = scene.body_bounds(index, time);
+        let center = project_to_screen(uniforms, bounds.center)?;
+        let pixels_per_unit = self.size() as f32 / (2.0 * self.extent);
+        let radius = (bounds.radius * pixels_per_unit).max(MIN_BODY_RADIUS);
+        Some((center.x, center.y, radius))
+    }
+
+    /// Mezcla el minimapa en su esquina y le pone un borde.
+    fn blit(&self, screen: &mut Framebuffer) {
+        let (left, top) = self.origin(screen);
+        let size = self.size();
+        for y in 0..size {
+            for x in 0..size {
+                let color = if x == 0 || y == 0 || x == size - 1 || y == size - 1 {
+                    BORDER_COLOR
+                } else {
+                    self.framebuffer.buffer[y * size + x]
+                };
+                screen.blend_pixel(left + x, top + y, color, OPACITY);
+            }
+        }
+    }
+}
+
+fn put_pixel(framebuffer: &mut Framebuffer, x: f32, y: f32, color: u32) {
+    if x >= 0.0 && y >= 0.0 {
+        framebuffer.blend_pixel(x as usize, y as usize, color, 1.0);
+    }
+}
+
+fn fill_circle(framebuffer: &mut Framebuffer, cx: f32, cy: f32, radius: f32, color: u32) {
+    let reach = radius.ceil() as i32;
+    for dy in -reach..=reach {
+        for dx in -reach..=reach {
+            if ((dx * dx + dy * dy) as f32) <= radius * radius {
+                put_pixel(framebuffer, cx + dx as f32, cy + dy as f32, color);
+            }
+        }
+    }
+}
+
+fn draw_circle(framebuffer: &mut Framebuffer, cx: f32, cy: f32, radius: f32, color: u32) {
+    let steps = (radius * 8.0).ceil().max(8.0) as usize;
+    for i in 0..steps {
+        let angle = i as f32 / steps as f32 * std::f32::consts::TAU;
+        put_pixel(framebuffer, cx + radius * angle.cos(), cy + radius * angle.sin(), color);
+    }
+}
+
+fn draw_cross(framebuffer: &mut Framebuffer, cx: f32, cy: f32, color: u32) {
+    for d in -3..=3 {
+        put_pixel(framebuffer, cx + d as f32, cy, color);
+        put_pixel(framebuffer, cx, cy + d as f32, color);
+    }
+}
+
+/// Flecha con la punta hacia `(dx, dz)` en el plano de las órbitas; en la imagen `+x`
+/// va a la derecha y `-z` hacia arriba, igual que en [`create_top_down_uniforms`].
+fn draw_arrow(framebuffer: &mut Framebuffer, cx: f32, cy: f32, dx: f32, dz: f32, color: u32) {
+    let length = dx.hypot(dz);
+    let (fx, fy) = if length > 1e-4 { (dx / length, dz / length) } else { (0.0, -1.0) };
+    // Triángulo: punta adelante y dos esquinas atrás
+    let tip = (cx + fx * 5.0, cy + fy * 5.0);
+    let left = (cx - fx * 3.0 - fy * 3.0, cy - fy * 3.0 + fx * 3.0);
+    let right = (cx - fx * 3.0 + fy * 3.0, cy - fy * 3.0 - fx * 3.0);
+    for (a, b) in [(tip, left), (left, right), (right, tip)] {
+        let steps = 12;
+        for i in 0..=steps {
+            let t = i as f32 / steps as f32;
+            put_pixel(framebuffer, a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t, color);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use crate::camera::Camera;
+use crate::chase_camera::ChaseCamera;
+use crate::free_camera::FreeCamera;
+
+/// Estado del mouse en un cuadro, leído de la ventana.
+///
+/// `position` está en píxeles (origen arriba a la izquierda) y `scroll` es el
+/// movimiento vertical de la rueda desde el cuadro anterior.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MouseState {
+    pub position: Option<(f32, f32)>,
+    pub left: bool,
+    pub right: bool,
+    pub scroll: f32,
+}
+
+/// Qué hizo el mouse con la cámara en este cuadro.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MouseAction {
+    pub rotated: bool,
+    pub panned: bool,
+    pub zoomed: bool,
+}
+
+/// Arrastrar con el botón izquierdo orbita (o mira, en vuelo libre), con el derecho
+/// desplaza la cámara y la rueda hace zoom (o acerca la cámara a la nave).
+pub struct MouseController {
+    /// Radianes por píxel al arrastrar.
+    pub rotate_sensitivity: f32,
+    /// Fracción de la distancia al centro que se desplaza por píxel.
+    pub pan_sensitivity: f32,
+    /// Fracción de la distancia al centro que cambia por paso de la rueda.
+    pub zoom_sensitivity: f32,
+    pub invert_y: bool,
+    last_position: Option<(f32, f32)>,
+}
+
+impl Default for MouseController {
+    fn default() -> Self {
+        MouseController::new()
+    }
+}
+
+impl MouseController {
+    pub fn new() -> Self {
+        MouseController {
+            rotate_sensitivity: 0.005,
+            pan_sensitivity: 0.002,
+            zoom_sensitivity: 0.1,
+            invert_y: false,
+            last_position: None,
+        }
+    }
+
+    /// Movimiento desde el cuadro anterior mientras hay un botón presionado.
+    fn drag_delta(&mut self, state: &MouseState) -> (f32, f32) {
+        let dragging = state.left || state.right;
+        let delta = match (dragging, self.last_position, state.position) {
+            (true, Some((x0, y0)), Some((x1, y1))) => (x1 - x0, y1 - y0),
+            _ => (0.0, 0.0),
+        };
+        self.last_position = if dragging { state.position } else { None };
+        delta
+    }
+
+    fn vertical(&self, dy: f32) -> f32 {
+        if self.invert_y {
+            -dy
+        } else {
+            dy
+        }
+    }
+
+    /// Controles de la cámara de órbita.
+    pub fn update_orbit(&mut self, state: &MouseState, camera: &mut Camera) -> MouseAction {
+        let (dx, dy) = self.drag_delta(state);
+        let mut action = MouseAction::default();
+
+        if state.left && (dx != 0.0 || dy != 0.0) {
+            // Se "arrastra" el mundo: mover el mouse a la derecha lo gira a la derecha
+            camera.orbit(
+                dx * self.rotate_sensitivity,
+                -self.vertical(dy) * self.rotate_sensitivity,
+            );
+            action.rotated = true;
+        } else if state.right && (dx != 0.0 || dy != 0.0) {
+            let distance = (camera.eye - camera.center).magnitude();
+            let scale = distance * self.pan_sensitivity;
+            camera.pan(-dx * scale, dy * scale);
+            action.panned = true;
+        }
+
+        if state.scroll != 0.0 {
+            // Zoom exponencial: cada paso cambia la distancia en la misma proporción,
+            // así que se avanza rápido lejos del centro y despacio cerca de él
+            camera.dolly((1.0 - self.zoom_sensitivity).powf(state.scroll));
+            action.zoomed = true;
+        }
+
+        action
+    }
+
+    /// En vuelo libre el arrastre con el botón izquierdo mira alrededor.
+    pub fn update_free_flight(
+        &mut self,
+        state: &MouseState,
+        camera: &mut FreeCamera,
+    ) -> MouseAction {
+        let (dx, dy) = self.drag_delta(state);
+        let mut action = MouseAction::default();
+
+        if state.left && (dx != 0.0 || dy != 0.0) {
+            camera.look(
+                -dx * self.rotate_sensitivity,
+                -self.vertical(dy) * self.rotate_sensitivity,
+            );
+            action.rotated = true;
+        }
+
+        action
+    }
+
+    /// Detrás de la nave la rueda cambia la distancia de persecución.
+    pub fn update_chase(&mut self, state: &MouseState, chase: &mut ChaseCamera) -> MouseAction {
+        self.drag_delta(state);
+        let mut action = MouseAction::default();
+
+        if state.scroll != 0.0 {
+            chase.distance *= (1.0 - self.zoom_sensitivity).powf(state.scroll);
+            chase.distance = chase.distance.clamp(0.2, 10.0);
+            action.zoomed = true;
+        }
+
+        action
+    }
+}
@@ -0,0 +1,282 @@
+use nalgebra_glm::Vec3;
+
+/// Cuánto crece la frecuencia de una octava a la siguiente.
+const LACUNARITY: f32 = 2.0;
+/// Cuánto pesa cada octava respecto de la anterior.
+const GAIN: f32 = 0.5;
+
+/// Gradientes del ruido simplex: los puntos medios de las aristas de un cubo.
+const SIMPLEX_GRADIENTS: [[f32; 3]; 12] = [
+    [1.0, 1.0, 0.0],
+    [-1.0, 1.0, 0.0],
+    [1.0, -1.0, 0.0],
+    [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0],
+    [-1.0, 0.0, 1.0],
+    [1.0, 0.0, -1.0],
+    [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0],
+    [0.0, -1.0, 1.0],
+    [0.0, 1.0, -1.0],
+    [0.0, -1.0, -1.0],
+];
+
+/// Distancias de un punto a los puntos característicos más cercanos del ruido celular.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cells {
+    /// Distancia al punto más cercano.
+    pub nearest: f32,
+    /// Distancia al segundo más cercano; `second - nearest` es 0 en el borde entre dos
+    /// celdas.
+    pub second: f32,
+    /// Número al azar propio de la celda más cercana, de 0 a 255.
+    pub id: u8,
+}
+
+/// Ruido coherente en 3D a partir de una permutación elegida por una semilla.
+///
+/// Trabajar en 3D, sobre la posición del fragmento en la esfera, evita que los patrones
+/// se estiren en los polos. La misma semilla da siempre el mismo ruido; como `new` es
+/// `const`, los shaders pueden guardarlo en un `static`.
+#[derive(Clone)]
+pub struct Noise {
+    permutation: [u8; 512],
+}
+
+impl Noise {
+    /// Mezcla los números de 0 a 255 con Fisher-Yates y un xorshift sembrado con `seed`.
+    pub const fn new(seed: u32) -> Self {
+        let mut table = [0u8; 256];
+        let mut i = 0;
+        while i < 256 {
+            table[i] = i as u8;
+            i += 1;
+        }
+
+        // El xorshift no puede empezar en 0
+        let mut state = seed ^ 0x9E37_79B9;
+        if state == 0 {
+            state = 1;
+        }
+        let mut i = 255;
+        while i > 0 {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            let j = (state % (i as u32 + 1)) as usize;
+            let swap = table[i];
+            table[i] = table[j];
+            table[j] = swap;
+            i -= 1;
+        }
+
+        // Duplicada para no tener que envolver los índices al sumar
+        let mut permutation = [0u8; 512];
+        let mut i = 0;
+        while i < 512 {
+            permutation[i] = table[i % 256];
+            i += 1;
+        }
+        Noise { permutation }
+    }
+
+    fn hash(&self, x: i32, y: i32, z: i32) -> usize {
+        let p = &self.permutation;
+        let x = p[(x & 255) as usize] as usize;
+        let y = p[x + (y & 255) as usize] as usize;
+        p[y + (z & 255) as usize] as usize
+    }
+
+    /// Ruido de Perlin mejorado, aproximadamente entre -1 y 1, y 0 en cada punto de
+    /// coordenadas enteras.
+    pub fn perlin(&self, point: Vec3) -> f32 {
+        let (cell_x, cell_y, cell_z) = (point.x.floor(), point.y.floor(), point.z.floor());
+        let (x, y, z) = (point.x - cell_x, point.y - cell_y, point.z - cell_z);
+        let (cell_x, cell_y, cell_z) = (cell_x as i32, cell_y as i32, cell_z as i32);
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+
+        let corner = |dx: i32, dy: i32, dz: i32| {
+            let hash = self.hash(cell_x + dx, cell_y + dy, cell_z + dz);
+            gradient(hash, x - dx as f32, y - dy as f32, z - dz as f32)
+        };
+        let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+        lerp(
+            lerp(
+                lerp(corner(0, 0, 0), corner(1, 0, 0), u),
+                lerp(corner(0, 1, 0), corner(1, 1, 0), u),
+                v,
+            ),
+            lerp(
+                lerp(corner(0, 0, 1), corner(1, 0, 1), u),
+                lerp(corner(0, 1, 1), corner(1, 1, 1), u),
+                v,
+            ),
+            w,
+        )
+    }
+
+    /// Ruido simplex, entre -1 y 1. Más barato que Perlin y sin direcciones preferidas
+    /// a lo largo de los ejes.
+    pub fn simplex(&self, point: Vec3) -> f32 {
+        const SKEW: f32 = 1.0 / 3.0;
+        const UNSKEW: f32 = 1.0 / 6.0;
+
+        // Celda del retículo de tetraedros que contiene al punto
+        let skew = (point.x + point.y + point.z) * SKEW;
+        let (i, j, k) = (
+            (point.x + skew).floor(),
+            (point.y + skew).floor(),
+            (point.z + skew).floor(),
+        );
+        let unskew = (i + j + k) * UNSKEW;
+        let first = Vec3::new(
+            point.x - (i - unskew),
+            point.y - (j - unskew),
+            point.z - (k - unskew),
+        );
+
+        // Cuál de los seis tetraedros del cubo, según el orden de las coordenadas
+        let (second_step, third_step) = if first.x >= first.y {
+            if first.y >= first.z {
+                ([1, 0, 0], [1, 1, 0])
+            } else if first.x >= first.z {
+                ([1, 0, 0], [1, 0, 1])
+            } else {
+                ([0, 0, 1], [1, 0, 1])
+            }
+        } else if first.y < first.z {
+            ([0, 0, 1], [0, 1, 1])
+        } else if first.x < first.z {
+            ([0, 1, 0], [0, 1, 1])
+        } else {
+            ([0, 1, 0], [1, 1, 0])
+        };
+
+        let (i, j, k) = (i as i32, j as i32, k as i32);
+        let corner = |step: [i32; 3], offset: f32| {
+            let local = Vec3::new(
+                first.x - step[0] as f32 + offset,
+                first.y - step[1] as f32 + offset,
+                first.z - step[2] as f32 + offset,
+            );
+            let falloff = 0.6 - local.magnitude_squared();
+            if falloff <= 0.0 {
+                return 0.0;
+            }
+            let hash = self.hash(i + step[0], j + step[1], k + step[2]);
+            let g = SIMPLEX_GRADIENTS[hash % 12];
+            falloff.powi(4) * (g[0] * local.x + g[1] * local.y + g[2] * local.z)
+        };
+
+        32.0 * (corner([0, 0, 0], 0.0)
+            + corner(second_step, UNSKEW)
+            + corner(third_step, 2.0 * UNSKEW)
+            + corner([1, 1, 1], 3.0 * UNSKEW))
+    }
+
+    /// Movimiento browniano fraccional: `octaves` capas de Perlin, cada una al doble de
+    /// frecuencia y la mitad de amplitud que la anterior. Entre -1 y 1 aproximadamente.
+    pub fn fbm(&self, point: Vec3, octaves: u32) -> f32 {
+        let mut sum = 0.0;
+        let mut amplitude = 1.0;
+        let mut total = 0.0;
+        let mut frequency = 1.0;
+        for octave in 0..octaves {
+            // Cada octava corrida un poco para que no coincidan los ceros de la grilla
+            let shift = octave as f32 * 17.31;
+            let sample = point * frequency + Vec3::new(shift, shift, shift);
+            sum += amplitude * self.perlin(sample);
+            total += amplitude;
+            amplitude *= GAIN;
+            frequency *= LACUNARITY;
+        }
+        if total > 0.0 { sum / total } else { 0.0 }
+    }
+
+    /// Fractal de crestas, entre 0 y 1: las líneas donde el ruido cruza el 0 se vuelven
+    /// crestas afiladas, como cordilleras. Cada octava pesa más sobre las crestas de la
+    /// anterior.
+    pub fn ridged(&self, point: Vec3, octaves: u32) -> f32 {
+        let mut sum = 0.0;
+        let mut amplitude = 1.0;
+        let mut total = 0.0;
+        let mut frequency = 1.0;
+        let mut weight = 1.0;
+        for octave in 0..octaves {
+            let shift = octave as f32 * 17.31;
+            let sample = point * frequency + Vec3::new(shift, shift, shift);
+            let ridge = (1.0 - self.perlin(sample).abs()).powi(2);
+            sum += amplitude * ridge * weight;
+            total += amplitude;
+            weight = ridge;
+            amplitude *= GAIN;
+            frequency *= LACUNARITY;
+        }
+        if total > 0.0 { sum / total } else { 0.0 }
+    }
+
+    /// Ruido celular (Worley): un punto al azar por cada celda de la grilla entera y las
+    /// distancias a los dos más cercanos.
+    pub fn worley(&self, point: Vec3) -> Cells {
+        let (cell_x, cell_y, cell_z) = (
+            point.x.floor() as i32,
+            point.y.floor() as i32,
+            point.z.floor() as i32,
+        );
+        let mut cells = Cells {
+            nearest: f32::INFINITY,
+            second: f32::INFINITY,
+            id: 0,
+        };
+        for dz in -1..=1 {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let (x, y, z) = (cell_x + dx, cell_y + dy, cell_z + dz);
+                    let hash = self.hash(x, y, z);
+                    let p = &self.permutation;
+                    let feature = Vec3::new(
+                        x as f32 + p[hash] as f32 / 255.0,
+                        y as f32 + p[hash + 1] as f32 / 255.0,
+                        z as f32 + p[hash + 2] as f32 / 255.0,
+                    );
+                    let distance = (feature - point).magnitude();
+                    if distance < cells.nearest {
+                        cells.second = cells.nearest;
+                        cells.nearest = distance;
+                        cells.id = hash as u8;
+                    } else if distance < cells.second {
+                        cells.second = distance;
+                    }
+                }
+            }
+        }
+        cells
+    }
+
+    /// Deformación del dominio: corre `point` según tres fBm independientes, hasta
+    /// `amount` en cada eje. Muestrear otro ruido en el punto corrido da formas
+    /// retorcidas, como costas o remolinos de nubes.
+    pub fn warp(&self, point: Vec3, amount: f32) -> Vec3 {
+        let offset = |shift: f32| self.fbm(point + Vec3::new(shift, shift * 0.7, shift * 1.3), 3);
+        point + Vec3::new(offset(0.0), offset(31.4), offset(72.9)) * amount
+    }
+}
+
+/// Curva de Perlin `6t⁵ - 15t⁴ + 10t³`: derivadas primera y segunda nulas en 0 y 1.
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+/// Producto del desplazamiento con uno de 12 gradientes elegido por `hash`.
+fn gradient(hash: usize, x: f32, y: f32, z: f32) -> f32 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
@@ -2,6 +2,7 @@ use tobj;
 use nalgebra_glm::{Vec2, Vec3};
 use crate::vertex::Vertex;
 
+/// Modelo cargado de un archivo `.obj` (triangulado, con un índice por vértice).
 pub struct Obj {
     meshes: Vec<Mesh>,
 }
@@ -14,6 +15,7 @@ struct Mesh {
 }
 
 impl Obj {
+    /// Carga el archivo con `tobj`.
     pub fn load(filename: &str) -> Result<Self, tobj::LoadError> {
         let (models, _) = tobj::load_obj(filename, &tobj::LoadOptions {
             single_index: true,
@@ -40,6 +42,7 @@ impl Obj {
         Ok(Obj { meshes })
     }
 
+    /// Vértices listos para el pipeline, de tres en tres por triángulo.
     pub fn get_vertex_array(&self) -> Vec<Vertex> {
         let mut vertices = Vec::new();
 
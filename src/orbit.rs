@@ -0,0 +1,67 @@
+use nalgebra_glm::Vec3;
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+use crate::body::{body_position, Body};
+use crate::color::Color;
+
+const ORBIT_SEGMENTS: usize = 128;
+
+pub struct Trail {
+    points: VecDeque<Vec3>,
+    capacity: usize,
+}
+
+impl Trail {
+    pub fn new(capacity: usize) -> Self {
+        Trail {
+            points: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, point: Vec3) {
+        if self.points.len() == self.capacity {
+            self.points.pop_front();
+        }
+        self.points.push_back(point);
+    }
+
+    /// Del punto más viejo al más reciente
+    pub fn points(&self) -> Vec<Vec3> {
+        self.points.iter().copied().collect()
+    }
+
+    /// El color se desvanece hacia el fondo conforme el punto es más viejo
+    pub fn colors(&self, color: Color, background: Color) -> Vec<Color> {
+        let count = self.points.len();
+        (0..count)
+            .map(|i| {
+                let t = if count > 1 { i as f32 / (count - 1) as f32 } else { 1.0 };
+                background.lerp(&color, t)
+            })
+            .collect()
+    }
+}
+
+/// Muestrea la trayectoria del cuerpo durante un periodo completo a partir de `time`,
+/// alrededor de la posición actual del cuerpo padre: la órbita de la Luna es una elipse
+/// cerrada alrededor de donde está la Tierra ahora, no la espiral que recorre mientras
+/// la Tierra avanza.
+pub fn predicted_orbit(bodies: &[Body], index: usize, time: f32) -> Vec<Vec3> {
+    let body = &bodies[index];
+    if !body.has_orbit() {
+        return Vec::new();
+    }
+
+    let parent_position = match body.parent {
+        Some(parent) => body_position(bodies, parent, time),
+        None => Vec3::new(0.0, 0.0, 0.0),
+    };
+    let period = 2.0 * PI / body.orbit_speed.abs();
+    (0..=ORBIT_SEGMENTS)
+        .map(|i| {
+            let t = time + period * i as f32 / ORBIT_SEGMENTS as f32;
+            parent_position + body.local_position(t)
+        })
+        .collect()
+}
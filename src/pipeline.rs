@@ -0,0 +1,288 @@
+use nalgebra_glm::{
+    look_at, ortho, perspective, quat_to_mat4, scaling, translation, Mat4, Quat, Vec3, Vec4,
+};
+use std::f32::consts::PI;
+use crate::camera::Camera;
+use crate::color::Color;
+use crate::debug::{normal_shader, DebugView, RenderMode};
+use crate::framebuffer::Framebuffer;
+use crate::line::draw_line;
+use crate::fragment::Fragment;
+use crate::shaders::vertex_shader;
+use crate::triangle::{calculate_bounding_box, triangle_samples};
+use crate::vertex::Vertex;
+
+/// Valores constantes durante el dibujo de un modelo, compartidos por los shaders.
+pub struct Uniforms {
+    /// Modelo a mundo (traslación, escala y rotación del objeto).
+    pub model_matrix: Mat4,
+    /// Mundo a cámara.
+    pub view_matrix: Mat4,
+    /// Cámara a espacio de recorte.
+    pub projection_matrix: Mat4,
+    /// Coordenadas normalizadas a píxeles del framebuffer.
+    pub viewport_matrix: Mat4,
+    /// Tiempo de simulación en cuadros; anima órbitas y shaders.
+    pub time: u32,
+}
+
+/// Matriz de modelo: rotación (x, luego y, luego z), escala uniforme y traslación.
+pub fn create_model_matrix(translation: Vec3, scale: f32, rotation: Vec3) -> Mat4 {
+    // Transformación para rotación y escala
+    let transform_matrix = Mat4::new(
+        scale,
+        0.0,
+        0.0,
+        translation.x,
+        0.0,
+        scale,
+        0.0,
+        translation.y,
+        0.0,
+        0.0,
+        scale,
+        translation.z,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+
+    // Crear matriz de rotación
+    let rotation_x = Mat4::new_rotation(Vec3::new(rotation.x, 0.0, 0.0));
+    let rotation_y = Mat4::new_rotation(Vec3::new(0.0, rotation.y, 0.0));
+    let rotation_z = Mat4::new_rotation(Vec3::new(0.0, 0.0, rotation.z));
+    let rotation_matrix = rotation_z * rotation_y * rotation_x;
+
+    transform_matrix * rotation_matrix
+}
+/// Matriz de modelo con la orientación dada por un cuaternión (para la nave).
+pub fn create_oriented_model_matrix(position: Vec3, scale: f32, orientation: &Quat) -> Mat4 {
+    translation(&position) * quat_to_mat4(orientation) * scaling(&Vec3::new(scale, scale, scale))
+}
+
+pub fn create_view_matrix(eye: Vec3, center: Vec3, up: Vec3) -> Mat4 {
+    look_at(&eye, &center, &up)
+}
+/// Perspectiva de 45° con planos cercano y lejano en 0.1 y 1000.
+pub fn create_perspective_matrix(window_width: f32, window_height: f32) -> Mat4 {
+    let fov = 45.0 * PI / 180.0;
+    let aspect_ratio = window_width / window_height;
+    let near = 0.1;
+    let far = 1000.0;
+
+    perspective(fov, aspect_ratio, near, far)
+}
+/// Lleva coordenadas normalizadas a píxeles, con `y` hacia abajo.
+pub fn create_viewport_matrix(width: f32, height: f32) -> Mat4 {
+    Mat4::new(
+        width / 2.0,
+        0.0,
+        0.0,
+        width / 2.0,
+        0.0,
+        -height / 2.0,
+        0.0,
+        height / 2.0,
+        0.0,
+        0.0,
+        1.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    )
+}
+/// Uniforms para dibujar un modelo desde `camera` en todo el framebuffer.
+pub fn create_uniforms(
+    framebuffer: &Framebuffer,
+    camera: &Camera,
+    model_matrix: Mat4,
+    time: u32,
+) -> Uniforms {
+    let width = framebuffer.width as f32;
+    let height = framebuffer.height as f32;
+    Uniforms {
+        model_matrix,
+        view_matrix: create_view_matrix(camera.eye, camera.center, camera.up),
+        projection_matrix: create_perspective_matrix(width, height),
+        viewport_matrix: create_viewport_matrix(width, height),
+        time,
+    }
+}
+/// Uniforms para mirar el plano de las órbitas desde arriba con proyección ortográfica,
+/// abarcando `extent` unidades a cada lado del origen. `-z` queda hacia arriba en la
+/// imagen y `+x` a la derecha.
+pub fn create_top_down_uniforms(framebuffer: &Framebuffer, extent: f32, time: u32) -> Uniforms {
+    let width = framebuffer.width as f32;
+    let height = framebuffer.height as f32;
+    let aspect_ratio = width / height;
+    let height_extent = 500.0;
+    Uniforms {
+        model_matrix: Mat4::identity(),
+        view_matrix: create_view_matrix(
+            Vec3::new(0.0, height_extent, 0.0),
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, -1.0),
+        ),
+        projection_matrix: ortho(
+            -extent * aspect_ratio,
+            extent * aspect_ratio,
+            -extent,
+            extent,
+            0.1,
+            height_extent * 2.0,
+        ),
+        viewport_matrix: create_viewport_matrix(width, height),
+        time,
+    }
+}
+
+/// Lleva un punto del mundo a píxeles con las mismas matrices que el pipeline: `x` e
+/// `y` en el framebuffer y `z` la profundidad normalizada. `None` si queda detrás de
+/// la cámara; puede caer fuera de la imagen.
+pub fn project_to_screen(uniforms: &Uniforms, point: Vec3) -> Option<Vec3> {
+    let clip = uniforms.projection_matrix
+        * uniforms.view_matrix
+        * Vec4::new(point.x, point.y, point.z, 1.0);
+    if clip.w <= 0.1 {
+        return None;
+    }
+    let ndc = Vec4::new(clip.x / clip.w, clip.y / clip.w, clip.z / clip.w, 1.0);
+    let screen = uniforms.viewport_matrix * ndc;
+    Some(Vec3::new(screen.x, screen.y, screen.z))
+}
+
+/// Dibuja un modelo completo: vertex shader, ensamblado, rasterización y `shader` por fragmento.
+///
+/// Se mezcla con el modo actual del framebuffer; si no es opaco, los triángulos se
+/// dibujan de atrás hacia adelante.
+pub fn render(
+    framebuffer: &mut Framebuffer,
+    uniforms: &Uniforms,
+    vertex_array: &[Vertex],
+    shader: impl Fn(&Fragment, &Uniforms) -> Color,
+    debug: &mut DebugView,
+) {
+    // Vertex Shader
+    let mut transformed_vertices = Vec::with_capacity(vertex_array.len());
+    for vertex in vertex_array {
+        let transformed = vertex_shader(vertex, uniforms);
+        transformed_vertices.push(transformed);
+    }
+
+    // Primitive Assembly
+    // Se descartan los triángulos con algún vértice fuera del rango de profundidad
+    // (detrás de la cámara o más allá del plano lejano)
+    let in_depth_range = |vertex: &Vertex| {
+        let z = vertex.transformed_position.z;
+        (-1.0..=1.0).contains(&z)
+    };
+    let mut triangles = Vec::new();
+    for i in (0..transformed_vertices.len()).step_by(3) {
+        if i + 2 < transformed_vertices.len()
+            && transformed_vertices[i..i + 3].iter().all(in_depth_range)
+        {
+            triangles.push([
+                transformed_vertices[i].clone(),
+                transformed_vertices[i + 1].clone(),
+                transformed_vertices[i + 2].clone(),
+            ]);
+        }
+    }
+
+    // Lo transparente se mezcla con lo que ya está dibujado, así que va de atrás hacia
+    // adelante
+    if !framebuffer.blend_mode().is_opaque() {
+        let depth = |tri: &[Vertex; 3]| {
+            tri.iter().map(|vertex| vertex.transformed_position.z).sum::<f32>()
+        };
+        triangles.sort_by(|a, b| depth(b).total_cmp(&depth(a)));
+    }
+
+    for tri in &triangles {
+        debug.add_bounding_box(calculate_bounding_box(
+            &tri[0].transformed_position,
+            &tri[1].transformed_position,
+            &tri[2].transformed_position,
+        ));
+    }
+
+    // Wireframe: solo las aristas de cada triángulo
+    if debug.mode == RenderMode::Wireframe {
+        for tri in &mut triangles {
+            for vertex in tri.iter_mut() {
+                vertex.color = debug.wireframe_color;
+            }
+            for (a, b) in [(0, 1), (1, 2), (2, 0)] {
+                draw_line(framebuffer, &tri[a], &tri[b]);
+            }
+        }
+        return;
+    }
+
+    // Rasterization; con MSAA la cobertura se prueba en cada muestra del píxel
+    let mut fragments = Vec::new();
+    for tri in &triangles {
+        fragments.extend(triangle_samples(
+            &tri[0],
+            &tri[1],
+            &tri[2],
+            framebuffer.width,
+            framebuffer.height,
+            framebuffer.sample_positions(),
+        ));
+    }
+
+    let normals = debug.mode == RenderMode::Normals;
+
+    // Fragment Processing: el shader corre una vez por píxel aunque haya varias muestras.
+    // Los fragmentos sin opacidad se descartan: no tocan ni el color ni la profundidad
+    for fragment in fragments {
+        let x = fragment.position.x as usize;
+        let y = fragment.position.y as usize;
+
+        if x < framebuffer.width && y < framebuffer.height {
+            let color = if normals {
+                normal_shader(&fragment, uniforms)
+            } else {
+                shader(&fragment, uniforms)
+            };
+            if color.get_a() <= 0.0 {
+                continue;
+            }
+            framebuffer.set_current_color(color);
+            framebuffer.point_samples(x, y, fragment.coverage, &fragment.sample_depths);
+        }
+    }
+}
+/// Dibuja los vértices del skybox con la textura dada.
+pub fn render_skybox(
+    framebuffer: &mut Framebuffer,
+    uniforms: &Uniforms,
+    skybox_texture: &image::RgbaImage,
+    skybox_vertices: &[Vertex],
+) {
+    for vertex in skybox_vertices {
+        let transformed = vertex_shader(vertex, uniforms);
+
+        let x = transformed.position.x as usize;
+        let y = transformed.position.y as usize;
+
+        if x < framebuffer.width && y < framebuffer.height {
+            // Mapeo de coordenadas de textura
+            let u = (vertex.tex_coords.x.clamp(0.0, 1.0) * skybox_texture.width() as f32) as u32;
+            let v = (vertex.tex_coords.y.clamp(0.0, 1.0) * skybox_texture.height() as f32) as u32;
+
+            if u >= skybox_texture.width() || v >= skybox_texture.height() {
+                continue;
+            }
+
+            let pixel = skybox_texture.get_pixel(u, v).0;
+            let color = Color::new(pixel[0], pixel[1], pixel[2]);
+            framebuffer.set_current_color(color);
+            framebuffer.point(x, y, transformed.position.z);
+        }
+    }
+}
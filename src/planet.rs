@@ -0,0 +1,311 @@
+use nalgebra_glm::Vec3;
+use std::f32::consts::PI;
+use crate::color::Color;
+use crate::fragment::Fragment;
+use crate::noise::Noise;
+use crate::shaders::{
+    cloud_density, cloud_drift, continents, crater_shade, smoothstep, sphere_point,
+};
+use crate::Uniforms;
+
+/// Puntos de la esfera donde se mide el ruido de cada planeta para saber qué altura
+/// deja bajo el agua la fracción pedida de la superficie.
+const SURVEY_POINTS: usize = 1024;
+
+/// Colores de la superficie de un planeta generado, de lo más hondo a lo más alto.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub deep_ocean: Color,
+    pub ocean: Color,
+    pub lowland: Color,
+    pub highland: Color,
+    pub mountain: Color,
+    pub ice: Color,
+    pub cloud: Color,
+}
+
+/// Paletas predefinidas para `PlanetGenerator`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Biome {
+    /// Océanos azules, llanuras verdes y montañas pardas, como la Tierra.
+    Temperate,
+    /// Arena y roca rojiza; lo que queda bajo el "océano" son salares.
+    Desert,
+    /// Roca casi negra con mares de lava, que brillan más que el blanco.
+    Volcanic,
+    /// Océanos congelados y roca azulada.
+    Frozen,
+    /// Roca gris sin agua ni aire, como Mercurio o la Luna.
+    Barren,
+    /// Mares verdes y tierras moradas.
+    Alien,
+}
+
+impl Biome {
+    pub fn all() -> [Biome; 6] {
+        [
+            Biome::Temperate,
+            Biome::Desert,
+            Biome::Volcanic,
+            Biome::Frozen,
+            Biome::Barren,
+            Biome::Alien,
+        ]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Biome::Temperate => "Templado",
+            Biome::Desert => "Desértico",
+            Biome::Volcanic => "Volcánico",
+            Biome::Frozen => "Helado",
+            Biome::Barren => "Árido",
+            Biome::Alien => "Alienígena",
+        }
+    }
+
+    pub fn palette(self) -> Palette {
+        let white = Color::new(255, 255, 255);
+        match self {
+            Biome::Temperate => Palette {
+                deep_ocean: Color::new(0, 45, 100),
+                ocean: Color::new(0, 105, 148),
+                lowland: Color::new(34, 139, 34),
+                highland: Color::new(160, 140, 80),
+                mountain: Color::new(139, 69, 19),
+                ice: Color::new(235, 240, 250),
+                cloud: white,
+            },
+            Biome::Desert => Palette {
+                deep_ocean: Color::new(200, 190, 170),
+                ocean: Color::new(230, 220, 200),
+                lowland: Color::new(220, 170, 100),
+                highland: Color::new(190, 120, 70),
+                mountain: Color::new(120, 70, 45),
+                ice: Color::new(245, 240, 230),
+                cloud: Color::new(240, 225, 200),
+            },
+            Biome::Volcanic => Palette {
+                deep_ocean: Color::rgb(4.0, 0.9, 0.1),
+                ocean: Color::rgb(2.0, 0.3, 0.02),
+                lowland: Color::new(50, 40, 38),
+                highland: Color::new(30, 25, 25),
+                mountain: Color::new(80, 60, 50),
+                ice: Color::new(120, 115, 110),
+                cloud: Color::new(90, 80, 75),
+            },
+            Biome::Frozen => Palette {
+                deep_ocean: Color::new(150, 190, 215),
+                ocean: Color::new(200, 225, 240),
+                lowland: Color::new(170, 180, 195),
+                highland: Color::new(120, 130, 150),
+                mountain: Color::new(90, 95, 110),
+                ice: Color::new(245, 250, 255),
+                cloud: white,
+            },
+            Biome::Barren => Palette {
+                deep_ocean: Color::new(80, 78, 75),
+                ocean: Color::new(100, 98, 95),
+                lowland: Color::new(150, 145, 140),
+                highland: Color::new(120, 115, 110),
+                mountain: Color::new(175, 170, 165),
+                ice: Color::new(230, 230, 230),
+                cloud: white,
+            },
+            Biome::Alien => Palette {
+                deep_ocean: Color::new(10, 70, 40),
+                ocean: Color::new(40, 150, 90),
+                lowland: Color::new(120, 60, 140),
+                highland: Color::new(170, 100, 160),
+                mountain: Color::new(70, 40, 90),
+                ice: Color::new(220, 240, 230),
+                cloud: Color::new(230, 255, 220),
+            },
+        }
+    }
+}
+
+/// Superficie de un planeta inventada a partir de una semilla y unos pocos parámetros,
+/// para no escribir un shader por planeta.
+///
+/// La misma semilla da siempre los mismos continentes, cráteres y nubes; los
+/// parámetros solo cambian cuánto hay de cada cosa. Se asigna a un cuerpo con
+/// `Body::with_surface`.
+#[derive(Clone)]
+pub struct PlanetGenerator {
+    /// Fracción de la superficie bajo el océano, de 0 a 1.
+    pub ocean_level: f32,
+    pub palette: Palette,
+    /// Fracción del planeta tapada por nubes, de 0 a 1.
+    pub cloud_cover: f32,
+    /// De 0 (sin cráteres) a 1 (cráteres en toda la superficie).
+    pub crater_density: f32,
+    /// Latitud en grados desde la que hay hielo; 90 o más, sin casquetes.
+    pub ice_latitude: f32,
+    seed: u32,
+    noise: Noise,
+    /// Alturas y nubosidad medidas en `SURVEY_POINTS` puntos, ordenadas.
+    elevations: Vec<f32>,
+    cloudiness: Vec<f32>,
+}
+
+impl PlanetGenerator {
+    /// Planeta templado con la mitad de océano, algunas nubes y casquetes desde los 70°.
+    pub fn new(seed: u32) -> Self {
+        let noise = Noise::new(seed);
+        let mut generator = PlanetGenerator {
+            ocean_level: 0.5,
+            palette: Biome::Temperate.palette(),
+            cloud_cover: 0.3,
+            crater_density: 0.0,
+            ice_latitude: 70.0,
+            seed,
+            noise,
+            elevations: Vec::new(),
+            cloudiness: Vec::new(),
+        };
+
+        // Puntos de Fibonacci: repartidos casi por igual sobre la esfera
+        let golden_angle = PI * (3.0 - 5f32.sqrt());
+        let survey = (0..SURVEY_POINTS).map(|i| {
+            let y = 1.0 - 2.0 * (i as f32 + 0.5) / SURVEY_POINTS as f32;
+            let ring = (1.0 - y * y).sqrt();
+            let angle = golden_angle * i as f32;
+            Vec3::new(ring * angle.cos(), y, ring * angle.sin())
+        });
+        let (mut elevations, mut cloudiness): (Vec<f32>, Vec<f32>) = survey
+            .map(|point| (generator.elevation(point), cloud_density(&generator.noise, point)))
+            .unzip();
+        elevations.sort_by(f32::total_cmp);
+        cloudiness.sort_by(f32::total_cmp);
+        generator.elevations = elevations;
+        generator.cloudiness = cloudiness;
+        generator
+    }
+
+    pub fn with_ocean_level(mut self, ocean_level: f32) -> Self {
+        self.ocean_level = ocean_level.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_biome(mut self, biome: Biome) -> Self {
+        self.palette = biome.palette();
+        self
+    }
+
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    pub fn with_cloud_cover(mut self, cloud_cover: f32) -> Self {
+        self.cloud_cover = cloud_cover.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_craters(mut self, crater_density: f32) -> Self {
+        self.crater_density = crater_density.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_ice_caps(mut self, latitude: f32) -> Self {
+        self.ice_latitude = latitude;
+        self
+    }
+
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    /// Altura del terreno en `point`, sobre la esfera unitaria; solo importa comparada
+    /// con la de otros puntos.
+    pub fn elevation(&self, point: Vec3) -> f32 {
+        continents(&self.noise, point)
+    }
+
+    /// Altura por debajo de la cual queda `ocean_level` de la superficie.
+    pub fn sea_level(&self) -> f32 {
+        quantile(&self.elevations, self.ocean_level)
+    }
+
+    /// Si `point`, sobre la esfera unitaria, está bajo el océano.
+    pub fn is_ocean(&self, point: Vec3) -> bool {
+        self.ocean_level > 0.0 && self.elevation(point) < self.sea_level()
+    }
+
+    /// Color de la superficie en `point`, sobre la esfera unitaria con `y` de polo a
+    /// polo, en el instante `time`: terreno, cráteres, hielo y, encima, nubes.
+    pub fn color(&self, point: Vec3, time: u32) -> Color {
+        let palette = &self.palette;
+        let elevation = self.elevation(point);
+        let sea_level = self.sea_level();
+        let lowest = quantile(&self.elevations, 0.0);
+        let highest = quantile(&self.elevations, 1.0);
+
+        let surface = if self.ocean_level > 0.0 && elevation < sea_level {
+            let depth = (sea_level - elevation) / (sea_level - lowest).max(1e-4);
+            palette.ocean.lerp(&palette.deep_ocean, smoothstep(0.0, 0.6, depth))
+        } else {
+            let height = (elevation - sea_level) / (highest - sea_level).max(1e-4);
+            let ridges = self.noise.ridged(point * 3.0, 4);
+            let variation = smoothstep(-0.3, 0.3, self.noise.fbm(point * 8.0, 3));
+            let upland = smoothstep(0.1, 0.7, height) * 0.7 + variation * 0.3;
+            let land = palette.lowland.lerp(&palette.highland, upland);
+            let mountains = smoothstep(0.45, 0.8, ridges) * smoothstep(0.15, 0.6, height);
+            land.lerp(&palette.mountain, mountains) * self.craters(point)
+        };
+
+        // Casquetes polares, con el borde irregular. El borde puede pasar de 90°, así que
+        // sin casquetes no se mezcla nada
+        let surface = if self.ice_latitude < 90.0 {
+            let latitude = point.y.clamp(-1.0, 1.0).abs().asin().to_degrees()
+                + 5.0 * self.noise.fbm(point * 6.0, 3);
+            surface.lerp(
+                &palette.ice,
+                smoothstep(self.ice_latitude, self.ice_latitude + 3.0, latitude),
+            )
+        } else {
+            surface
+        };
+
+        if self.cloud_cover <= 0.0 {
+            return surface;
+        }
+        let clouds = cloud_density(&self.noise, cloud_drift(point, time));
+        let threshold = quantile(&self.cloudiness, 1.0 - self.cloud_cover);
+        let cover = smoothstep(threshold, threshold + 0.1, clouds);
+        surface.lerp(&palette.cloud, cover * 0.9)
+    }
+
+    /// Brillo por los cráteres en `point`: cada celda del ruido celular tiene un cráter
+    /// con probabilidad `crater_density`, en dos tamaños.
+    fn craters(&self, point: Vec3) -> f32 {
+        if self.crater_density <= 0.0 {
+            return 1.0;
+        }
+        let threshold = self.crater_density * 255.0;
+        [(4.0, 0.4, 0.0), (10.0, 0.35, 9.0)]
+            .into_iter()
+            .map(|(frequency, radius, shift)| {
+                let cells = self.noise.worley(point * frequency + Vec3::new(shift, 0.0, 0.0));
+                // Elegidas con otro orden que el tamaño, para no quedarse solo con las chicas
+                let pick = cells.id.wrapping_mul(97) as f32;
+                if pick < threshold { crater_shade(cells, radius) } else { 1.0 }
+            })
+            .product()
+    }
+
+    /// Fragment shader del planeta, para `pipeline::render`.
+    pub fn shade(&self, fragment: &Fragment, uniforms: &Uniforms) -> Color {
+        self.color(sphere_point(fragment), uniforms.time)
+    }
+}
+
+/// Valor de `sorted` por debajo del cual queda la fracción `fraction` de los valores.
+fn quantile(sorted: &[f32], fraction: f32) -> f32 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let index = (fraction.clamp(0.0, 1.0) * (sorted.len() - 1) as f32).round() as usize;
+    sorted[index]
+}
@@ -0,0 +1,207 @@
+use crate::color::Color;
+use crate::framebuffer::Framebuffer;
+
+/// Resplandor alrededor de lo que brilla más que el blanco, como el Sol.
+///
+/// Se extrae lo que pasa de `threshold` a una imagen reducida `downsample` veces, se
+/// difumina con un filtro gaussiano separable y se suma a la imagen HDR.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bloom {
+    /// Brillo (el canal más alto, en luz lineal) a partir del cual un píxel resplandece.
+    pub threshold: f32,
+    /// Cuánto del resplandor se suma a la imagen.
+    pub intensity: f32,
+    /// Desviación del filtro gaussiano, en píxeles de la imagen reducida.
+    pub sigma: f32,
+    /// Factor de reducción de la imagen donde se difumina.
+    pub downsample: usize,
+}
+
+impl Default for Bloom {
+    fn default() -> Self {
+        Bloom::new()
+    }
+}
+
+impl Bloom {
+    pub fn new() -> Self {
+        Bloom {
+            threshold: 1.0,
+            intensity: 1.5,
+            sigma: 3.0,
+            downsample: 4,
+        }
+    }
+
+    pub fn apply(&self, framebuffer: &mut Framebuffer) {
+        let factor = self.downsample.max(1);
+        let width = framebuffer.width.div_ceil(factor);
+        let height = framebuffer.height.div_ceil(factor);
+        let mut bright = self.bright_pass(framebuffer, factor, width, height);
+        let kernel = gaussian_kernel(self.sigma);
+        blur(&mut bright, width, height, &kernel, true);
+        blur(&mut bright, width, height, &kernel, false);
+
+        for y in 0..framebuffer.height {
+            for x in 0..framebuffer.width {
+                // Centro del píxel en coordenadas de la imagen reducida
+                let u = (x as f32 + 0.5) / factor as f32 - 0.5;
+                let v = (y as f32 + 0.5) / factor as f32 - 0.5;
+                let glow = sample_bilinear(&bright, width, height, u, v);
+                let pixel = &mut framebuffer.color[y * framebuffer.width + x];
+                *pixel = *pixel + glow * self.intensity;
+            }
+        }
+    }
+
+    /// Promedia bloques de `factor` x `factor` píxeles y se queda con lo que pasa del
+    /// umbral.
+    fn bright_pass(
+        &self,
+        framebuffer: &Framebuffer,
+        factor: usize,
+        width: usize,
+        height: usize,
+    ) -> Vec<Color> {
+        let mut bright = vec![Color::black(); width * height];
+        for (index, target) in bright.iter_mut().enumerate() {
+            let (bx, by) = (index % width, index / width);
+            let mut sum = Color::black();
+            let mut count = 0;
+            for y in by * factor..((by + 1) * factor).min(framebuffer.height) {
+                for x in bx * factor..((bx + 1) * factor).min(framebuffer.width) {
+                    sum = sum + framebuffer.color[y * framebuffer.width + x];
+                    count += 1;
+                }
+            }
+            let average = sum * (1.0 / count.max(1) as f32);
+            let brightness = average.get_r().max(average.get_g()).max(average.get_b());
+            if brightness > self.threshold {
+                *target = average * ((brightness - self.threshold) / brightness);
+            }
+        }
+        bright
+    }
+}
+
+/// Un efecto de posprocesado sobre la imagen HDR, antes del tonemapping.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PostEffect {
+    Bloom(Bloom),
+}
+
+impl PostEffect {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PostEffect::Bloom(_) => "Bloom",
+        }
+    }
+
+    pub fn apply(&self, framebuffer: &mut Framebuffer) {
+        match self {
+            PostEffect::Bloom(bloom) => bloom.apply(framebuffer),
+        }
+    }
+}
+
+/// Un efecto de la pila y si está activo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PostPass {
+    pub effect: PostEffect,
+    pub enabled: bool,
+}
+
+/// Efectos de posprocesado que se aplican en orden a la imagen HDR completa.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostStack {
+    pub passes: Vec<PostPass>,
+}
+
+impl Default for PostStack {
+    fn default() -> Self {
+        PostStack::new()
+    }
+}
+
+impl PostStack {
+    /// Pila con el bloom activo.
+    pub fn new() -> Self {
+        PostStack {
+            passes: vec![PostPass {
+                effect: PostEffect::Bloom(Bloom::new()),
+                enabled: true,
+            }],
+        }
+    }
+
+    /// Pila sin efectos.
+    pub fn empty() -> Self {
+        PostStack { passes: Vec::new() }
+    }
+
+    /// Agrega un efecto activo al final.
+    pub fn with(mut self, effect: PostEffect) -> Self {
+        self.passes.push(PostPass { effect, enabled: true });
+        self
+    }
+
+    /// Activa o desactiva el efecto llamado `name`; devuelve su estado nuevo, o `None`
+    /// si no está en la pila.
+    pub fn toggle(&mut self, name: &str) -> Option<bool> {
+        let pass = self.passes.iter_mut().find(|pass| pass.effect.name() == name)?;
+        pass.enabled = !pass.enabled;
+        Some(pass.enabled)
+    }
+
+    /// Aplica los efectos activos en orden.
+    pub fn apply(&self, framebuffer: &mut Framebuffer) {
+        for pass in self.passes.iter().filter(|pass| pass.enabled) {
+            pass.effect.apply(framebuffer);
+        }
+    }
+}
+
+/// Pesos normalizados de un filtro gaussiano de radio `3 * sigma`, desde el centro.
+pub fn gaussian_kernel(sigma: f32) -> Vec<f32> {
+    let sigma = sigma.max(0.1);
+    let radius = (sigma * 3.0).ceil() as usize;
+    let mut kernel: Vec<f32> = (0..=radius)
+        .map(|i| (-((i * i) as f32) / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let total = kernel[0] + 2.0 * kernel[1..].iter().sum::<f32>();
+    for weight in kernel.iter_mut() {
+        *weight /= total;
+    }
+    kernel
+}
+
+/// Difumina por filas o por columnas; en los bordes se repite el último píxel.
+fn blur(image: &mut [Color], width: usize, height: usize, kernel: &[f32], horizontal: bool) {
+    let source = image.to_vec();
+    let (length, lines) = if horizontal { (width, height) } else { (height, width) };
+    let index = |line: usize, i: usize| {
+        if horizontal { line * width + i } else { i * width + line }
+    };
+    for line in 0..lines {
+        for i in 0..length {
+            let mut sum = source[index(line, i)] * kernel[0];
+            for (offset, &weight) in kernel.iter().enumerate().skip(1) {
+                let before = i.saturating_sub(offset);
+                let after = (i + offset).min(length - 1);
+                sum = sum + (source[index(line, before)] + source[index(line, after)]) * weight;
+            }
+            image[index(line, i)] = sum;
+        }
+    }
+}
+
+fn sample_bilinear(image: &[Color], width: usize, height: usize, u: f32, v: f32) -> Color {
+    let u = u.clamp(0.0, (width - 1) as f32);
+    let v = v.clamp(0.0, (height - 1) as f32);
+    let (x0, y0) = (u.floor() as usize, v.floor() as usize);
+    let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+    let (tx, ty) = (u - x0 as f32, v - y0 as f32);
+    let top = image[y0 * width + x0].lerp(&image[y0 * width + x1], tx);
+    let bottom = image[y1 * width + x0].lerp(&image[y1 * width + x1], tx);
+    top.lerp(&bottom, ty)
+}
@@ -0,0 +1,101 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::framebuffer::Framebuffer;
+
+/// Graba una secuencia numerada de cuadros (frame_00000.png, frame_00001.png, ...)
+/// tomando uno cada `step` unidades de tiempo de simulación.
+pub struct Recorder {
+    directory: PathBuf,
+    step: u32,
+    next_time: u32,
+    frame: u32,
+    recording: bool,
+}
+
+impl Recorder {
+    pub fn new(directory: &str, step: u32) -> Self {
+        Recorder {
+            directory: PathBuf::from(directory),
+            step: step.max(1),
+            next_time: 0,
+            frame: 0,
+            recording: false,
+        }
+    }
+
+    pub fn start(&mut self, time: u32) -> Result<(), String> {
+        fs::create_dir_all(&self.directory)
+            .map_err(|err| format!("Failed to create {}: {}", self.directory.display(), err))?;
+        self.next_time = time;
+        self.frame = 0;
+        self.recording = true;
+        Ok(())
+    }
+
+    pub fn stop(&mut self) {
+        self.recording = false;
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    pub fn frames(&self) -> u32 {
+        self.frame
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    pub fn is_due(&self, time: u32) -> bool {
+        self.recording && time >= self.next_time
+    }
+
+    /// Guarda el cuadro si le toca; devuelve la ruta escrita
+    pub fn capture(&mut self, framebuffer: &Framebuffer, time: u32) -> Result<Option<String>, String> {
+        if !self.is_due(time) {
+            return Ok(None);
+        }
+
+        let path = self.directory.join(format!("frame_{:05}.png", self.frame));
+        let path = path.to_string_lossy().into_owned();
+        framebuffer
+            .save_png(&path)
+            .map_err(|err| format!("Failed to write {}: {}", path, err))?;
+
+        self.frame += 1;
+        self.next_time = time + self.step;
+        Ok(Some(path))
+    }
+}
+
+pub fn save_screenshot(framebuffer: &Framebuffer, directory: &str) -> Result<String, String> {
+    fs::create_dir_all(directory)
+        .map_err(|err| format!("Failed to create {}: {}", directory, err))?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or(0);
+    let path = Path::new(directory).join(format!("screenshot_{}.png", timestamp));
+    let path = path.to_string_lossy().into_owned();
+
+    framebuffer
+        .save_png(&path)
+        .map_err(|err| format!("Failed to write {}: {}", path, err))?;
+    Ok(path)
+}
+
+/// Carpeta nueva para cada grabación, para no sobrescribir la anterior
+pub fn recording_directory(base: &str) -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    Path::new(base)
+        .join(format!("take_{}", timestamp))
+        .to_string_lossy()
+        .into_owned()
+}
@@ -0,0 +1,627 @@
+use image::io::Reader as ImageReader;
+use image::RgbaImage;
+use nalgebra_glm::{quat_identity, rotation, translation, Mat4, Vec2, Vec3, Vec4};
+use std::f32::consts::FRAC_PI_2;
+use std::collections::HashMap;
+use crate::antialias::{downsample, fxaa, AntiAliasing, SSAA_FACTOR};
+use crate::body::{body_position, fictional_system, solar_system, Body, TRAIL_LENGTH};
+use crate::bounds::BoundingSphere;
+use crate::camera::Camera;
+use crate::collision::{
+    mesh_contact, respond, sphere_contact, CollisionEvent, CollisionResponse, CollisionSettings,
+    Contact,
+};
+use crate::color::{BlendMode, Color};
+use crate::debug::{DebugView, RenderMode};
+use crate::fragment::Fragment;
+use crate::framebuffer::Framebuffer;
+use crate::gravity::{acceleration, circular_velocity, predict_trajectory};
+use crate::line::polyline;
+use crate::obj::Obj;
+use crate::orbit::predicted_orbit;
+use crate::pipeline::{
+    create_model_matrix, create_oriented_model_matrix, create_uniforms, render, render_skybox,
+    Uniforms,
+};
+use crate::post::PostStack;
+use crate::shaders::spaceship_shader;
+use crate::ship::{Ship, ShipInput};
+use crate::tonemap::ToneMapping;
+use crate::vertex::Vertex;
+
+/// Escala de las distancias orbitales usada por el programa.
+pub const DISTANCE_SCALE: f32 = 0.6;
+
+/// Paso en segundos y cantidad de pasos de la trayectoria predicha de la nave.
+pub const TRAJECTORY_STEP: f32 = 0.1;
+pub const TRAJECTORY_STEPS: usize = 600;
+
+/// El Sistema Solar completo: cuerpos, modelos cargados, skybox y nave.
+pub struct Scene {
+    pub bodies: Vec<Body>,
+    pub show_orbits: bool,
+    pub show_trails: bool,
+    pub show_trajectory: bool,
+    pub debug: DebugView,
+    pub ship: Ship,
+    pub collisions: CollisionSettings,
+    /// Cómo se suavizan los bordes en `render`.
+    pub anti_aliasing: AntiAliasing,
+    /// Efectos sobre la imagen HDR al terminar `render`, antes del tonemapping.
+    pub post: PostStack,
+    /// Cómo se lleva la imagen HDR a la pantalla al terminar `render`.
+    pub tone_mapping: ToneMapping,
+    meshes: HashMap<String, Vec<Vertex>>,
+    mesh_bounds: HashMap<String, BoundingSphere>,
+    ship_vertices: Vec<Vertex>,
+    ship_scale: f32,
+    /// Centra el modelo de la nave y gira su proa (`+x` en el archivo) hacia `-z`.
+    ship_alignment: Mat4,
+    ship_radius: f32,
+    /// Cuerpos que la nave tocaba en el cuadro anterior.
+    ship_contacts: Vec<usize>,
+    /// Cuerpo en cuyo marco de referencia se mide la velocidad de la nave.
+    ship_frame: Option<usize>,
+    skybox_texture: RgbaImage,
+    skybox_vertices: Vec<Vertex>,
+    /// Framebuffer grande donde se dibuja con SSAA; se conserva entre cuadros.
+    supersampled: Option<Framebuffer>,
+}
+
+impl Scene {
+    /// Carga modelos y texturas desde `assets/`; entra en pánico si falta alguno.
+    pub fn load(distance_scale: f32) -> Self {
+        Scene::with_bodies(solar_system(distance_scale))
+    }
+
+    /// El Sistema Solar, o el sistema inventado de `seed` si hay una.
+    pub fn load_system(seed: Option<u32>, distance_scale: f32) -> Self {
+        match seed {
+            Some(seed) => Scene::with_bodies(fictional_system(seed, distance_scale)),
+            None => Scene::load(distance_scale),
+        }
+    }
+
+    /// Escena con otros cuerpos, como un sistema inventado con `fictional_system`. El
+    /// primero es la estrella. Carga los modelos que usen y el resto de `assets/`.
+    pub fn with_bodies(bodies: Vec<Body>) -> Self {
+        let mut meshes: HashMap<String, Vec<Vertex>> = HashMap::new();
+        for body in &bodies {
+            if !meshes.contains_key(&body.mesh) {
+                let obj = Obj::load(&body.mesh).expect("Failed to load model");
+                meshes.insert(body.mesh.clone(), obj.get_vertex_array());
+            }
+        }
+
+        let mesh_bounds = meshes
+            .iter()
+            .map(|(name, vertices)| (name.clone(), BoundingSphere::from_vertices(vertices)))
+            .collect();
+
+        // Configuración de la Nave Espacial
+        let obj_ship =
+            Obj::load("assets/models/naveEspacial1.obj").expect("Failed to load spaceship");
+        let ship_vertices = obj_ship.get_vertex_array();
+        let ship_bounds = BoundingSphere::from_vertices(&ship_vertices);
+        let ship_center = ship_bounds.center;
+        let ship_scale = 0.009;
+        let ship_alignment =
+            rotation(FRAC_PI_2, &Vec3::new(0.0, 1.0, 0.0)) * translation(&-ship_center);
+
+        // Cargar textura del Skybox
+        let skybox_texture = ImageReader::open("assets/textures/skyblock.png")
+            .expect("Failed to load skybox texture")
+            .decode()
+            .expect("Failed to decode skybox texture")
+            .to_rgba8();
+
+        Scene {
+            bodies,
+            show_orbits: true,
+            show_trails: true,
+            show_trajectory: true,
+            debug: DebugView::new(),
+            ship: spawn_ship(),
+            collisions: CollisionSettings::default(),
+            anti_aliasing: AntiAliasing::Off,
+            post: PostStack::new(),
+            tone_mapping: ToneMapping::new(),
+            meshes,
+            mesh_bounds,
+            ship_vertices,
+            ship_scale,
+            ship_alignment,
+            ship_radius: ship_bounds.radius * ship_scale,
+            ship_contacts: Vec::new(),
+            ship_frame: None,
+            skybox_texture,
+            skybox_vertices: skybox_vertices(),
+            supersampled: None,
+        }
+    }
+
+    /// Avanza las estelas de los cuerpos al instante `time`.
+    pub fn update(&mut self, time: u32) {
+        let positions = self.positions(time);
+        for (body, position) in self.bodies.iter_mut().zip(&positions) {
+            if body.has_orbit() {
+                body.trail.push(*position);
+            }
+        }
+    }
+
+    /// Llena las estelas como si la simulación hubiera corrido hasta `time`,
+    /// útil cuando se arranca directamente en un instante dado.
+    pub fn seed_trails(&mut self, time: u32) {
+        for t in time.saturating_sub(TRAIL_LENGTH as u32)..=time {
+            self.update(t);
+        }
+    }
+
+    pub fn positions(&self, time: u32) -> Vec<Vec3> {
+        (0..self.bodies.len())
+            .map(|i| body_position(&self.bodies, i, time as f32))
+            .collect()
+    }
+
+    /// Esfera envolvente del cuerpo `index` en mundo, en el instante `time`.
+    pub fn body_bounds(&self, index: usize, time: u32) -> BoundingSphere {
+        let body = &self.bodies[index];
+        self.mesh_bounds[&body.mesh].transformed(
+            body_position(&self.bodies, index, time as f32),
+            body.scale,
+            body.rotation(time as f32),
+        )
+    }
+
+    /// Esfera envolvente de la nave en mundo.
+    pub fn ship_bounds(&self) -> BoundingSphere {
+        BoundingSphere {
+            center: self.ship.position,
+            radius: self.ship_radius,
+        }
+    }
+
+    /// Vuelve a poner la nave quieta en su posición inicial.
+    pub fn reset_ship(&mut self) {
+        self.ship = spawn_ship();
+        self.ship_contacts.clear();
+        self.ship_frame = None;
+    }
+
+    /// Cuerpo cuya gravedad manda en `point`: el de esfera de influencia más pequeña
+    /// que lo contiene. El Sol, con influencia infinita, es el último recurso.
+    pub fn dominant_body(&self, point: Vec3, time: u32) -> usize {
+        let mut dominant = 0;
+        let mut smallest = f32::INFINITY;
+        for (index, body) in self.bodies.iter().enumerate() {
+            if body.gravity <= 0.0 {
+                continue;
+            }
+            let distance = (point - self.body_bounds(index, time).center).magnitude();
+            if distance <= body.influence_radius && body.influence_radius <= smallest {
+                dominant = index;
+                smallest = body.influence_radius;
+            }
+        }
+        dominant
+    }
+
+    /// Cuerpo en cuyo marco se movió la nave en el último paso.
+    pub fn ship_frame(&self) -> Option<usize> {
+        self.ship_frame
+    }
+
+    /// Velocidad del centro del cuerpo `index` en el paso que termina en `time`.
+    pub fn body_velocity(&self, index: usize, time: u32, dt: f32) -> Vec3 {
+        let previous = self.body_bounds(index, time.saturating_sub(1)).center;
+        let current = self.body_bounds(index, time).center;
+        (current - previous) / dt.max(1e-3)
+    }
+
+    /// Mueve la nave un paso con sus motores y la gravedad.
+    ///
+    /// Los planetas siguen órbitas fijas mucho más rápidas de lo que la gravedad del Sol
+    /// permitiría, así que se usan cónicas empalmadas: la nave viaja en el marco del
+    /// cuerpo dominante (se desplaza con él) y solo siente su gravedad. Al cambiar de
+    /// esfera de influencia la velocidad se conserva relativa al marco nuevo: igualar
+    /// la velocidad real de un planeta sería imposible con los motores de la nave.
+    pub fn fly_ship(&mut self, input: &ShipInput, time: u32, dt: f32) {
+        let frame = self.dominant_body(self.ship.position, time);
+        self.ship_frame = Some(frame);
+
+        let previous = self.body_bounds(frame, time.saturating_sub(1)).center;
+        let center = self.body_bounds(frame, time).center;
+        self.ship.position += center - previous;
+
+        let mu = self.bodies[frame].gravity;
+        let offset = self.ship.position - center;
+        self.ship.velocity += acceleration(mu, offset) * dt;
+
+        if input.circularize {
+            let mut thrust = self.ship.thrust_acceleration;
+            if input.boost {
+                thrust *= self.ship.boost_factor;
+            }
+            let target = circular_velocity(mu, offset, self.ship.velocity);
+            let burn = target - self.ship.velocity;
+            let delta = thrust * dt;
+            if burn.magnitude() <= delta {
+                self.ship.velocity = target;
+            } else {
+                self.ship.velocity += burn.normalize() * delta;
+            }
+        }
+
+        self.ship.update(input, dt);
+    }
+
+    /// Puntos en mundo por donde pasará la nave si no enciende los motores, con el
+    /// cuerpo dominante quieto en su posición actual.
+    pub fn predicted_trajectory(&self, time: u32) -> Vec<Vec3> {
+        let frame = self
+            .ship_frame
+            .unwrap_or_else(|| self.dominant_body(self.ship.position, time));
+        let bounds = self.body_bounds(frame, time);
+        let mu = self.bodies[frame].gravity;
+        predict_trajectory(
+            self.ship.position - bounds.center,
+            self.ship.velocity,
+            mu,
+            bounds.radius,
+            TRAJECTORY_STEP,
+            TRAJECTORY_STEPS,
+        )
+        .into_iter()
+        .map(|offset| bounds.center + offset)
+        .collect()
+    }
+
+    /// Contactos de `sphere` con cada cuerpo sólido en el instante `time`: primero con
+    /// la esfera envolvente y, si está activado, con los triángulos del modelo.
+    pub fn contacts(&self, sphere: &BoundingSphere, time: u32) -> Vec<(usize, Contact)> {
+        let mut contacts = Vec::new();
+        for (index, body) in self.bodies.iter().enumerate() {
+            if !body.solid {
+                continue;
+            }
+            let bounds = self.body_bounds(index, time);
+            let Some(contact) = sphere_contact(sphere, &bounds) else {
+                continue;
+            };
+            if !self.collisions.triangle_level {
+                contacts.push((index, contact));
+                continue;
+            }
+            let triangles = self.body_triangles(index, time);
+            if let Some(contact) = mesh_contact(sphere, &triangles, bounds.center) {
+                contacts.push((index, contact));
+            }
+        }
+        contacts
+    }
+
+    /// Resuelve los choques de la nave tras moverla y devuelve los impactos nuevos.
+    ///
+    /// La velocidad de cada cuerpo se estima con su desplazamiento en el último paso
+    /// de simulación, que en la ventana dura `dt` segundos.
+    pub fn collide_ship(&mut self, time: u32, dt: f32) -> Vec<CollisionEvent> {
+        if !self.collisions.enabled {
+            self.ship_contacts.clear();
+            return Vec::new();
+        }
+
+        let contacts = self.contacts(&self.ship_bounds(), time);
+        let mut events = Vec::new();
+        // La velocidad de la nave es relativa al cuerpo dominante
+        let frame_velocity = match self.ship_frame {
+            Some(frame) => self.body_velocity(frame, time, dt),
+            None => Vec3::new(0.0, 0.0, 0.0),
+        };
+        for (index, contact) in &contacts {
+            let body_velocity = self.body_velocity(*index, time, dt) - frame_velocity;
+            let impact_speed = respond(&mut self.ship, contact, body_velocity, &self.collisions);
+
+            if !self.ship_contacts.contains(index) {
+                events.push(CollisionEvent {
+                    body: *index,
+                    point: contact.point,
+                    normal: contact.normal,
+                    impact_speed,
+                    crashed: self.collisions.response == CollisionResponse::Crash,
+                });
+            }
+        }
+        self.ship_contacts = contacts.iter().map(|(index, _)| *index).collect();
+        events
+    }
+
+    /// Triángulos del modelo del cuerpo en mundo.
+    fn body_triangles(&self, index: usize, time: u32) -> Vec<[Vec3; 3]> {
+        let body = &self.bodies[index];
+        let model_matrix = create_model_matrix(
+            body_position(&self.bodies, index, time as f32),
+            body.scale,
+            body.rotation(time as f32),
+        );
+        let world = |vertex: &Vertex| {
+            let position = vertex.position;
+            let world = model_matrix * Vec4::new(position.x, position.y, position.z, 1.0);
+            Vec3::new(world.x, world.y, world.z)
+        };
+        self.meshes[&body.mesh]
+            .chunks_exact(3)
+            .map(|triangle| [world(&triangle[0]), world(&triangle[1]), world(&triangle[2])])
+            .collect()
+    }
+
+    /// Dibuja un cuadro completo visto desde `camera` con `anti_aliasing`, le aplica
+    /// `post` y lo presenta en `framebuffer.buffer` con `tone_mapping`. Las normales y el
+    /// wireframe se presentan tal cual, sin `post` y con tonemapping lineal; las demás
+    /// vistas de depuración se dibujan sobre la imagen final.
+    pub fn render(&mut self, framebuffer: &mut Framebuffer, camera: &Camera, time: u32) {
+        framebuffer.set_samples(self.anti_aliasing.samples());
+        if self.anti_aliasing == AntiAliasing::Ssaa {
+            let width = framebuffer.width * SSAA_FACTOR;
+            let height = framebuffer.height * SSAA_FACTOR;
+            let mut supersampled = self
+                .supersampled
+                .take()
+                .filter(|supersampled| supersampled.width == width && supersampled.height == height)
+                .unwrap_or_else(|| Framebuffer::new(width, height));
+            supersampled.set_background_color(framebuffer.get_background_color());
+            self.draw(&mut supersampled, camera, time);
+            downsample(&supersampled, framebuffer, SSAA_FACTOR);
+            self.debug.scale_bounding_boxes(SSAA_FACTOR);
+            self.supersampled = Some(supersampled);
+        } else {
+            self.supersampled = None;
+            self.draw(framebuffer, camera, time);
+            framebuffer.resolve();
+        }
+        // Las normales y el wireframe muestran valores exactos: ni bloom ni tonemapping
+        if matches!(self.debug.mode, RenderMode::Normals | RenderMode::Wireframe) {
+            framebuffer.present(&ToneMapping::linear());
+        } else {
+            self.post.apply(framebuffer);
+            framebuffer.present(&self.tone_mapping);
+        }
+        if self.anti_aliasing == AntiAliasing::Fxaa {
+            fxaa(framebuffer);
+        }
+        self.debug.finish_frame(framebuffer);
+    }
+
+    /// Todo lo que tiene profundidad, en la imagen HDR de `framebuffer`.
+    fn draw(&mut self, framebuffer: &mut Framebuffer, camera: &Camera, time: u32) {
+        framebuffer.clear();
+        self.render_skybox(framebuffer, camera, time);
+        self.render_bodies(framebuffer, camera, time);
+        self.render_orbits(framebuffer, camera, time);
+        if self.show_trajectory {
+            self.render_trajectory(framebuffer, camera, time);
+        }
+        self.render_ship(framebuffer, camera, time);
+    }
+
+    pub fn render_skybox(&self, framebuffer: &mut Framebuffer, camera: &Camera, time: u32) {
+        // Posición del skybox centrada en la cámara
+        let model_matrix = create_model_matrix(camera.eye, 100.0, Vec3::new(0.0, 0.0, 0.0));
+        let uniforms = create_uniforms(framebuffer, camera, model_matrix, time);
+        render_skybox(
+            framebuffer,
+            &uniforms,
+            &self.skybox_texture,
+            &self.skybox_vertices,
+        );
+    }
+
+    /// El Sol, los planetas, la Luna, las atmósferas y los anillos de Saturno: primero los
+    /// cuerpos opacos y después lo transparente, del más lejano al más cercano a la
+    /// cámara, para que cada capa se mezcle con todo lo que tiene detrás.
+    pub fn render_bodies(&mut self, framebuffer: &mut Framebuffer, camera: &Camera, time: u32) {
+        let positions = self.positions(time);
+        let (opaque, transparent): (Vec<usize>, Vec<usize>) =
+            (0..self.bodies.len()).partition(|&index| self.bodies[index].blend.is_opaque());
+
+        // Las atmósferas van antes que los cuerpos transparentes a la misma distancia,
+        // como los anillos de Saturno, para quedar debajo de ellos. Las vistas de
+        // depuración las omiten
+        let shaded = self.debug.mode == RenderMode::Shaded;
+        let mut layers: Vec<Layer> = (0..self.bodies.len())
+            .filter(|&index| shaded && self.bodies[index].atmosphere.is_some())
+            .map(Layer::Atmosphere)
+            .chain(transparent.into_iter().map(Layer::Body))
+            .collect();
+        let distance =
+            |index: usize| (self.body_bounds(index, time).center - camera.eye).magnitude();
+        layers.sort_by(|a, b| distance(b.body()).total_cmp(&distance(a.body())));
+
+        for index in opaque {
+            self.render_body(framebuffer, camera, time, index, positions[index]);
+        }
+        // El Sol es el primer cuerpo
+        let sun = self.body_bounds(0, time).center;
+        for layer in layers {
+            match layer {
+                Layer::Body(index) => {
+                    self.render_body(framebuffer, camera, time, index, positions[index])
+                }
+                Layer::Atmosphere(index) => {
+                    self.render_atmosphere(framebuffer, camera, time, index, sun)
+                }
+            }
+        }
+        framebuffer.set_blend_mode(BlendMode::Opaque);
+    }
+
+    fn render_body(
+        &mut self,
+        framebuffer: &mut Framebuffer,
+        camera: &Camera,
+        time: u32,
+        index: usize,
+        position: Vec3,
+    ) {
+        let body = &self.bodies[index];
+        let model_matrix = create_model_matrix(position, body.scale, body.rotation(time as f32));
+        let uniforms = create_uniforms(framebuffer, camera, model_matrix, time);
+        framebuffer.set_blend_mode(body.blend);
+        let mesh = &self.meshes[&body.mesh];
+        match &body.surface {
+            Some(surface) => {
+                let shader = |fragment: &Fragment, uniforms: &Uniforms| {
+                    surface.shade(fragment, uniforms)
+                };
+                render(framebuffer, &uniforms, mesh, shader, &mut self.debug);
+            }
+            None => render(framebuffer, &uniforms, mesh, body.shader, &mut self.debug),
+        }
+    }
+
+    /// La atmósfera del cuerpo `index`: su misma malla agrandada, mezclada encima de lo
+    /// que ya está dibujado.
+    fn render_atmosphere(
+        &mut self,
+        framebuffer: &mut Framebuffer,
+        camera: &Camera,
+        time: u32,
+        index: usize,
+        sun: Vec3,
+    ) {
+        let body = &self.bodies[index];
+        let Some(atmosphere) = body.atmosphere else {
+            return;
+        };
+        let bounds = self.body_bounds(index, time);
+        let body_matrix = create_model_matrix(
+            body_position(&self.bodies, index, time as f32),
+            body.scale,
+            body.rotation(time as f32),
+        );
+        let model_matrix =
+            atmosphere.shell_matrix(&body_matrix, self.mesh_bounds[&body.mesh].center);
+        let uniforms = create_uniforms(framebuffer, camera, model_matrix, time);
+        let eye = camera.eye;
+        let shader = |fragment: &Fragment, uniforms: &Uniforms| {
+            let position = fragment.vertex_position;
+            let world = uniforms.model_matrix * Vec4::new(position.x, position.y, position.z, 1.0);
+            atmosphere.scatter(eye, world.xyz(), bounds.center, bounds.radius, sun)
+        };
+        framebuffer.set_blend_mode(BlendMode::Normal);
+        render(framebuffer, &uniforms, &self.meshes[&body.mesh], shader, &mut self.debug);
+    }
+
+    /// Órbitas predichas y estelas recientes
+    pub fn render_orbits(&self, framebuffer: &mut Framebuffer, camera: &Camera, time: u32) {
+        let uniforms = create_uniforms(framebuffer, camera, Mat4::identity(), time);
+        let background_color = Color::from_hex(framebuffer.get_background_color());
+
+        for (i, body) in self.bodies.iter().enumerate() {
+            if !body.show_orbit || !body.has_orbit() {
+                continue;
+            }
+            if self.show_orbits {
+                let points = predicted_orbit(&self.bodies, i, time as f32);
+                let colors = vec![body.orbit_color * 0.4; points.len()];
+                polyline(framebuffer, &uniforms, &points, &colors);
+            }
+            if self.show_trails {
+                let colors = body.trail.colors(body.orbit_color, background_color);
+                polyline(framebuffer, &uniforms, &body.trail.points(), &colors);
+            }
+        }
+    }
+
+    /// Trayectoria predicha de la nave, que se desvanece hacia el futuro
+    pub fn render_trajectory(&self, framebuffer: &mut Framebuffer, camera: &Camera, time: u32) {
+        let uniforms = create_uniforms(framebuffer, camera, Mat4::identity(), time);
+        let points = self.predicted_trajectory(time);
+        let last = points.len().saturating_sub(1).max(1) as f32;
+        let colors: Vec<Color> = (0..points.len())
+            .map(|i| Color::new(120, 255, 160) * (1.0 - 0.8 * i as f32 / last))
+            .collect();
+        polyline(framebuffer, &uniforms, &points, &colors);
+    }
+
+    pub fn render_ship(&mut self, framebuffer: &mut Framebuffer, camera: &Camera, time: u32) {
+        let ship = &self.ship;
+        let model_matrix =
+            create_oriented_model_matrix(ship.position, self.ship_scale, &ship.orientation)
+                * self.ship_alignment;
+        let uniforms = create_uniforms(framebuffer, camera, model_matrix, time);
+        render(
+            framebuffer,
+            &uniforms,
+            &self.ship_vertices,
+            spaceship_shader,
+            &mut self.debug,
+        );
+    }
+}
+
+/// Algo transparente que se dibuja después de los cuerpos opacos.
+enum Layer {
+    Body(usize),
+    Atmosphere(usize),
+}
+
+impl Layer {
+    fn body(&self) -> usize {
+        match *self {
+            Layer::Body(index) | Layer::Atmosphere(index) => index,
+        }
+    }
+}
+
+/// La nave empieza fuera de la órbita de la Tierra, mirando hacia el Sol.
+fn spawn_ship() -> Ship {
+    Ship::new(Vec3::new(0.0, 2.0, 20.0), quat_identity())
+}
+
+fn skybox_vertices() -> Vec<Vertex> {
+    vec![
+        // Cara frontal
+        Vertex::new(
+            Vec3::new(-1.0, -1.0, 1.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec2::new(0.25, 0.33),
+        ),
+        Vertex::new(
+            Vec3::new(1.0, -1.0, 1.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec2::new(0.5, 0.33),
+        ),
+        Vertex::new(
+            Vec3::new(1.0, 1.0, 1.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec2::new(0.5, 0.66),
+        ),
+        Vertex::new(
+            Vec3::new(-1.0, 1.0, 1.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec2::new(0.25, 0.66),
+        ),
+        // Cara trasera
+        Vertex::new(
+            Vec3::new(1.0, -1.0, -1.0),
+            Vec3::new(0.0, 0.0, -1.0),
+            Vec2::new(0.75, 0.33),
+        ),
+        Vertex::new(
+            Vec3::new(-1.0, -1.0, -1.0),
+            Vec3::new(0.0, 0.0, -1.0),
+            Vec2::new(1.0, 0.33),
+        ),
+        Vertex::new(
+            Vec3::new(-1.0, 1.0, -1.0),
+            Vec3::new(0.0, 0.0, -1.0),
+            Vec2::new(1.0, 0.66),
+        ),
+        Vertex::new(
+            Vec3::new(1.0, 1.0, -1.0),
+            Vec3::new(0.0, 0.0, -1.0),
+            Vec2::new(0.75, 0.66),
+        ),
+    ]
+}
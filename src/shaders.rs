@@ -1,10 +1,65 @@
-use nalgebra_glm::{dot, mat4_to_mat3, Mat3, Vec2, Vec3, Vec4};
+use nalgebra_glm::{mat4_to_mat3, Mat3, Vec2, Vec3, Vec4};
 use crate::vertex::Vertex;
 use crate::Uniforms;
 use crate::fragment::Fragment;
 use crate::color::Color;
-use std::f32::consts::PI;
+use crate::noise::{Cells, Noise};
 
+/// Firma común de los fragment shaders de los cuerpos.
+pub type FragmentShader = fn(&Fragment, &Uniforms) -> Color;
+
+/// Centro de `planet.obj` (y de `rings.obj`) en espacio de modelo; no está en el origen.
+pub const PLANET_CENTER: Vec3 = Vec3::new(0.035634, 5.310887, -0.630639);
+
+// Un ruido por cuerpo, para que no compartan continentes ni cráteres
+static EARTH_NOISE: Noise = Noise::new(3);
+static MARS_NOISE: Noise = Noise::new(4);
+static MOON_NOISE: Noise = Noise::new(31);
+
+/// Dirección del centro de `planet.obj` al fragmento: un punto de la esfera unitaria,
+/// con el eje `y` de polo a polo. El ruido muestreado ahí no se estira en los polos.
+pub fn sphere_point(fragment: &Fragment) -> Vec3 {
+  (fragment.vertex_position - PLANET_CENTER).normalize()
+}
+
+/// 0 antes de `edge0`, 1 después de `edge1` y una curva suave entre ambos.
+pub fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+  let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+  t * t * (3.0 - 2.0 * t)
+}
+
+/// Brillo de un cráter según el ruido celular: fondo oscuro, borde claro y 1 fuera de
+/// él. El radio de cada cráter depende de su celda, hasta `max_radius`.
+pub fn crater_shade(cells: Cells, max_radius: f32) -> f32 {
+  let radius = max_radius * (0.35 + 0.65 * cells.id as f32 / 255.0);
+  let distance = cells.nearest;
+  let floor = 1.0 - 0.35 * (1.0 - smoothstep(radius * 0.6, radius * 0.9, distance));
+  let rim = smoothstep(radius * 0.75, radius, distance)
+      * (1.0 - smoothstep(radius, radius * 1.3, distance));
+  floor + 0.35 * rim
+}
+
+/// Altura de los continentes en `point`, sobre la esfera unitaria: fBm sobre un dominio
+/// deformado, para costas irregulares. La usan la Tierra y los planetas generados.
+pub fn continents(noise: &Noise, point: Vec3) -> f32 {
+  noise.fbm(noise.warp(point * 1.6, 0.5) * 1.8, 5)
+}
+
+/// Densidad de nubes en `point`, enroscadas por la deformación del dominio. Mayor es
+/// más nublado.
+pub fn cloud_density(noise: &Noise, point: Vec3) -> f32 {
+  noise.fbm(noise.warp(point * 2.5, 0.6) * 2.0 + Vec3::new(40.0, 0.0, 0.0), 4)
+}
+
+/// `point` girado alrededor del eje de los polos en el instante `time`: las nubes
+/// giran un poco más rápido que la superficie.
+pub fn cloud_drift(point: Vec3, time: u32) -> Vec3 {
+  let angle = time as f32 * 0.004;
+  let (sin, cos) = angle.sin_cos();
+  Vec3::new(point.x * cos - point.z * sin, point.y, point.x * sin + point.z * cos)
+}
+
+/// Transforma la posición a píxeles y la normal a espacio de mundo.
 pub fn vertex_shader(vertex: &Vertex, uniforms: &Uniforms) -> Vertex {
     let position = Vec4::new(
         vertex.position.x,
@@ -36,7 +91,7 @@ pub fn vertex_shader(vertex: &Vertex, uniforms: &Uniforms) -> Vertex {
         tex_coords: vertex.tex_coords,
         color: vertex.color,
         transformed_position: Vec3::new(screen_position.x, screen_position.y, screen_position.z),
-        transformed_normal: transformed_normal
+        transformed_normal
     }
 }
 
@@ -74,7 +129,7 @@ fn circle_shader(fragment: &Fragment) -> Color {
     if distance < 0.25 { // Circle radius
       Color::new(255, 255, 0) // Yellow circle
     } else {
-      Color::new(0, 0, 0) // Black (transparent) background
+      Color::transparent() // Transparent background
     }
 }
 
@@ -154,18 +209,14 @@ pub fn sun_shader(fragment: &Fragment, uniforms: &Uniforms) -> Color {
   let gradient_color = color_yellow.lerp(&color_orange, distance.clamp(0.0, 1.0));
 
   let noise_intensity = ((x * 10.0 + time).sin() * (y * 10.0 + time).cos()).abs();
-  let noise_color = Color::new(
-      (gradient_color.get_r() as f32 * noise_intensity) as u8,
-      (gradient_color.get_g() as f32 * noise_intensity) as u8,
-      (gradient_color.get_b() as f32 * noise_intensity) as u8,
-  );
-
+  let noise_color = gradient_color * noise_intensity;
 
-  let emission_intensity = 1.5; 
+  // Emisivo: pasa del blanco, así que resplandece con el bloom y el tonemapping lo comprime
+  let emission_intensity = 3.0;
   noise_color * emission_intensity
 }
 
-pub fn rocky_planet_shader(fragment: &Fragment, uniforms: &Uniforms) -> Color {
+pub fn rocky_planet_shader(fragment: &Fragment, _uniforms: &Uniforms) -> Color {
   let x = fragment.vertex_position.x;
   let y = fragment.vertex_position.y;
 
@@ -174,19 +225,12 @@ pub fn rocky_planet_shader(fragment: &Fragment, uniforms: &Uniforms) -> Color {
 
 
   let pattern = ((x * 10.0).sin() * (y * 10.0).cos()).abs();
-  let base_color = Color::new(
-      (color_dark_brown.get_r() as f32 * pattern) as u8,
-      (color_dark_brown.get_g() as f32 * pattern) as u8,
-      (color_dark_brown.get_b() as f32 * pattern) as u8,
-  );
-
-
   let noise_intensity = (5.0 * (x * 5.0).sin() * (y * 5.0).cos()).abs();
-  let noise_color = Color::new(
-      (base_color.get_r() as f32 * noise_intensity) as u8,
-      (base_color.get_g() as f32 * noise_intensity) as u8,
-      (base_color.get_b() as f32 * noise_intensity) as u8,
-  );
+
+  // Las intensidades se pensaron sobre valores sRGB: en luz lineal van elevadas a 2.2.
+  // El ruido satura el color, que se recorta a 1 antes de multiplicar
+  let intensity = (pattern * noise_intensity).powf(2.2);
+  let noise_color = (color_dark_brown * intensity).map(|channel| channel.min(1.0));
 
   color_dark_gray.blend_multiply(&noise_color)
 }
@@ -207,79 +251,77 @@ pub fn venus_shader(fragment: &Fragment, uniforms: &Uniforms) -> Color {
 
   // Mezcla de colores para simular las capas de nubes con ondas
   let base_color = color_soft_yellow.lerp(&color_light_gray, wave_pattern_x);
-  let final_color = base_color.lerp(&color_white, wave_pattern_y);
+  
 
-  final_color
+  base_color.lerp(&color_white, wave_pattern_y)
 }
 
 
 pub fn earth_shader(fragment: &Fragment, uniforms: &Uniforms) -> Color {
-  let x = fragment.vertex_position.x;
-  let y = fragment.vertex_position.y;
-
-  let color_water = Color::new(0, 105, 148);       // Color de agua
-  let color_land = Color::new(34, 139, 34);        // Color de tierra
-  let color_mountain = Color::new(139, 69, 19);    // Color de montañas
-  let color_cloud = Color::new(255, 255, 255);     // Color de nubes
-
-  // Desplazamiento temporal horizontal para la superficie (tierra y montañas)
-  let time_surface = uniforms.time as f32 * 0.005; // Movimiento lento en el eje x
-  let moving_x = x + time_surface;                 // Movimiento horizontal de la superficie
-  let moving_y = y;
-
-  // Calcular el factor de continente (tierra y montañas) con el desplazamiento horizontal
-  let continent_factor = ((moving_x * 3.5 + moving_y * 2.1).sin() * (moving_x * 2.8 - moving_y * 3.3).cos()).abs();
-
-  let base_color = if continent_factor > 0.4 {
-      if continent_factor > 0.6 {
-          color_mountain
-      } else {
-          color_land
-      }
+  let point = sphere_point(fragment);
+  let noise = &EARTH_NOISE;
+
+  let color_deep_water = Color::new(0, 45, 100);   // Océano profundo
+  let color_water = Color::new(0, 105, 148);       // Plataforma continental
+  let color_land = Color::new(34, 139, 34);        // Llanuras
+  let color_dry = Color::new(160, 140, 80);        // Zonas secas
+  let color_mountain = Color::new(139, 69, 19);    // Montañas
+  let color_ice = Color::new(235, 240, 250);       // Hielo y nieve
+  let color_cloud = Color::new(255, 255, 255);     // Nubes
+
+  let elevation = continents(noise, point);
+  let sea_level = 0.05;
+  let ridges = noise.ridged(point * 3.0, 4);
+
+  let surface = if elevation < sea_level {
+      let depth = smoothstep(sea_level - 0.25, sea_level, elevation);
+      color_deep_water.lerp(&color_water, depth)
   } else {
-      color_water
+      let height = smoothstep(sea_level, sea_level + 0.3, elevation);
+      let moisture = noise.fbm(point * 4.0 + Vec3::new(5.2, 1.3, 7.7), 3);
+      let dryness = smoothstep(-0.1, 0.3, moisture);
+      let lowland = color_land.lerp(&color_dry, dryness * 0.8);
+      let mountains = smoothstep(0.5, 0.8, ridges) * height;
+      let land = lowland.lerp(&color_mountain, mountains.max(height * 0.5));
+      // Nieve en las cumbres más altas
+      land.lerp(&color_ice, smoothstep(0.75, 0.9, ridges * height))
   };
 
-  // Movimiento rápido de las nubes en ambas direcciones
-  let cloud_time = uniforms.time as f32 * 0.02; // Velocidad de movimiento de las nubes
-  let cloud_x = x + cloud_time.sin() * 0.3;     // Desplazamiento en x para las nubes
-  let cloud_y = y + cloud_time.cos() * 0.3;     // Desplazamiento en y para las nubes
-  let cloud_noise = ((cloud_x * 20.0).sin() * (cloud_y * 20.0).cos()).abs();
+  // Casquetes polares con borde irregular
+  let latitude = point.y.abs() + 0.08 * noise.fbm(point * 6.0, 3);
+  let surface = surface.lerp(&color_ice, smoothstep(0.82, 0.88, latitude));
 
-  let cloud_color = if cloud_noise > 0.6 {
-      color_cloud
-  } else {
-      base_color
-  };
+  let clouds = cloud_density(noise, cloud_drift(point, uniforms.time));
+  let cover = smoothstep(0.05, 0.3, clouds);
 
-  cloud_color
+  surface.lerp(&color_cloud, cover * 0.9)
 }
 
 
 
 
-pub fn mars_shader(fragment: &Fragment, uniforms: &Uniforms) -> Color {
-  let x = fragment.vertex_position.x;
-  let y = fragment.vertex_position.y;
+pub fn mars_shader(fragment: &Fragment, _uniforms: &Uniforms) -> Color {
+  let point = sphere_point(fragment);
+  let noise = &MARS_NOISE;
 
+  let color_red = Color::new(205, 92, 92);
+  let color_dark_red = Color::new(139, 69, 19);
+  let color_brown = Color::new(165, 42, 42);
+  let color_ice = Color::new(240, 235, 230);
 
-  let color_red = Color::new(205, 92, 92);       
-  let color_dark_red = Color::new(139, 69, 19);  
-  let color_brown = Color::new(165, 42, 42);     
+  // Llanuras oscuras de basalto y tierras altas rojizas
+  let terrain = noise.fbm(noise.warp(point * 1.5, 0.3) * 2.0, 5);
+  let highlands = smoothstep(-0.15, 0.15, terrain);
+  let dust = smoothstep(-0.2, 0.3, noise.fbm(point * 8.0, 3));
+  let base_color = color_dark_red.lerp(&color_red, highlands).lerp(&color_brown, dust * 0.35);
 
+  // Cráteres de dos tamaños
+  let shade = crater_shade(noise.worley(point * 5.0), 0.35)
+      * crater_shade(noise.worley(point * 11.0 + Vec3::new(9.0, 0.0, 0.0)), 0.3);
 
-  let surface_noise = ((x * 8.0).sin() * (y * 8.0).cos()).abs();
-  let crater_noise = ((x * 15.0).sin() * (y * 15.0).cos()).abs();
-
-  let base_color = if crater_noise > 0.6 {
-      color_dark_red 
-  } else if surface_noise > 0.4 {
-      color_brown 
-  } else {
-      color_red 
-  };
-
-  base_color
+  // Casquetes polares pequeños
+  let latitude = point.y.abs() + 0.05 * noise.fbm(point * 10.0, 2);
+  (base_color * shade).lerp(&color_ice, smoothstep(0.9, 0.94, latitude))
 }
 
 pub fn jupiter_shader(fragment: &Fragment, uniforms: &Uniforms) -> Color {
@@ -309,35 +351,36 @@ pub fn jupiter_shader(fragment: &Fragment, uniforms: &Uniforms) -> Color {
   let red_spot_y = (y + 0.2).powi(2) / 0.2;
   let red_spot_intensity = 1.0 - (red_spot_x + red_spot_y).clamp(0.0, 1.0);
 
-  let final_color = if red_spot_intensity > 0.7 {
+  
+
+  if red_spot_intensity > 0.7 {
       color_red_spot.lerp(&base_color, red_spot_intensity)
   } else {
       base_color
-  };
-
-  final_color
+  }
 }
 
 pub fn moon_shader(fragment: &Fragment, _uniforms: &Uniforms) -> Color {
-  let x = fragment.vertex_position.x;
-  let y = fragment.vertex_position.y;
+  let point = sphere_point(fragment);
+  let noise = &MOON_NOISE;
 
+  let color_light_gray = Color::new(200, 200, 200);
+  let color_dark_gray = Color::new(105, 105, 105);
 
-  let color_light_gray = Color::new(200, 200, 200); 
-  let color_dark_gray = Color::new(105, 105, 105); 
+  // Mares: manchas grandes y oscuras de bordes suaves
+  let maria = smoothstep(0.0, 0.12, noise.fbm(noise.warp(point * 1.2, 0.4) * 1.5, 4));
+  let grain = 1.0 + 0.1 * noise.simplex(point * 20.0);
+  let base_color = color_light_gray.lerp(&color_dark_gray, maria) * grain;
 
-  let surface_noise = ((x * 5.0).sin() * (y * 5.0).cos()).abs();
-  let base_color = if surface_noise > 0.5 {
-      color_dark_gray
-  } else {
-      color_light_gray
-  };
+  // Cráteres de tres tamaños, más pequeños cuanto más abundan
+  let shade = crater_shade(noise.worley(point * 4.0), 0.4)
+      * crater_shade(noise.worley(point * 9.0 + Vec3::new(3.0, 0.0, 0.0)), 0.35)
+      * crater_shade(noise.worley(point * 20.0 + Vec3::new(0.0, 7.0, 0.0)), 0.3);
 
-  base_color
+  base_color * shade
 }
 
 pub fn saturn_shader(fragment: &Fragment, uniforms: &Uniforms) -> Color {
-  let x = fragment.vertex_position.x;
   let y = fragment.vertex_position.y;
 
   let color_pale_yellow = Color::new(253, 253, 150); 
@@ -347,39 +390,43 @@ pub fn saturn_shader(fragment: &Fragment, uniforms: &Uniforms) -> Color {
   // Bandas horizontales en la atmósfera
   let band_pattern = ((y * 5.0 + uniforms.time as f32 * 0.01).sin() * 0.5 + 0.5).clamp(0.0, 1.0);
 
-  let base_color = if band_pattern < 0.3 {
+  
+
+  if band_pattern < 0.3 {
       color_pale_yellow
   } else if band_pattern < 0.6 {
       color_beige
   } else {
       color_light_brown
-  };
-
-  base_color
+  }
 }
 
-pub fn saturn_rings_shader(fragment: &Fragment, uniforms: &Uniforms) -> Color {
-  let x = fragment.vertex_position.x;
-  let y = fragment.vertex_position.y;
+/// Anillos de Saturno sobre `rings.obj`: bandas semitransparentes según la distancia al
+/// centro del anillo, con la división de Cassini y la de Encke vacías.
+pub fn saturn_rings_shader(fragment: &Fragment, _uniforms: &Uniforms) -> Color {
+  // Posición en el plano del anillo, desde su centro
+  let x = fragment.vertex_position.x - PLANET_CENTER.x;
+  let z = fragment.vertex_position.z - PLANET_CENTER.z;
 
   // Distancia radial desde el centro para crear anillos concéntricos
-  let distance = (x * x + y * y).sqrt();
+  let distance = (x * x + z * z).sqrt();
 
   // Colores para los anillos
-  let color_ring1 = Color::new(200, 200, 200); // Gris claro
-  let color_ring2 = Color::new(169, 169, 169); // Gris oscuro
-
-  // Crear anillos concéntricos basados en la distancia
-  if distance > 1.1 && distance < 1.2 {
-      color_ring1
-  } else if distance > 1.3 && distance < 1.4 {
-      color_ring2
-  } else if distance > 1.5 && distance < 1.6 {
-      color_ring1
-  } else if distance > 1.7 && distance < 1.8 {
-      color_ring2
+  let color_ring_c = Color::new(140, 125, 105); // Gris pardo, tenue
+  let color_ring_b = Color::new(225, 210, 180); // Beige claro, el más denso
+  let color_ring_a = Color::new(190, 180, 160); // Gris beige
+
+  // Ondulación fina dentro de cada anillo
+  let ringlets = 0.85 + 0.15 * (distance * 90.0).sin();
+
+  if (1.3..1.6).contains(&distance) {
+      color_ring_c.with_alpha(0.3 * ringlets)
+  } else if (1.6..2.05).contains(&distance) {
+      color_ring_b.with_alpha(0.9 * ringlets)
+  } else if (2.14..2.5).contains(&distance) && !(2.41..2.43).contains(&distance) {
+      color_ring_a.with_alpha(0.65 * ringlets)
   } else {
-      Color::new(0, 0, 0) // Negro (transparente) para el área sin anillos
+      Color::transparent() // Fuera de los anillos y en las divisiones
   }
 }
 
@@ -396,13 +443,13 @@ pub fn spaceship_shader(fragment: &Fragment, _uniforms: &Uniforms) -> Color {
     let noise_pattern = ((x * 10.0).sin() * (y * 10.0).cos()).abs();
 
     // Interpolación de colores basada en el patrón de ruido
-    let base_color = if noise_pattern > 0.5 {
+    
+
+    if noise_pattern > 0.5 {
         color_dark_gray
     } else {
         color_light_gray
-    };
-
-    base_color
+    }
 }
 
 
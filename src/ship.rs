@@ -0,0 +1,100 @@
+use nalgebra_glm::{quat_angle_axis, quat_normalize, quat_rotate_vec3, Quat, Vec3};
+
+/// Lo que pide el piloto en un cuadro. `thrust` es local (x derecha, y arriba,
+/// z adelante) y `torque` es (pitch, yaw, roll); las componentes van de -1 a 1.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShipInput {
+    pub thrust: Vec3,
+    pub torque: Vec3,
+    /// Empuja en contra de la velocidad hasta detener la nave.
+    pub brake: bool,
+    /// Multiplica el empuje por [`Ship::boost_factor`].
+    pub boost: bool,
+    /// Empuja hacia la velocidad de una órbita circular alrededor del cuerpo dominante.
+    /// Lo resuelve la escena, que es la que conoce la gravedad.
+    pub circularize: bool,
+}
+
+/// Nave con vuelo newtoniano: el empuje cambia la velocidad y, sin empuje, la nave
+/// sigue a la deriva en línea recta. Solo la rotación tiene amortiguación, como un
+/// sistema de estabilización, para que sea posible apuntar.
+///
+/// Igual que en la cámara libre, en espacio local `-z` es la proa, `+y` arriba y
+/// `+x` la derecha.
+pub struct Ship {
+    pub position: Vec3,
+    pub orientation: Quat,
+    pub velocity: Vec3,
+    /// Velocidad angular en espacio local (pitch, yaw, roll) en radianes por segundo.
+    pub angular_velocity: Vec3,
+    /// Aceleración de los motores en unidades por segundo al cuadrado.
+    pub thrust_acceleration: f32,
+    pub boost_factor: f32,
+    pub angular_acceleration: f32,
+    /// Fracción de la velocidad angular que se pierde por segundo.
+    pub angular_damping: f32,
+}
+
+impl Ship {
+    pub fn new(position: Vec3, orientation: Quat) -> Self {
+        Ship {
+            position,
+            orientation,
+            velocity: Vec3::new(0.0, 0.0, 0.0),
+            angular_velocity: Vec3::new(0.0, 0.0, 0.0),
+            thrust_acceleration: 1.5,
+            boost_factor: 6.0,
+            angular_acceleration: 4.0,
+            angular_damping: 3.0,
+        }
+    }
+
+    pub fn forward(&self) -> Vec3 {
+        quat_rotate_vec3(&self.orientation, &Vec3::new(0.0, 0.0, -1.0))
+    }
+
+    pub fn right(&self) -> Vec3 {
+        quat_rotate_vec3(&self.orientation, &Vec3::new(1.0, 0.0, 0.0))
+    }
+
+    pub fn up(&self) -> Vec3 {
+        quat_rotate_vec3(&self.orientation, &Vec3::new(0.0, 1.0, 0.0))
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.velocity.magnitude()
+    }
+
+    /// Integra un paso de `dt` segundos.
+    pub fn update(&mut self, input: &ShipInput, dt: f32) {
+        let mut acceleration = self.thrust_acceleration;
+        if input.boost {
+            acceleration *= self.boost_factor;
+        }
+
+        let local_thrust = Vec3::new(input.thrust.x, input.thrust.y, -input.thrust.z);
+        self.velocity += quat_rotate_vec3(&self.orientation, &local_thrust) * acceleration * dt;
+
+        if input.brake {
+            // Los retrocohetes no pueden invertir el movimiento, solo detenerlo
+            let speed = self.speed();
+            let delta = acceleration * dt;
+            if speed <= delta {
+                self.velocity = Vec3::new(0.0, 0.0, 0.0);
+            } else {
+                self.velocity -= self.velocity / speed * delta;
+            }
+        }
+
+        self.position += self.velocity * dt;
+
+        self.angular_velocity += input.torque * self.angular_acceleration * dt;
+        self.angular_velocity *= (-self.angular_damping * dt).exp();
+
+        let delta = self.angular_velocity * dt;
+        let pitch = quat_angle_axis(delta.x, &Vec3::new(1.0, 0.0, 0.0));
+        let yaw = quat_angle_axis(delta.y, &Vec3::new(0.0, 1.0, 0.0));
+        let roll = quat_angle_axis(delta.z, &Vec3::new(0.0, 0.0, 1.0));
+        self.orientation = quat_normalize(&(self.orientation * yaw * pitch * roll));
+    }
+}
@@ -0,0 +1,102 @@
+use crate::color::Color;
+
+/// Curva que comprime la luz HDR de la escena al rango [0, 1] de la pantalla.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToneMapper {
+    /// Sin compresión: todo lo que pase de 1 se recorta.
+    Linear,
+    /// `x / (1 + x)`: suave, nunca llega al blanco puro.
+    Reinhard,
+    /// Aproximación de la curva fílmica ACES (Narkowicz), con más contraste.
+    Aces,
+}
+
+impl ToneMapper {
+    pub fn next(self) -> Self {
+        match self {
+            ToneMapper::Linear => ToneMapper::Reinhard,
+            ToneMapper::Reinhard => ToneMapper::Aces,
+            ToneMapper::Aces => ToneMapper::Linear,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ToneMapper::Linear => "Lineal",
+            ToneMapper::Reinhard => "Reinhard",
+            ToneMapper::Aces => "ACES",
+        }
+    }
+
+    /// Operador por su nombre, sin importar mayúsculas: `lineal`/`linear`, `reinhard`
+    /// o `aces`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "lineal" | "linear" => Some(ToneMapper::Linear),
+            "reinhard" => Some(ToneMapper::Reinhard),
+            "aces" => Some(ToneMapper::Aces),
+            _ => None,
+        }
+    }
+
+    /// Aplica la curva a un canal ya multiplicado por la exposición.
+    pub fn apply(self, x: f32) -> f32 {
+        let x = x.max(0.0);
+        match self {
+            ToneMapper::Linear => x.min(1.0),
+            ToneMapper::Reinhard => x / (1.0 + x),
+            ToneMapper::Aces => {
+                ((x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14)).clamp(0.0, 1.0)
+            }
+        }
+    }
+}
+
+/// Cómo se presenta la imagen HDR: exposición y curva de tonemapping.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToneMapping {
+    pub operator: ToneMapper,
+    /// Exposición en pasos (EV): cada paso duplica la luz.
+    pub exposure: f32,
+}
+
+impl Default for ToneMapping {
+    fn default() -> Self {
+        ToneMapping::new()
+    }
+}
+
+impl ToneMapping {
+    /// ACES sin corrección de exposición.
+    pub fn new() -> Self {
+        ToneMapping {
+            operator: ToneMapper::Aces,
+            exposure: 0.0,
+        }
+    }
+
+    /// Sin tonemapping: los colores se recortan tal cual. Para capas 2D, como el
+    /// minimapa, que no tienen luz HDR.
+    pub fn linear() -> Self {
+        ToneMapping {
+            operator: ToneMapper::Linear,
+            exposure: 0.0,
+        }
+    }
+
+    pub fn with_operator(mut self, operator: ToneMapper) -> Self {
+        self.operator = operator;
+        self
+    }
+
+    pub fn with_exposure(mut self, exposure: f32) -> Self {
+        self.exposure = exposure;
+        self
+    }
+
+    /// Color de la escena listo para codificar en sRGB, con canales entre 0 y 1.
+    pub fn map(&self, color: Color) -> Color {
+        let scale = self.exposure.exp2();
+        color.map(|channel| self.operator.apply(channel * scale))
+    }
+}
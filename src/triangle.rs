@@ -2,58 +2,94 @@ use nalgebra_glm::{Vec3, dot};
 use crate::fragment::Fragment;
 use crate::vertex::Vertex;
 use crate::color::Color;
+use crate::framebuffer::MAX_SAMPLES;
 
-pub fn triangle(v1: &Vertex, v2: &Vertex, v3: &Vertex) -> Vec<Fragment> {
+/// Rasteriza un triángulo en coordenadas de pantalla, recortado a `width` x `height`.
+pub fn triangle(v1: &Vertex, v2: &Vertex, v3: &Vertex, width: usize, height: usize) -> Vec<Fragment> {
+  triangle_samples(v1, v2, v3, width, height, &[(0.5, 0.5)])
+}
+
+/// Como [`triangle`], pero prueba la cobertura en cada posición de `samples` dentro del
+/// píxel (hasta [`MAX_SAMPLES`]). Se produce un fragmento por píxel con alguna muestra
+/// cubierta; los atributos se interpolan en el centro si cae dentro del triángulo, o en
+/// la primera muestra cubierta si no, y la profundidad en cada muestra.
+pub fn triangle_samples(
+  v1: &Vertex,
+  v2: &Vertex,
+  v3: &Vertex,
+  width: usize,
+  height: usize,
+  samples: &[(f32, f32)],
+) -> Vec<Fragment> {
   let mut fragments = Vec::new();
   let (a, b, c) = (v1.transformed_position, v2.transformed_position, v3.transformed_position);
 
+  // Solo se recorre la parte de la caja envolvente que cae dentro de la pantalla
   let (min_x, min_y, max_x, max_y) = calculate_bounding_box(&a, &b, &c);
+  let (min_x, min_y) = (min_x.max(0), min_y.max(0));
+  let (max_x, max_y) = (max_x.min(width as i32 - 1), max_y.min(height as i32 - 1));
 
   let light_dir = Vec3::new(0.0, 0.0, 1.0);
 
   let triangle_area = edge_function(&a, &b, &c);
+  let inside = |(w1, w2, w3): (f32, f32, f32)| {
+    (0.0..=1.0).contains(&w1) && (0.0..=1.0).contains(&w2) && (0.0..=1.0).contains(&w3)
+  };
 
   for y in min_y..=max_y {
     for x in min_x..=max_x {
-      let point = Vec3::new(x as f32 + 0.5, y as f32 + 0.5, 0.0);
-
-      let (w1, w2, w3) = barycentric_coordinates(&point, &a, &b, &c, triangle_area);
-
-      if w1 >= 0.0 && w1 <= 1.0 && 
-         w2 >= 0.0 && w2 <= 1.0 &&
-         w3 >= 0.0 && w3 <= 1.0 {
-
-        let normal = v1.transformed_normal * w1 + v2.transformed_normal * w2 + v3.transformed_normal * w3;
-        let normal = normal.normalize();
-
-        let intensity = dot(&normal, &light_dir).max(0.0);
-
-        let base_color = Color::new(100, 100, 100);
-        let lit_color = base_color * intensity;
-
-        let depth = a.z * w1 + b.z * w2 + c.z * w3;
-
-        let vertex_position = v1.position * w1 + v2.position * w2 + v3.position * w3;
-
-        fragments.push(
-            Fragment::new(
-                x as f32,
-                y as f32,
-                lit_color,
-                depth,
-                normal,
-                intensity,
-                vertex_position,
-            )
-        );
+      let mut coverage = 0;
+      let mut sample_depths = [0.0; MAX_SAMPLES];
+      let mut first_covered = None;
+      for (sample, &(dx, dy)) in samples.iter().enumerate().take(MAX_SAMPLES) {
+        let point = Vec3::new(x as f32 + dx, y as f32 + dy, 0.0);
+        let weights = barycentric_coordinates(&point, &a, &b, &c, triangle_area);
+        if inside(weights) {
+          coverage |= 1 << sample;
+          sample_depths[sample] = a.z * weights.0 + b.z * weights.1 + c.z * weights.2;
+          first_covered.get_or_insert(weights);
+        }
       }
+      let Some(first_covered) = first_covered else {
+        continue;
+      };
+
+      let center = Vec3::new(x as f32 + 0.5, y as f32 + 0.5, 0.0);
+      let center_weights = barycentric_coordinates(&center, &a, &b, &c, triangle_area);
+      let (w1, w2, w3) = if inside(center_weights) { center_weights } else { first_covered };
+
+      let normal = v1.transformed_normal * w1 + v2.transformed_normal * w2 + v3.transformed_normal * w3;
+      let normal = normal.normalize();
+
+      let intensity = dot(&normal, &light_dir).max(0.0);
+
+      let base_color = Color::new(100, 100, 100);
+      let lit_color = base_color * intensity;
+
+      let depth = a.z * w1 + b.z * w2 + c.z * w3;
+
+      let vertex_position = v1.position * w1 + v2.position * w2 + v3.position * w3;
+
+      let mut fragment = Fragment::new(
+        x as f32,
+        y as f32,
+        lit_color,
+        depth,
+        normal,
+        intensity,
+        vertex_position,
+      );
+      fragment.coverage = coverage;
+      fragment.sample_depths = sample_depths;
+      fragments.push(fragment);
     }
   }
 
   fragments
 }
 
-fn calculate_bounding_box(v1: &Vec3, v2: &Vec3, v3: &Vec3) -> (i32, i32, i32, i32) {
+/// Caja envolvente `(min_x, min_y, max_x, max_y)` en píxeles.
+pub fn calculate_bounding_box(v1: &Vec3, v2: &Vec3, v3: &Vec3) -> (i32, i32, i32, i32) {
     let min_x = v1.x.min(v2.x).min(v3.x).floor() as i32;
     let min_y = v1.y.min(v2.y).min(v3.y).floor() as i32;
     let max_x = v1.x.max(v2.x).max(v3.x).ceil() as i32;
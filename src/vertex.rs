@@ -1,6 +1,7 @@
 use nalgebra_glm::{Vec2, Vec3};
 use crate::color::Color;
 
+/// Vértice del modelo; `transformed_*` lo llena el vertex shader (posición en píxeles y normal en mundo).
 #[derive(Clone, Debug)]
 pub struct Vertex {
   pub position: Vec3,
@@ -0,0 +1,93 @@
+use crate::framebuffer::Framebuffer;
+
+const STREAK_COLOR: (f32, f32, f32) = (200.0, 220.0, 255.0);
+
+/// Estelas de estrellas que salen del centro de la pantalla durante un salto warp.
+///
+/// Cada estrella tiene un ángulo fijo y avanza hacia afuera; cerca del borde va más
+/// rápido y es más larga, como si pasara junto a la cámara.
+pub struct StarStreaks {
+    /// (ángulo, desfase, brillo) de cada estrella.
+    stars: Vec<(f32, f32, f32)>,
+    phase: f32,
+    /// Vueltas por segundo del recorrido de cada estrella a intensidad máxima.
+    pub speed: f32,
+}
+
+impl StarStreaks {
+    pub fn new(count: usize, seed: u32) -> Self {
+        let stars = (0..count as u32)
+            .map(|i| {
+                let base = seed.wrapping_add(i.wrapping_mul(3));
+                (
+                    hash(base) * std::f32::consts::TAU,
+                    hash(base.wrapping_add(1)),
+                    0.4 + 0.6 * hash(base.wrapping_add(2)),
+                )
+            })
+            .collect();
+        StarStreaks {
+            stars,
+            phase: 0.0,
+            speed: 1.5,
+        }
+    }
+
+    pub fn update(&mut self, intensity: f32, dt: f32) {
+        self.phase = (self.phase + intensity * self.speed * dt).fract();
+    }
+
+    /// Suma las estelas a la imagen; con `intensity` 0 no dibuja nada.
+    pub fn draw(&self, framebuffer: &mut Framebuffer, intensity: f32) {
+        if intensity <= 0.0 {
+            return;
+        }
+        let center_x = framebuffer.width as f32 / 2.0;
+        let center_y = framebuffer.height as f32 / 2.0;
+        let max_radius = center_x.hypot(center_y);
+
+        for &(angle, offset, brightness) in &self.stars {
+            let progress = (offset + self.phase).fract();
+            // El avance cuadrático hace que las estrellas aceleren al acercarse al borde
+            let outer = progress * progress * max_radius;
+            let inner = outer * (1.0 - 0.6 * intensity * progress);
+            let (sin, cos) = angle.sin_cos();
+            let strength = brightness * intensity * progress;
+            let steps = (outer - inner).ceil().max(1.0) as usize;
+            for step in 0..=steps {
+                let radius = inner + (outer - inner) * step as f32 / steps as f32;
+                let x = center_x + cos * radius;
+                let y = center_y + sin * radius;
+                if x < 0.0 || y < 0.0 {
+                    continue;
+                }
+                // La cola de la estela es más tenue que la cabeza
+                let fade = 0.3 + 0.7 * step as f32 / steps as f32;
+                add_pixel(framebuffer, x as usize, y as usize, strength * fade);
+            }
+        }
+    }
+}
+
+fn add_pixel(framebuffer: &mut Framebuffer, x: usize, y: usize, amount: f32) {
+    if x >= framebuffer.width || y >= framebuffer.height {
+        return;
+    }
+    let pixel = &mut framebuffer.buffer[y * framebuffer.width + x];
+    let channel = |shift: u32, color: f32| {
+        let current = ((*pixel >> shift) & 0xFF) as f32;
+        ((current + color * amount).min(255.0) as u32) << shift
+    };
+    *pixel = channel(16, STREAK_COLOR.0) | channel(8, STREAK_COLOR.1) | channel(0, STREAK_COLOR.2);
+}
+
+/// Número pseudoaleatorio entre 0 y 1 a partir de un entero.
+fn hash(value: u32) -> f32 {
+    let mut x = value.wrapping_mul(0x9E37_79B9);
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x85EB_CA6B);
+    x ^= x >> 13;
+    x = x.wrapping_mul(0xC2B2_AE35);
+    x ^= x >> 16;
+    x as f32 / u32::MAX as f32
+}
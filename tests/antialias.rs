@@ -0,0 +1,136 @@
+// Pruebas del antialiasing: cobertura MSAA, reducción SSAA y FXAA.
+
+use nalgebra_glm::{Vec2, Vec3};
+use space_traver::antialias::{downsample, fxaa, AntiAliasing};
+use space_traver::color::Color;
+use space_traver::framebuffer::{resolve_average, Framebuffer};
+use space_traver::triangle::{triangle, triangle_samples};
+use space_traver::vertex::Vertex;
+
+/// Vértice ya transformado a píxeles.
+fn screen_vertex(x: f32, y: f32) -> Vertex {
+    let mut vertex = Vertex::new(Vec3::new(x, y, 0.0), Vec3::new(0.0, 0.0, 1.0), Vec2::zeros());
+    vertex.transformed_position = Vec3::new(x, y, 0.5);
+    vertex.transformed_normal = Vec3::new(0.0, 0.0, 1.0);
+    vertex
+}
+
+/// Triángulo con un borde inclinado que corta los píxeles por lugares distintos.
+fn slanted_triangle() -> [Vertex; 3] {
+    [screen_vertex(0.0, 0.0), screen_vertex(8.0, 0.0), screen_vertex(8.0, 5.0)]
+}
+
+#[test]
+fn msaa_covers_part_of_the_edge_pixels() {
+    let [a, b, c] = slanted_triangle();
+    let mut framebuffer = Framebuffer::new(8, 8);
+    framebuffer.set_samples(4);
+    let fragments = triangle_samples(&a, &b, &c, 8, 8, framebuffer.sample_positions());
+
+    // El borde pasa por debajo del centro de este píxel pero cubre una de sus muestras
+    let edge = fragments
+        .iter()
+        .find(|fragment| fragment.position == Vec2::new(3.0, 2.0))
+        .expect("the edge pixel should have a fragment");
+    let covered = edge.coverage.count_ones();
+    assert!(covered > 0 && covered < 4, "{}", covered);
+    let inside = fragments
+        .iter()
+        .find(|fragment| fragment.position == Vec2::new(6.0, 1.0))
+        .unwrap();
+    assert_eq!(inside.coverage, 0b1111);
+
+    // Con una sola muestra, igual que antes: solo píxeles con el centro dentro
+    let single = triangle(&a, &b, &c, 8, 8);
+    assert!(single.iter().all(|fragment| fragment.coverage == 1));
+    assert!(!single.iter().any(|fragment| fragment.position == Vec2::new(3.0, 2.0)));
+}
+
+#[test]
+fn resolve_averages_samples_and_keeps_the_closest_depth() {
+    let mut framebuffer = Framebuffer::new(2, 1);
+    framebuffer.set_samples(4);
+    framebuffer.clear();
+    framebuffer.set_current_color(Color::rgb(0.8, 0.8, 0.8));
+    framebuffer.point_samples(0, 0, 0b0011, &[0.3, 0.4, 0.0, 0.0]);
+    // Más lejos que lo ya dibujado: no cambia la muestra 0
+    framebuffer.set_current_color(Color::rgb(0.0, 0.0, 1.0));
+    framebuffer.point_samples(0, 0, 0b0001, &[0.9; 4]);
+    framebuffer.resolve();
+
+    let pixel = framebuffer.color[0];
+    assert!(pixel.get_r() > 0.1 && pixel.get_r() < 0.8, "{}", pixel);
+    assert_eq!(pixel.get_b(), pixel.get_r());
+    assert_eq!(framebuffer.zbuffer[0], 0.3);
+    assert!(framebuffer.color[1].is_black());
+    assert_eq!(framebuffer.zbuffer[1], f32::INFINITY);
+}
+
+#[test]
+fn bright_samples_do_not_dominate_the_average() {
+    let sun = Color::rgb(6.0, 6.0, 6.0);
+    let space = Color::black();
+    let quarter = resolve_average(&[sun, space, space, space]);
+    let half = resolve_average(&[sun, sun, space, space]);
+    let full = resolve_average(&[sun; 4]);
+
+    // Un promedio simple daría 1.5 con una sola muestra: blanco tras el tonemapping
+    assert!(quarter.get_r() < 1.0, "{}", quarter);
+    assert!(quarter.get_r() < half.get_r() && half.get_r() < full.get_r());
+    assert!((full.get_r() - 6.0).abs() < 1e-4);
+}
+
+#[test]
+fn downsample_averages_each_block() {
+    let mut large = Framebuffer::new(4, 2);
+    large.clear();
+    large.set_current_color(Color::rgb(1.0, 1.0, 1.0));
+    large.point(0, 0, 0.5);
+    large.point(1, 1, 0.2);
+    let mut small = Framebuffer::new(2, 1);
+    downsample(&large, &mut small, 2);
+
+    let gray = small.color[0].get_r();
+    assert!(gray > 0.0 && gray < 1.0, "{}", gray);
+    assert_eq!(small.zbuffer[0], 0.2);
+    assert_eq!(small.overdraw[0], 1);
+    assert!(small.color[1].is_black());
+}
+
+#[test]
+fn fxaa_softens_edges_and_leaves_flat_areas_alone() {
+    let size = 16;
+    let mut framebuffer = Framebuffer::new(size, size);
+    // Escalera blanca sobre negro: el borde avanza un píxel cada dos filas
+    for y in 0..size {
+        for x in 0..size {
+            let inside = x < 4 + y / 2;
+            framebuffer.buffer[y * size + x] = if inside { 0xFFFFFF } else { 0x000000 };
+        }
+    }
+    let before = framebuffer.buffer.clone();
+    fxaa(&mut framebuffer);
+
+    let partial = |pixel: u32| pixel != 0xFFFFFF && pixel != 0x000000;
+    assert!(framebuffer.buffer.iter().any(|&pixel| partial(pixel)));
+    assert!(!before.iter().any(|&pixel| partial(pixel)));
+    // Lejos del borde nada cambia
+    assert_eq!(framebuffer.buffer[8 * size], 0xFFFFFF);
+    assert_eq!(framebuffer.buffer[8 * size + 15], 0x000000);
+}
+
+#[test]
+fn modes_cycle_and_parse_by_name() {
+    let mut mode = AntiAliasing::Off;
+    for expected in AntiAliasing::all().iter().skip(1) {
+        mode = mode.next();
+        assert_eq!(mode, *expected);
+    }
+    assert_eq!(mode.next(), AntiAliasing::Off);
+
+    assert_eq!(AntiAliasing::from_name("MSAA"), Some(AntiAliasing::Msaa));
+    assert_eq!(AntiAliasing::from_name("fxaa"), Some(AntiAliasing::Fxaa));
+    assert_eq!(AntiAliasing::from_name("blur"), None);
+    assert_eq!(AntiAliasing::Msaa.samples(), 4);
+    assert_eq!(AntiAliasing::Ssaa.samples(), 1);
+}
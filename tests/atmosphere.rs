@@ -0,0 +1,127 @@
+// Pruebas de las atmósferas: opacidad según el camino de la mirada, luz del Sol y la
+// capa alrededor de los cuerpos de la escena.
+
+use nalgebra_glm::{Mat4, Vec3, Vec4};
+use space_traver::atmosphere::Atmosphere;
+use space_traver::camera::Camera;
+use space_traver::color::Color;
+use space_traver::framebuffer::Framebuffer;
+use space_traver::pipeline::{create_model_matrix, create_uniforms, project_to_screen};
+use space_traver::scene::{Scene, DISTANCE_SCALE};
+
+const CENTER: Vec3 = Vec3::new(0.0, 0.0, 0.0);
+const RADIUS: f32 = 1.0;
+
+fn atmosphere() -> Atmosphere {
+    Atmosphere::new(Color::rgb(0.2, 0.4, 1.0), 0.1, 0.2)
+}
+
+/// Punto de la cara de la capa que mira hacia `+z`, a `height` del eje.
+fn shell_point(height: f32) -> Vec3 {
+    let shell = RADIUS * atmosphere().scale();
+    Vec3::new(height, 0.0, (shell * shell - height * height).sqrt())
+}
+
+#[test]
+fn the_limb_is_more_opaque_than_the_center_of_the_disk() {
+    let eye = Vec3::new(0.0, 0.0, 50.0);
+    let sun = Vec3::new(0.0, 0.0, 100.0);
+    let at = |height| atmosphere().scatter(eye, shell_point(height), CENTER, RADIUS, sun);
+
+    let center = at(0.0).get_a();
+    let limb = at(RADIUS).get_a();
+    let edge = at(RADIUS * 1.095).get_a();
+    // De frente, el espesor óptico es la densidad
+    assert!((center - (1.0 - (-0.2f32).exp())).abs() < 0.02, "centro: {}", center);
+    assert!(limb > center * 2.0, "limbo {} vs centro {}", limb, center);
+    assert!(edge < limb * 0.5, "borde exterior {} vs limbo {}", edge, limb);
+}
+
+#[test]
+fn the_back_of_the_shell_is_transparent() {
+    let eye = Vec3::new(0.0, 0.0, 50.0);
+    let back = Vec3::new(0.0, 0.0, -1.1);
+    let color = atmosphere().scatter(eye, back, CENTER, RADIUS, Vec3::new(0.0, 0.0, 100.0));
+
+    assert_eq!(color.get_a(), 0.0);
+}
+
+#[test]
+fn the_night_side_does_not_scatter_light() {
+    let eye = Vec3::new(0.0, 0.0, 50.0);
+    let point = shell_point(0.0);
+
+    let day = atmosphere().scatter(eye, point, CENTER, RADIUS, Vec3::new(0.0, 0.0, 100.0));
+    let night = atmosphere().scatter(eye, point, CENTER, RADIUS, Vec3::new(0.0, 0.0, -100.0));
+
+    assert!(day.luminance() > 0.1);
+    assert!(night.luminance() < 1e-4, "noche: {}", night);
+    // La luz no cambia cuánto tapa la capa
+    assert!((day.get_a() - night.get_a()).abs() < 1e-6);
+}
+
+#[test]
+fn haze_glows_when_looking_toward_the_sun() {
+    let eye = Vec3::new(0.0, 0.0, 50.0);
+    // Limbo apenas del lado del Sol, con el Sol detrás del planeta
+    let point = shell_point(RADIUS * 1.02);
+    let behind = Vec3::new(20.0, 0.0, -100.0);
+    let plain = atmosphere();
+    let hazy = atmosphere().with_haze(Color::rgb(1.0, 1.0, 1.0), 2.0);
+
+    let without = plain.scatter(eye, point, CENTER, RADIUS, behind);
+    let with = hazy.scatter(eye, point, CENTER, RADIUS, behind);
+
+    assert!(with.get_r() > without.get_r() * 4.0, "{} vs {}", with, without);
+    assert_eq!(with.get_a(), without.get_a());
+}
+
+#[test]
+fn the_shell_stays_centered_on_the_mesh() {
+    let mesh_center = Vec3::new(0.035634, 5.310887, -0.630639);
+    let body_matrix =
+        create_model_matrix(Vec3::new(4.0, 0.0, -2.0), 0.6, Vec3::new(0.0, 1.0, 0.0));
+    let shell_matrix = atmosphere().shell_matrix(&body_matrix, mesh_center);
+    let world = |matrix: Mat4, point: Vec3| {
+        (matrix * Vec4::new(point.x, point.y, point.z, 1.0)).xyz()
+    };
+
+    let center = world(body_matrix, mesh_center);
+    assert!((world(shell_matrix, mesh_center) - center).magnitude() < 1e-4);
+
+    // Un punto de la superficie queda `thickness` más lejos del centro
+    let surface = mesh_center + Vec3::new(1.0, 0.0, 0.0);
+    let body_distance = (world(body_matrix, surface) - center).magnitude();
+    let shell_distance = (world(shell_matrix, surface) - center).magnitude();
+    assert!((shell_distance / body_distance - 1.1).abs() < 1e-4);
+}
+
+#[test]
+fn earth_has_a_blue_limb_in_the_scene() {
+    let mut scene = Scene::load(DISTANCE_SCALE);
+    let time = 240;
+    let earth = scene.bodies.iter().position(|body| body.name == "Tierra").unwrap();
+    assert!(scene.bodies[earth].atmosphere.is_some());
+    let venus = scene.bodies.iter().position(|body| body.name == "Venus").unwrap();
+    assert!(scene.bodies[venus].atmosphere.is_some());
+
+    // Mirando la Tierra desde el lado del Sol, por encima del plano de las órbitas
+    let bounds = scene.body_bounds(earth, time);
+    let toward_sun = -bounds.center.normalize();
+    let eye = bounds.center + toward_sun * 3.0 + Vec3::new(0.0, 0.3, 0.0);
+    let camera = Camera::new(eye, bounds.center, Vec3::new(0.0, 1.0, 0.0));
+    scene.show_orbits = false;
+    scene.show_trails = false;
+    scene.show_trajectory = false;
+    let mut framebuffer = Framebuffer::new(240, 180);
+    scene.render(&mut framebuffer, &camera, time);
+
+    // Justo por fuera del borde del planeta, de costado respecto de la mirada
+    let side = toward_sun.cross(&Vec3::new(0.0, 1.0, 0.0)).normalize();
+    let limb = bounds.center + side * (bounds.radius * 1.02);
+    let uniforms = create_uniforms(&framebuffer, &camera, Mat4::identity(), time);
+    let screen = project_to_screen(&uniforms, limb).unwrap();
+    let pixel = framebuffer.buffer[screen.y as usize * framebuffer.width + screen.x as usize];
+    let (red, blue) = ((pixel >> 16) & 0xFF, pixel & 0xFF);
+    assert!(blue > 40 && blue > red * 2, "pixel {:06X}", pixel);
+}
@@ -0,0 +1,89 @@
+// Pruebas del piloto automático: ruta, salto warp y órbita de estacionamiento.
+
+use nalgebra_glm::Vec3;
+use space_traver::autopilot::{parking_radius, Autopilot, AutopilotPhase};
+use space_traver::scene::{Scene, DISTANCE_SCALE};
+use space_traver::ship::ShipInput;
+
+const DT: f32 = 1.0 / 60.0;
+const EARTH: usize = 3;
+
+/// Corre el piloto automático hasta que llega y devuelve el instante final.
+fn fly_to(scene: &mut Scene, pilot: &mut Autopilot, mut time: u32) -> u32 {
+    for _ in 0..6000 {
+        time += 1;
+        pilot.update(scene, time, DT);
+        if pilot.phase == AutopilotPhase::Arrived {
+            return time;
+        }
+    }
+    panic!("the autopilot never arrived");
+}
+
+#[test]
+fn route_goes_around_the_sun() {
+    let mut scene = Scene::load(DISTANCE_SCALE);
+    // A t = 471 la Tierra está del otro lado del Sol
+    let time = 471;
+    let earth = scene.body_bounds(EARTH, time).center;
+    scene.ship.position = Vec3::new(-earth.x, earth.y, -earth.z);
+
+    let pilot = Autopilot::engage(&scene, EARTH, time);
+    let sun = scene.body_bounds(0, time);
+    for i in 0..=100 {
+        let point = pilot.path_point(&scene, i as f32 / 100.0, time);
+        assert!((point - sun.center).magnitude() > sun.radius, "cruza el Sol en {}", point);
+    }
+}
+
+#[test]
+fn straight_route_when_nothing_is_in_the_way() {
+    let mut scene = Scene::load(DISTANCE_SCALE);
+    let earth = scene.body_bounds(EARTH, 0).center;
+    scene.ship.position = earth + Vec3::new(5.0, 0.0, 0.0);
+
+    let pilot = Autopilot::engage(&scene, EARTH, 0);
+    let end = pilot.path_point(&scene, 1.0, 0);
+    assert!((pilot.control_point() - (scene.ship.position + end) / 2.0).magnitude() < 1e-4);
+}
+
+#[test]
+fn warp_speeds_up_and_slows_down() {
+    let mut scene = Scene::load(DISTANCE_SCALE);
+    let mut pilot = Autopilot::engage(&scene, EARTH, 0);
+    let mut intensities = Vec::new();
+    let mut time = 0;
+    while pilot.phase != AutopilotPhase::Arrived {
+        time += 1;
+        pilot.update(&mut scene, time, DT);
+        intensities.push(pilot.warp_intensity());
+    }
+    assert_eq!(intensities[0], 0.0);
+    assert_eq!(*intensities.last().unwrap(), 0.0);
+    assert!(intensities.iter().any(|intensity| *intensity > 0.9));
+}
+
+#[test]
+fn autopilot_parks_the_ship_in_a_stable_orbit() {
+    let mut scene = Scene::load(DISTANCE_SCALE);
+    let mut pilot = Autopilot::engage(&scene, EARTH, 0);
+    let mut time = fly_to(&mut scene, &mut pilot, 0);
+
+    let radius = parking_radius(&scene, EARTH, time);
+    let offset = scene.ship.position - scene.body_bounds(EARTH, time).center;
+    assert!((offset.magnitude() - radius).abs() < 1e-3);
+    assert!(radius > scene.body_bounds(EARTH, time).radius * 2.0);
+    let circular = (scene.bodies[EARTH].gravity / radius).sqrt();
+    assert!((scene.ship.speed() - circular).abs() < 1e-4);
+    // La nave apunta hacia donde se mueve
+    assert!(scene.ship.forward().dot(&scene.ship.velocity.normalize()) > 0.999);
+
+    // Ya sin piloto automático la nave se queda orbitando la Tierra
+    for _ in 0..600 {
+        time += 1;
+        scene.fly_ship(&ShipInput::default(), time, DT);
+        let distance = (scene.ship.position - scene.body_bounds(EARTH, time).center).magnitude();
+        assert!((distance - radius).abs() < 0.05, "distancia {}", distance);
+    }
+    assert_eq!(scene.ship_frame(), Some(EARTH));
+}
@@ -0,0 +1,144 @@
+// Pruebas de transparencia: modos de mezcla, descarte por opacidad y orden de dibujo.
+
+use nalgebra_glm::{Mat4, Vec2, Vec3};
+use space_traver::camera::Camera;
+use space_traver::color::{BlendMode, Color};
+use space_traver::debug::DebugView;
+use space_traver::fragment::Fragment;
+use space_traver::framebuffer::Framebuffer;
+use space_traver::pipeline::{create_uniforms, project_to_screen, render};
+use space_traver::scene::{Scene, DISTANCE_SCALE};
+use space_traver::vertex::Vertex;
+use space_traver::Uniforms;
+
+const EPSILON: f32 = 1e-5;
+
+fn assert_color(actual: Color, expected: Color) {
+    let channels = |color: Color| [color.get_r(), color.get_g(), color.get_b(), color.get_a()];
+    for (a, e) in channels(actual).into_iter().zip(channels(expected)) {
+        assert!((a - e).abs() < EPSILON, "{} != {}", actual, expected);
+    }
+}
+
+#[test]
+fn blend_modes_weight_the_source_by_its_alpha() {
+    let destination = Color::rgb(0.2, 0.4, 0.8);
+    let source = Color::rgba(1.0, 0.0, 0.5, 0.5);
+
+    assert_color(BlendMode::Opaque.apply(destination, source), source);
+    assert_color(BlendMode::Normal.apply(destination, source), Color::rgb(0.6, 0.2, 0.65));
+    assert_color(BlendMode::Add.apply(destination, source), Color::rgb(0.7, 0.4, 1.05));
+    assert_color(BlendMode::Multiply.apply(destination, source), Color::rgb(0.2, 0.2, 0.6));
+    assert_color(BlendMode::Subtract.apply(destination, source), Color::rgb(0.0, 0.4, 0.55));
+
+    // Sin opacidad no cambia nada
+    for mode in [BlendMode::Normal, BlendMode::Add, BlendMode::Multiply, BlendMode::Subtract] {
+        assert_color(mode.apply(destination, Color::transparent()), destination);
+    }
+}
+
+#[test]
+fn light_operations_keep_the_alpha() {
+    let color = Color::rgba(0.5, 0.5, 0.5, 0.25);
+    assert_eq!((color * 2.0).get_a(), 0.25);
+    assert_eq!((color + Color::rgb(1.0, 1.0, 1.0)).get_a(), 0.25);
+    assert_eq!(color.map(|c| c * 3.0).get_a(), 0.25);
+    assert_eq!(Color::new(10, 20, 30).get_a(), 1.0);
+    assert_eq!(color.with_alpha(2.0).get_a(), 1.0);
+}
+
+#[test]
+fn transparent_points_blend_without_writing_depth() {
+    let mut framebuffer = Framebuffer::new(1, 1);
+    framebuffer.clear();
+    framebuffer.set_blend_mode(BlendMode::Normal);
+    framebuffer.set_current_color(Color::rgba(1.0, 1.0, 1.0, 0.5));
+    framebuffer.point(0, 0, 0.5);
+    assert_color(framebuffer.color[0], Color::rgb(0.5, 0.5, 0.5));
+    assert_eq!(framebuffer.zbuffer[0], f32::INFINITY);
+
+    // Un opaco detrás del transparente igual lo tapa: por eso se dibuja antes
+    framebuffer.set_blend_mode(BlendMode::Opaque);
+    framebuffer.set_current_color(Color::rgb(0.0, 0.0, 1.0));
+    framebuffer.point(0, 0, 0.9);
+    assert_color(framebuffer.color[0], Color::rgb(0.0, 0.0, 1.0));
+    assert_eq!(framebuffer.zbuffer[0], 0.9);
+}
+
+/// Triángulo que cubre todo un framebuffer de 4x4, a profundidad `z`.
+fn screen_triangle(z: f32) -> Vec<Vertex> {
+    [(-1.0, -1.0), (12.0, -1.0), (-1.0, 12.0)]
+        .iter()
+        .map(|&(x, y)| Vertex::new(Vec3::new(x, y, z), Vec3::new(0.0, 0.0, 1.0), Vec2::zeros()))
+        .collect()
+}
+
+/// Uniforms que no transforman: los vértices ya están en píxeles.
+fn screen_uniforms() -> Uniforms {
+    Uniforms {
+        model_matrix: Mat4::identity(),
+        view_matrix: Mat4::identity(),
+        projection_matrix: Mat4::identity(),
+        viewport_matrix: Mat4::identity(),
+        time: 0,
+    }
+}
+
+/// Rojo semitransparente para lo cercano y azul para lo lejano.
+fn depth_shader(fragment: &Fragment, _uniforms: &Uniforms) -> Color {
+    if fragment.depth < 0.4 {
+        Color::rgba(1.0, 0.0, 0.0, 0.5)
+    } else {
+        Color::rgba(0.0, 0.0, 1.0, 0.5)
+    }
+}
+
+#[test]
+fn transparent_triangles_are_drawn_back_to_front() {
+    let mut framebuffer = Framebuffer::new(4, 4);
+    framebuffer.clear();
+    framebuffer.set_blend_mode(BlendMode::Normal);
+    // El rojo está más cerca pero viene primero en el modelo
+    let mut vertices = screen_triangle(0.2);
+    vertices.extend(screen_triangle(0.6));
+    render(&mut framebuffer, &screen_uniforms(), &vertices, depth_shader, &mut DebugView::new());
+
+    let pixel = framebuffer.color[5];
+    assert!(pixel.get_r() > pixel.get_b(), "{}", pixel);
+}
+
+#[test]
+fn fragments_without_alpha_are_discarded() {
+    let mut framebuffer = Framebuffer::new(4, 4);
+    framebuffer.clear();
+    let vertices = screen_triangle(0.5);
+    let invisible = |_: &Fragment, _: &Uniforms| Color::transparent();
+    render(&mut framebuffer, &screen_uniforms(), &vertices, invisible, &mut DebugView::new());
+
+    assert!(framebuffer.color.iter().all(Color::is_black));
+    assert!(framebuffer.zbuffer.iter().all(|depth| *depth == f32::INFINITY));
+}
+
+#[test]
+fn saturn_is_visible_through_its_rings() {
+    let mut scene = Scene::load(DISTANCE_SCALE);
+    let time = 240;
+    let saturn = scene.body_bounds(7, time);
+    let camera = Camera::new(
+        saturn.center + Vec3::new(0.0, 4.0, 8.0),
+        saturn.center,
+        Vec3::new(0.0, 1.0, 0.0),
+    );
+    let mut framebuffer = Framebuffer::new(160, 120);
+    scene.render(&mut framebuffer, &camera, time);
+
+    // Ningún cuerpo negro tapa al planeta
+    let uniforms = create_uniforms(&framebuffer, &camera, Mat4::identity(), time);
+    let center = project_to_screen(&uniforms, saturn.center).unwrap();
+    let pixel = framebuffer.color[center.y as usize * framebuffer.width + center.x as usize];
+    assert!(pixel.luminance() > 0.1, "{}", pixel);
+    // Y el anillo B se ve a un lado, en el plano del ecuador
+    let ring = project_to_screen(&uniforms, saturn.center + Vec3::new(1.8, 0.0, 0.0)).unwrap();
+    let pixel = framebuffer.color[ring.y as usize * framebuffer.width + ring.x as usize];
+    assert!(pixel.luminance() > 0.05, "{}", pixel);
+}
@@ -0,0 +1,147 @@
+// Pruebas de los controladores de cámara: vuelo libre, seguimiento de cuerpos y mouse.
+
+use nalgebra_glm::Vec3;
+use space_traver::camera::Camera;
+use space_traver::focus::{ease_in_out, Focus};
+use space_traver::free_camera::FreeCamera;
+use space_traver::mouse::{MouseController, MouseState};
+
+const EPSILON: f32 = 1e-4;
+
+fn assert_close(actual: Vec3, expected: Vec3) {
+    assert!(
+        (actual - expected).magnitude() < EPSILON,
+        "{:?} != {:?}",
+        actual,
+        expected
+    );
+}
+
+fn camera() -> Camera {
+    Camera::new(
+        Vec3::new(0.0, 0.0, 10.0),
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    )
+}
+
+#[test]
+fn free_camera_keeps_the_view_of_the_orbit_camera() {
+    let orbit = camera();
+    let free = FreeCamera::from_camera(&orbit);
+
+    assert_close(free.forward(), orbit.forward());
+    assert_close(free.up(), orbit.up());
+    assert_close(free.right(), orbit.right());
+}
+
+#[test]
+fn free_camera_pitches_past_the_poles() {
+    let mut free = FreeCamera::from_camera(&camera());
+    free.angular_damping = 0.0;
+    free.angular_velocity = Vec3::new(std::f32::consts::PI, 0.0, 0.0);
+
+    // Medio giro de pitch: queda mirando hacia atrás y cabeza abajo
+    for _ in 0..100 {
+        free.update(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 0.0), 0.01);
+    }
+    assert_close(free.forward(), Vec3::new(0.0, 0.0, 1.0));
+    assert_close(free.up(), Vec3::new(0.0, -1.0, 0.0));
+}
+
+#[test]
+fn ease_in_out_starts_and_ends_at_rest() {
+    assert_eq!(ease_in_out(0.0), 0.0);
+    assert_eq!(ease_in_out(1.0), 1.0);
+    assert!((ease_in_out(0.5) - 0.5).abs() < EPSILON);
+    assert!(ease_in_out(0.1) < 0.1);
+    assert!(ease_in_out(0.9) > 0.9);
+}
+
+#[test]
+fn focus_travels_to_the_target_and_then_follows_it() {
+    let mut camera = camera();
+    let mut focus = Focus::new();
+    focus.transition_time = 1.0;
+    focus.set_target(Some(0), &camera, 0.5);
+
+    let target = Vec3::new(20.0, 0.0, 0.0);
+    for _ in 0..10 {
+        focus.update(&mut camera, target, 0.1);
+    }
+    assert!(!focus.is_transitioning());
+    assert_close(camera.center, target);
+    assert_close(camera.eye, target + Vec3::new(0.0, 0.0, 0.5 * focus.distance_factor));
+
+    // Una vez llegado, el cuerpo se mueve y la cámara lo acompaña
+    let moved = Vec3::new(21.0, 0.0, 1.0);
+    focus.update(&mut camera, moved, 0.1);
+    assert_close(camera.center, moved);
+    assert_close(camera.eye, moved + Vec3::new(0.0, 0.0, 0.5 * focus.distance_factor));
+}
+
+fn drag(mouse: &mut MouseController, camera: &mut Camera, from: (f32, f32), to: (f32, f32), right: bool) {
+    for position in [from, to] {
+        let state = MouseState {
+            position: Some(position),
+            left: !right,
+            right,
+            scroll: 0.0,
+        };
+        mouse.update_orbit(&state, camera);
+    }
+    mouse.update_orbit(&MouseState::default(), camera);
+}
+
+#[test]
+fn mouse_drag_orbits_around_the_center() {
+    let mut camera = camera();
+    let mut mouse = MouseController::new();
+    drag(&mut mouse, &mut camera, (100.0, 100.0), (150.0, 80.0), false);
+
+    assert_close(camera.center, Vec3::new(0.0, 0.0, 0.0));
+    assert!(((camera.eye - camera.center).magnitude() - 10.0).abs() < EPSILON);
+    assert!(camera.eye.x < 0.0, "arrastrar a la derecha gira el mundo a la derecha");
+    assert!(camera.eye.y < 0.0, "arrastrar hacia arriba baja la cámara");
+}
+
+#[test]
+fn mouse_invert_y_flips_vertical_orbit() {
+    let mut normal = camera();
+    let mut inverted = camera();
+    let mut mouse = MouseController::new();
+    drag(&mut mouse, &mut normal, (0.0, 0.0), (0.0, 40.0), false);
+    mouse.invert_y = true;
+    drag(&mut mouse, &mut inverted, (0.0, 0.0), (0.0, 40.0), false);
+
+    assert!((normal.eye.y + inverted.eye.y).abs() < EPSILON);
+    assert!(normal.eye.y > 0.0);
+}
+
+#[test]
+fn mouse_right_drag_pans_eye_and_center_together() {
+    let mut camera = camera();
+    let mut mouse = MouseController::new();
+    drag(&mut mouse, &mut camera, (0.0, 0.0), (-100.0, 0.0), true);
+
+    assert!(camera.center.x > 0.0);
+    assert_close(camera.eye - camera.center, Vec3::new(0.0, 0.0, 10.0));
+}
+
+#[test]
+fn mouse_wheel_zoom_scales_with_distance() {
+    let mut camera = camera();
+    let mut mouse = MouseController::new();
+    let scroll = MouseState {
+        scroll: 1.0,
+        ..MouseState::default()
+    };
+
+    mouse.update_orbit(&scroll, &mut camera);
+    let first = (camera.eye - camera.center).magnitude();
+    mouse.update_orbit(&scroll, &mut camera);
+    let second = (camera.eye - camera.center).magnitude();
+
+    assert!((first - 9.0).abs() < EPSILON);
+    assert!((second / first - 0.9).abs() < EPSILON);
+}
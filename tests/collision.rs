@@ -0,0 +1,153 @@
+// Pruebas de detección y respuesta a colisiones.
+
+use nalgebra_glm::{quat_identity, Vec3};
+use space_traver::bounds::BoundingSphere;
+use space_traver::collision::{
+    closest_point_on_triangle, mesh_contact, respond, sphere_contact, CollisionResponse,
+    CollisionSettings,
+};
+use space_traver::scene::{Scene, DISTANCE_SCALE};
+use space_traver::ship::Ship;
+
+const EPSILON: f32 = 1e-4;
+
+fn sphere(center: Vec3, radius: f32) -> BoundingSphere {
+    BoundingSphere { center, radius }
+}
+
+/// Cubo de lado 2 centrado en el origen.
+fn cube() -> Vec<[Vec3; 3]> {
+    // Índices de las esquinas de cada cara; la esquina i tiene x, y, z según sus bits
+    let corner = |i: usize| {
+        let sign = |bit: usize| if i & bit != 0 { 1.0 } else { -1.0 };
+        Vec3::new(sign(1), sign(2), sign(4))
+    };
+    let faces = [
+        [4, 5, 7, 6],
+        [1, 0, 2, 3],
+        [5, 1, 3, 7],
+        [0, 4, 6, 2],
+        [6, 7, 3, 2],
+        [0, 1, 5, 4],
+    ];
+    faces
+        .iter()
+        .flat_map(|[a, b, c, d]| {
+            [
+                [corner(*a), corner(*b), corner(*c)],
+                [corner(*a), corner(*c), corner(*d)],
+            ]
+        })
+        .collect()
+}
+
+#[test]
+fn closest_point_covers_vertices_edges_and_face() {
+    let triangle = [
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    ];
+    let closest = |p: Vec3| closest_point_on_triangle(p, &triangle);
+
+    assert_eq!(closest(Vec3::new(-1.0, -1.0, 2.0)), triangle[0]);
+    assert_eq!(closest(Vec3::new(3.0, -0.5, 0.0)), triangle[1]);
+    let assert_closest = |p: Vec3, expected: Vec3| {
+        assert!((closest(p) - expected).magnitude() < EPSILON, "{:?}", closest(p));
+    };
+    assert_closest(Vec3::new(0.5, -1.0, 1.0), Vec3::new(0.5, 0.0, 0.0));
+    assert_closest(Vec3::new(1.0, 1.0, 0.0), Vec3::new(0.5, 0.5, 0.0));
+    assert_closest(Vec3::new(0.2, 0.3, 5.0), Vec3::new(0.2, 0.3, 0.0));
+}
+
+#[test]
+fn spheres_touch_only_when_overlapping() {
+    let body = sphere(Vec3::new(0.0, 0.0, 0.0), 1.0);
+    assert!(sphere_contact(&sphere(Vec3::new(0.0, 2.5, 0.0), 1.0), &body).is_none());
+
+    let contact = sphere_contact(&sphere(Vec3::new(0.0, 1.5, 0.0), 1.0), &body).unwrap();
+    assert!((contact.normal - Vec3::new(0.0, 1.0, 0.0)).magnitude() < EPSILON);
+    assert!((contact.depth - 0.5).abs() < EPSILON);
+    assert!((contact.point - Vec3::new(0.0, 1.0, 0.0)).magnitude() < EPSILON);
+}
+
+#[test]
+fn mesh_contact_uses_the_real_surface() {
+    let cube = cube();
+    let center = Vec3::new(0.0, 0.0, 0.0);
+
+    // Cerca de una arista la esfera envolvente del cubo ya choca, pero la malla no
+    let near_corner = sphere(Vec3::new(1.3, 1.3, 0.0), 0.2);
+    assert!(sphere_contact(&near_corner, &sphere(center, 3.0_f32.sqrt())).is_some());
+    assert!(mesh_contact(&near_corner, &cube, center).is_none());
+
+    let touching = mesh_contact(&sphere(Vec3::new(0.0, 0.0, 1.1), 0.2), &cube, center).unwrap();
+    assert!((touching.normal - Vec3::new(0.0, 0.0, 1.0)).magnitude() < EPSILON);
+    assert!((touching.depth - 0.1).abs() < EPSILON);
+
+    // Con el centro adentro la normal igual apunta hacia afuera
+    let inside = mesh_contact(&sphere(Vec3::new(0.0, 0.0, 0.8), 0.1), &cube, center).unwrap();
+    assert!((inside.normal - Vec3::new(0.0, 0.0, 1.0)).magnitude() < EPSILON);
+    assert!((inside.depth - 0.3).abs() < EPSILON);
+}
+
+fn falling_ship() -> Ship {
+    let mut ship = Ship::new(Vec3::new(0.0, 1.05, 0.0), quat_identity());
+    ship.velocity = Vec3::new(1.0, -2.0, 0.0);
+    ship
+}
+
+#[test]
+fn responses_stop_or_bounce_the_ship() {
+    let body = sphere(Vec3::new(0.0, 0.0, 0.0), 1.0);
+    let mut settings = CollisionSettings {
+        response: CollisionResponse::Bounce,
+        ..CollisionSettings::default()
+    };
+
+    let mut ship = falling_ship();
+    let contact = sphere_contact(&sphere(ship.position, 0.1), &body).unwrap();
+    let impact = respond(&mut ship, &contact, Vec3::new(0.0, 0.0, 0.0), &settings);
+    assert!((impact - 2.0).abs() < EPSILON);
+    let bounced = Vec3::new(1.0, 2.0 * settings.restitution, 0.0);
+    assert!((ship.velocity - bounced).magnitude() < EPSILON);
+    assert!((ship.position.y - 1.1).abs() < EPSILON);
+
+    settings.response = CollisionResponse::Stop;
+    let mut ship = falling_ship();
+    let body_velocity = Vec3::new(0.0, 0.0, 3.0);
+    respond(&mut ship, &contact, body_velocity, &settings);
+    assert_eq!(ship.velocity, body_velocity);
+}
+
+#[test]
+fn scene_reports_each_impact_once() {
+    let mut scene = Scene::load(DISTANCE_SCALE);
+    scene.collisions.response = CollisionResponse::Crash;
+    let sun = scene.body_bounds(0, 0);
+    scene.ship.position = sun.center + Vec3::new(0.0, 0.0, sun.radius);
+    scene.ship.velocity = Vec3::new(0.0, 0.0, -1.0);
+
+    let events = scene.collide_ship(0, 1.0 / 60.0);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].body, 0);
+    assert!(events[0].crashed);
+    assert!((events[0].impact_speed - 1.0).abs() < EPSILON);
+
+    // Sigue apoyada en la superficie: no hay un impacto nuevo
+    scene.ship.position -= Vec3::new(0.0, 0.0, 0.01);
+    assert!(scene.collide_ship(0, 1.0 / 60.0).is_empty());
+}
+
+#[test]
+fn saturn_rings_are_not_solid() {
+    let mut scene = Scene::load(DISTANCE_SCALE);
+    let rings = scene.bodies.len() - 1;
+    let saturn = scene.body_bounds(rings - 1, 0);
+    let ring_bounds = scene.body_bounds(rings, 0);
+    // Dentro de la esfera de los anillos pero fuera de Saturno
+    let between = (saturn.radius + ring_bounds.radius) / 2.0;
+    scene.ship.position = saturn.center + Vec3::new(0.0, between, 0.0);
+
+    assert!(scene.contacts(&scene.ship_bounds(), 0).is_empty());
+}
@@ -0,0 +1,73 @@
+// Pruebas del texto de mapa de bits dibujado sobre el framebuffer.
+
+use space_traver::font::{
+    draw_styled_text, draw_text, text_size, text_width, TextStyle, GLYPH_HEIGHT, GLYPH_WIDTH,
+    LINE_HEIGHT,
+};
+use space_traver::framebuffer::Framebuffer;
+
+fn lit_pixels(framebuffer: &Framebuffer) -> Vec<(usize, usize, u32)> {
+    framebuffer
+        .buffer
+        .iter()
+        .enumerate()
+        .filter(|(_, pixel)| **pixel != 0)
+        .map(|(i, pixel)| (i % framebuffer.width, i / framebuffer.width, *pixel))
+        .collect()
+}
+
+#[test]
+fn text_is_drawn_opaque_inside_its_box() {
+    let mut framebuffer = Framebuffer::new(40, 20);
+    draw_text(&mut framebuffer, 2, 3, "Hi", 0x123456);
+    let pixels = lit_pixels(&framebuffer);
+    assert!(!pixels.is_empty());
+    for (x, y, color) in pixels {
+        assert_eq!(color, 0x123456);
+        assert!((2..2 + text_width("Hi")).contains(&x));
+        assert!((3..3 + GLYPH_HEIGHT).contains(&y));
+    }
+}
+
+#[test]
+fn alpha_blends_with_the_background() {
+    let mut framebuffer = Framebuffer::new(10, 10);
+    framebuffer.buffer.fill(0x000000);
+    // `|` de la fuente ocupa la columna central de todas las filas
+    draw_styled_text(&mut framebuffer, 0, 0, "|", &TextStyle::new(0xFF8000).with_alpha(0.5));
+    assert_eq!(framebuffer.buffer[2], 0x804000);
+
+    let mut hidden = Framebuffer::new(10, 10);
+    draw_styled_text(&mut hidden, 0, 0, "|", &TextStyle::new(0xFFFFFF).with_alpha(0.0));
+    assert!(lit_pixels(&hidden).is_empty());
+}
+
+#[test]
+fn size_scales_each_glyph_pixel() {
+    let mut small = Framebuffer::new(40, 40);
+    let mut large = Framebuffer::new(40, 40);
+    draw_text(&mut small, 0, 0, "A", 0xFFFFFF);
+    draw_styled_text(&mut large, 0, 0, "A", &TextStyle::new(0xFFFFFF).with_size(3));
+
+    let small_pixels = lit_pixels(&small);
+    assert_eq!(lit_pixels(&large).len(), small_pixels.len() * 9);
+    for (x, y, _) in small_pixels {
+        assert_eq!(large.buffer[(y * 3 + 2) * large.width + x * 3 + 2], 0xFFFFFF);
+    }
+}
+
+#[test]
+fn text_size_accounts_for_lines_and_scale() {
+    assert_eq!(text_size("abc", 1), (3 * GLYPH_WIDTH + 2, GLYPH_HEIGHT));
+    let expected = ((4 * GLYPH_WIDTH + 3) * 2, (LINE_HEIGHT + GLYPH_HEIGHT) * 2);
+    assert_eq!(text_size("ab\nabcd", 2), expected);
+}
+
+#[test]
+fn text_is_clipped_at_the_edges() {
+    let mut framebuffer = Framebuffer::new(8, 8);
+    let style = TextStyle::new(0xFFFFFF).with_size(4);
+    draw_styled_text(&mut framebuffer, -10, -6, "Sistema\nSolar", &style);
+    draw_styled_text(&mut framebuffer, 5, 5, "¿Órbita?", &style);
+    assert!(!lit_pixels(&framebuffer).is_empty());
+}
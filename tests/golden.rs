@@ -0,0 +1,402 @@
+// Pruebas de regresión con imágenes de referencia ("golden images").
+//
+// Cada escena se renderiza sin ventana a baja resolución y se compara píxel por píxel
+// contra `tests/golden/<nombre>.png`. Si algo cambia, se escriben la imagen obtenida y
+// un mapa de diferencias en `target/golden/`. Para regenerar las referencias después
+// de un cambio intencional:
+//
+//     UPDATE_GOLDEN=1 cargo test golden
+
+use image::{Rgb, RgbImage};
+use nalgebra_glm::{quat_angle_axis, Mat4, Vec2, Vec3};
+use std::fs;
+use std::path::PathBuf;
+use space_traver::antialias::AntiAliasing;
+use space_traver::camera::Camera;
+use space_traver::chase_camera::ChaseCamera;
+use space_traver::color::{BlendMode, Color};
+use space_traver::debug::DebugView;
+use space_traver::font::{draw_styled_text, TextStyle};
+use space_traver::fragment::Fragment;
+use space_traver::framebuffer::Framebuffer;
+use space_traver::obj::Obj;
+use space_traver::pipeline::{create_model_matrix, create_uniforms, render};
+use space_traver::planet::{Biome, PlanetGenerator};
+use space_traver::scene::{Scene, DISTANCE_SCALE};
+use space_traver::shaders::{
+    combined_blend_shader, combined_shader, earth_shader, jupiter_shader, mars_shader,
+    moon_shader, rocky_planet_shader, saturn_rings_shader, saturn_shader, spaceship_shader,
+    sun_shader, venus_shader,
+};
+use space_traver::tonemap::ToneMapping;
+use space_traver::vertex::Vertex;
+use space_traver::Uniforms;
+
+const WIDTH: usize = 96;
+const HEIGHT: usize = 72;
+const TIME: u32 = 240;
+
+// Diferencia máxima por canal para considerar dos píxeles iguales
+const CHANNEL_TOLERANCE: u8 = 4;
+// Fracción de píxeles que puede exceder la tolerancia (bordes de rasterización)
+const MAX_MISMATCH_RATIO: f32 = 0.002;
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden")
+}
+
+fn output_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target").join("golden")
+}
+
+fn to_image(framebuffer: &Framebuffer) -> RgbImage {
+    RgbImage::from_fn(framebuffer.width as u32, framebuffer.height as u32, |x, y| {
+        let pixel = framebuffer.buffer[y as usize * framebuffer.width + x as usize];
+        Rgb([(pixel >> 16) as u8, (pixel >> 8) as u8, pixel as u8])
+    })
+}
+
+fn assert_golden(name: &str, framebuffer: &Framebuffer) {
+    let actual = to_image(framebuffer);
+    let reference_path = golden_dir().join(format!("{}.png", name));
+
+    if std::env::var("UPDATE_GOLDEN").is_ok() {
+        fs::create_dir_all(golden_dir()).unwrap();
+        actual.save(&reference_path).unwrap();
+        return;
+    }
+
+    fs::create_dir_all(output_dir()).unwrap();
+    let actual_path = output_dir().join(format!("{}_actual.png", name));
+
+    let reference = match image::open(&reference_path) {
+        Ok(reference) => reference.to_rgb8(),
+        Err(err) => {
+            actual.save(&actual_path).unwrap();
+            panic!(
+                "Missing reference {} ({}); run with UPDATE_GOLDEN=1 to create it",
+                reference_path.display(),
+                err
+            );
+        }
+    };
+    assert_eq!(
+        reference.dimensions(),
+        actual.dimensions(),
+        "{}: reference image has a different size",
+        name
+    );
+
+    // Mapa de diferencias: rojo donde se excede la tolerancia, la imagen atenuada en el resto
+    let mut diff = RgbImage::new(actual.width(), actual.height());
+    let mut mismatches = 0;
+    for (x, y, expected) in reference.enumerate_pixels() {
+        let got = actual.get_pixel(x, y);
+        let exceeds = expected
+            .0
+            .iter()
+            .zip(got.0.iter())
+            .any(|(a, b)| a.abs_diff(*b) > CHANNEL_TOLERANCE);
+        if exceeds {
+            mismatches += 1;
+            diff.put_pixel(x, y, Rgb([255, 0, 0]));
+        } else {
+            diff.put_pixel(x, y, Rgb(got.0.map(|channel| channel / 4)));
+        }
+    }
+
+    let allowed = (MAX_MISMATCH_RATIO * (actual.width() * actual.height()) as f32) as u32;
+    if mismatches > allowed {
+        let diff_path = output_dir().join(format!("{}_diff.png", name));
+        actual.save(&actual_path).unwrap();
+        diff.save(&diff_path).unwrap();
+        panic!(
+            "{}: {} pixels differ from {} (allowed {}); see {} and {}",
+            name,
+            mismatches,
+            reference_path.display(),
+            allowed,
+            actual_path.display(),
+            diff_path.display()
+        );
+    }
+}
+
+fn new_framebuffer() -> Framebuffer {
+    let mut framebuffer = Framebuffer::new(WIDTH, HEIGHT);
+    framebuffer.set_background_color(0x000000);
+    framebuffer.clear();
+    framebuffer
+}
+
+fn check_sphere(name: &str, shader: impl Fn(&Fragment, &Uniforms) -> Color) {
+    let vertex_array = Obj::load("assets/models/planet.obj")
+        .expect("Failed to load planet")
+        .get_vertex_array();
+    // planet.obj no está centrado en el origen: su centro está cerca de (0, 5.3, -0.6)
+    let camera = Camera::new(
+        Vec3::new(0.0, 5.3, 3.0),
+        Vec3::new(0.0, 5.3, -0.6),
+        Vec3::new(0.0, 1.0, 0.0),
+    );
+
+    let mut framebuffer = new_framebuffer();
+    let model_matrix = create_model_matrix(
+        Vec3::new(0.0, 0.0, 0.0),
+        1.0,
+        Vec3::new(0.0, 0.0, 0.0),
+    );
+    let uniforms = create_uniforms(&framebuffer, &camera, model_matrix, TIME);
+    render(&mut framebuffer, &uniforms, &vertex_array, shader, &mut DebugView::new());
+    framebuffer.present(&ToneMapping::new());
+
+    assert_golden(&format!("sphere_{}", name), &framebuffer);
+}
+
+#[test]
+fn golden_sphere_combined_shader() {
+    check_sphere("combined", combined_shader);
+}
+
+/// Cuadrado de lado 1 centrado en el origen del modelo, de frente a la cámara: a
+/// diferencia de `planet.obj`, contiene el círculo de `combined_blend_shader`.
+fn render_quad(shader: impl Fn(&Fragment, &Uniforms) -> Color) -> Framebuffer {
+    let corner = |x: f32, y: f32| {
+        Vertex::new(Vec3::new(x, y, 0.0), Vec3::new(0.0, 0.0, 1.0), Vec2::zeros())
+    };
+    let vertex_array = vec![
+        corner(-0.5, -0.5),
+        corner(0.5, -0.5),
+        corner(0.5, 0.5),
+        corner(-0.5, -0.5),
+        corner(0.5, 0.5),
+        corner(-0.5, 0.5),
+    ];
+    let camera = Camera::new(
+        Vec3::new(0.0, 0.0, 1.5),
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    );
+
+    let mut framebuffer = new_framebuffer();
+    let uniforms = create_uniforms(&framebuffer, &camera, Mat4::identity(), TIME);
+    render(&mut framebuffer, &uniforms, &vertex_array, shader, &mut DebugView::new());
+    framebuffer.present(&ToneMapping::new());
+    framebuffer
+}
+
+#[test]
+fn golden_quad_combined_blend_shader() {
+    let modes = ["normal", "multiply", "add", "subtract"];
+    let framebuffers: Vec<Framebuffer> = modes
+        .iter()
+        .map(|&mode| render_quad(|fragment, _| combined_blend_shader(fragment, mode)))
+        .collect();
+
+    // Cada modo deja el círculo de otro color
+    for (i, first) in framebuffers.iter().enumerate() {
+        for second in &framebuffers[i + 1..] {
+            assert!(first.buffer != second.buffer, "{} igual a otro modo", modes[i]);
+        }
+    }
+    for (mode, framebuffer) in modes.iter().zip(&framebuffers) {
+        assert_golden(&format!("quad_blend_{}", mode), framebuffer);
+    }
+}
+
+#[test]
+fn golden_sphere_sun_shader() {
+    check_sphere("sun", sun_shader);
+}
+
+#[test]
+fn golden_sphere_rocky_planet_shader() {
+    check_sphere("rocky_planet", rocky_planet_shader);
+}
+
+#[test]
+fn golden_sphere_venus_shader() {
+    check_sphere("venus", venus_shader);
+}
+
+#[test]
+fn golden_sphere_earth_shader() {
+    check_sphere("earth", earth_shader);
+}
+
+#[test]
+fn golden_sphere_mars_shader() {
+    check_sphere("mars", mars_shader);
+}
+
+#[test]
+fn golden_sphere_jupiter_shader() {
+    check_sphere("jupiter", jupiter_shader);
+}
+
+#[test]
+fn golden_sphere_moon_shader() {
+    check_sphere("moon", moon_shader);
+}
+
+#[test]
+fn golden_sphere_saturn_shader() {
+    check_sphere("saturn", saturn_shader);
+}
+
+#[test]
+fn golden_generated_planets() {
+    for biome in Biome::all() {
+        let planet = PlanetGenerator::new(5).with_biome(biome).with_craters(0.5);
+        let name = format!("planet_{:?}", biome).to_lowercase();
+        check_sphere(&name, |fragment, uniforms| planet.shade(fragment, uniforms));
+    }
+}
+
+#[test]
+fn golden_saturn_with_rings() {
+    let load = |path: &str| Obj::load(path).expect("Failed to load model").get_vertex_array();
+    let (planet, rings) = (load("assets/models/planet.obj"), load("assets/models/rings.obj"));
+    // Desde arriba del plano de los anillos, para ver el anillo delante y detrás de Saturno
+    let camera = Camera::new(
+        Vec3::new(0.0, 7.5, 5.6),
+        Vec3::new(0.0, 5.3, -0.6),
+        Vec3::new(0.0, 1.0, 0.0),
+    );
+
+    let mut framebuffer = new_framebuffer();
+    let model_matrix = create_model_matrix(
+        Vec3::new(0.0, 0.0, 0.0),
+        1.0,
+        Vec3::new(0.0, 0.0, 0.0),
+    );
+    let uniforms = create_uniforms(&framebuffer, &camera, model_matrix, TIME);
+    let mut debug = DebugView::new();
+    render(&mut framebuffer, &uniforms, &planet, saturn_shader, &mut debug);
+    framebuffer.set_blend_mode(BlendMode::Normal);
+    render(&mut framebuffer, &uniforms, &rings, saturn_rings_shader, &mut debug);
+    framebuffer.present(&ToneMapping::new());
+
+    assert_golden("saturn_rings", &framebuffer);
+}
+
+#[test]
+fn golden_atmospheres() {
+    let mut scene = Scene::load(DISTANCE_SCALE);
+    scene.show_orbits = false;
+    scene.show_trails = false;
+    scene.show_trajectory = false;
+    // Desde el lado del Sol y un poco de costado, para ver el limbo iluminado
+    for (name, index) in [("earth", 3), ("venus", 2)] {
+        let bounds = scene.body_bounds(index, TIME);
+        let toward_sun = -bounds.center.normalize();
+        let side = toward_sun.cross(&Vec3::new(0.0, 1.0, 0.0));
+        let eye = bounds.center + (toward_sun * 2.0 + side * 0.6) * bounds.radius * 1.4;
+        let camera = Camera::new(eye, bounds.center, Vec3::new(0.0, 1.0, 0.0));
+
+        let mut framebuffer = new_framebuffer();
+        scene.render(&mut framebuffer, &camera, TIME);
+        assert_golden(&format!("atmosphere_{}", name), &framebuffer);
+    }
+}
+
+#[test]
+fn golden_sphere_spaceship_shader() {
+    check_sphere("spaceship", spaceship_shader);
+}
+
+#[test]
+fn golden_solar_system() {
+    let mut scene = Scene::load(DISTANCE_SCALE);
+    scene.seed_trails(TIME);
+    let camera = Camera::new(
+        Vec3::new(0.0, 30.0, 45.0),
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    );
+
+    let mut framebuffer = new_framebuffer();
+    scene.render(&mut framebuffer, &camera, TIME);
+
+    assert_golden("solar_system", &framebuffer);
+}
+
+#[test]
+fn golden_solar_system_anti_aliasing() {
+    let mut scene = Scene::load(DISTANCE_SCALE);
+    scene.seed_trails(TIME);
+    let camera = Camera::new(
+        Vec3::new(0.0, 30.0, 45.0),
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    );
+
+    for (mode, name) in [
+        (AntiAliasing::Msaa, "msaa"),
+        (AntiAliasing::Ssaa, "ssaa"),
+        (AntiAliasing::Fxaa, "fxaa"),
+    ] {
+        scene.anti_aliasing = mode;
+        let mut framebuffer = new_framebuffer();
+        scene.render(&mut framebuffer, &camera, TIME);
+        assert_golden(&format!("solar_system_{}", name), &framebuffer);
+    }
+}
+
+#[test]
+fn golden_ship_chase_view() {
+    let mut scene = Scene::load(DISTANCE_SCALE);
+    // Nave girada para comprobar que el modelo sigue su orientación
+    scene.ship.orientation = quat_angle_axis(0.6, &Vec3::new(0.0, 1.0, 0.0))
+        * quat_angle_axis(0.4, &Vec3::new(0.0, 0.0, 1.0));
+    let mut camera = Camera::new(
+        Vec3::new(0.0, 0.0, 10.0),
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    );
+    ChaseCamera::new().snap(&mut camera, &scene.ship);
+
+    let mut framebuffer = new_framebuffer();
+    scene.render_ship(&mut framebuffer, &camera, TIME);
+    framebuffer.present(&scene.tone_mapping);
+
+    assert_golden("ship_chase", &framebuffer);
+}
+
+#[test]
+fn golden_skybox_only() {
+    let scene = Scene::load(DISTANCE_SCALE);
+    let camera = Camera::new(
+        Vec3::new(0.0, 0.0, 10.0),
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    );
+
+    let mut framebuffer = new_framebuffer();
+    scene.render_skybox(&mut framebuffer, &camera, TIME);
+    framebuffer.present(&scene.tone_mapping);
+
+    assert_golden("skybox", &framebuffer);
+}
+
+#[test]
+fn golden_text_styles() {
+    // Texto sobre el skybox para ver la mezcla con un fondo que no es uniforme
+    let scene = Scene::load(DISTANCE_SCALE);
+    let camera = Camera::new(
+        Vec3::new(0.0, 0.0, 10.0),
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    );
+    let mut framebuffer = new_framebuffer();
+    scene.render_skybox(&mut framebuffer, &camera, TIME);
+    framebuffer.present(&scene.tone_mapping);
+
+    draw_styled_text(&mut framebuffer, 2, 2, "Sol 1.0", &TextStyle::new(0xFFD27F));
+    let half = TextStyle::new(0x7FC8FF).with_alpha(0.5).with_size(2);
+    draw_styled_text(&mut framebuffer, 2, 14, "HUD", &half);
+    let large = TextStyle::new(0xFFFFFF).with_alpha(0.8).with_size(3);
+    draw_styled_text(&mut framebuffer, 2, 34, "Ñá", &large);
+
+    assert_golden("text_styles", &framebuffer);
+}
@@ -0,0 +1,93 @@
+// Pruebas de la gravedad sobre la nave y de su trayectoria predicha.
+
+use nalgebra_glm::Vec3;
+use space_traver::gravity::{acceleration, circular_velocity, predict_trajectory};
+use space_traver::scene::{Scene, DISTANCE_SCALE};
+use space_traver::ship::ShipInput;
+
+const DT: f32 = 1.0 / 60.0;
+
+#[test]
+fn gravity_pulls_toward_the_body_with_inverse_square() {
+    let near = acceleration(4.0, Vec3::new(2.0, 0.0, 0.0));
+    let far = acceleration(4.0, Vec3::new(4.0, 0.0, 0.0));
+    assert!((near - Vec3::new(-1.0, 0.0, 0.0)).magnitude() < 1e-5);
+    assert!((near.magnitude() / far.magnitude() - 4.0).abs() < 1e-4);
+}
+
+#[test]
+fn circular_velocity_keeps_the_radius() {
+    let mu = 2.0;
+    let offset = Vec3::new(3.0, 0.0, 0.0);
+    // Velocidad inicial casi radial: se orbita igual, en el plano horizontal
+    let velocity = circular_velocity(mu, offset, Vec3::new(-0.5, 0.0, 0.1));
+    assert!(velocity.dot(&offset).abs() < 1e-5);
+    assert!(velocity.z > 0.0);
+
+    // Más de una vuelta completa
+    let points = predict_trajectory(offset, velocity, mu, 0.5, 0.01, 4000);
+    assert_eq!(points.len(), 4001);
+    for point in points {
+        assert!((point.magnitude() - 3.0).abs() < 0.05, "radio {}", point.magnitude());
+    }
+}
+
+#[test]
+fn trajectory_stops_at_the_surface() {
+    let start = Vec3::new(0.0, 5.0, 0.0);
+    let points = predict_trajectory(start, Vec3::zeros(), 10.0, 1.0, 0.05, 1000);
+    assert!(points.len() < 1001);
+    assert!(points.last().unwrap().magnitude() <= 1.0);
+}
+
+#[test]
+fn ship_falls_toward_the_sun() {
+    let mut scene = Scene::load(DISTANCE_SCALE);
+    let start = scene.ship.position;
+    for time in 1..=60 {
+        scene.fly_ship(&ShipInput::default(), time, DT);
+    }
+    assert_eq!(scene.ship_frame(), Some(0));
+    let sun = scene.body_bounds(0, 60).center;
+    assert!((scene.ship.position - sun).magnitude() < (start - sun).magnitude());
+}
+
+#[test]
+fn ship_travels_with_the_planet_it_orbits() {
+    let mut scene = Scene::load(DISTANCE_SCALE);
+    let earth = 3;
+    let offset = Vec3::new(1.5, 0.0, 0.0);
+    scene.ship.position = scene.body_bounds(earth, 0).center + offset;
+    assert_eq!(scene.dominant_body(scene.ship.position, 0), earth);
+
+    // Insertar la nave en órbita circular con el propio control
+    let circularize = ShipInput {
+        circularize: true,
+        ..ShipInput::default()
+    };
+    let mut time = 0;
+    for _ in 0..120 {
+        time += 1;
+        scene.fly_ship(&circularize, time, DT);
+    }
+    let mu = scene.bodies[earth].gravity;
+    let radius = (scene.ship.position - scene.body_bounds(earth, time).center).magnitude();
+    assert!((scene.ship.speed() - (mu / radius).sqrt()).abs() < 1e-3);
+
+    // Luego se deriva sin motores: la distancia a la Tierra se conserva
+    for _ in 0..600 {
+        time += 1;
+        scene.fly_ship(&ShipInput::default(), time, DT);
+        let distance = (scene.ship.position - scene.body_bounds(earth, time).center).magnitude();
+        assert!((distance - radius).abs() < 0.05, "distancia {}", distance);
+    }
+    assert_eq!(scene.ship_frame(), Some(earth));
+}
+
+#[test]
+fn predicted_trajectory_starts_at_the_ship() {
+    let scene = Scene::load(DISTANCE_SCALE);
+    let points = scene.predicted_trajectory(0);
+    assert_eq!(points[0], scene.ship.position);
+    assert!(points.len() > 1);
+}
@@ -0,0 +1,127 @@
+// Pruebas del HUD: proyección a pantalla, fecha simulada, rumbo y etiquetas.
+
+use nalgebra_glm::{Mat4, Vec3};
+use space_traver::camera::Camera;
+use space_traver::framebuffer::Framebuffer;
+use space_traver::hud::{heading, simulation_date, Hud, DAYS_PER_TICK};
+use space_traver::pipeline::{create_uniforms, project_to_screen};
+use space_traver::scene::{Scene, DISTANCE_SCALE};
+
+fn camera() -> Camera {
+    Camera::new(
+        Vec3::new(0.0, 0.0, 20.0),
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    )
+}
+
+#[test]
+fn projection_matches_the_viewport() {
+    let framebuffer = Framebuffer::new(200, 100);
+    let uniforms = create_uniforms(&framebuffer, &camera(), Mat4::identity(), 0);
+
+    let center = project_to_screen(&uniforms, Vec3::new(0.0, 0.0, 0.0)).unwrap();
+    assert!((center.x - 100.0).abs() < 1e-3 && (center.y - 50.0).abs() < 1e-3);
+    // La `y` de pantalla crece hacia abajo
+    let above = project_to_screen(&uniforms, Vec3::new(1.0, 1.0, 0.0)).unwrap();
+    assert!(above.x > center.x && above.y < center.y);
+    assert!(project_to_screen(&uniforms, Vec3::new(0.0, 0.0, 30.0)).is_none());
+}
+
+#[test]
+fn simulation_date_follows_the_calendar() {
+    let ticks = |days: f32| (days / DAYS_PER_TICK).ceil() as u32;
+    assert_eq!(simulation_date(0), (2000, 1, 1));
+    assert_eq!(simulation_date(ticks(31.0)), (2000, 2, 1));
+    // 2000 es bisiesto
+    assert_eq!(simulation_date(ticks(59.0)), (2000, 2, 29));
+    assert_eq!(simulation_date(ticks(366.0)), (2001, 1, 1));
+}
+
+#[test]
+fn heading_is_measured_from_the_sun_direction() {
+    let (azimuth, elevation) = heading(Vec3::new(0.0, 0.0, -1.0));
+    assert!(azimuth.abs() < 1e-3 && elevation.abs() < 1e-3);
+    let (azimuth, _) = heading(Vec3::new(1.0, 0.0, 0.0));
+    assert!((azimuth - 90.0).abs() < 1e-3);
+    let (azimuth, _) = heading(Vec3::new(-1.0, 0.0, 0.0));
+    assert!((azimuth - 270.0).abs() < 1e-3);
+    let (_, elevation) = heading(Vec3::new(0.0, 1.0, 1.0).normalize());
+    assert!((elevation - 45.0).abs() < 1e-3);
+}
+
+#[test]
+fn time_scale_uses_the_measured_tick_length() {
+    let mut hud = Hud::new();
+    for _ in 0..200 {
+        hud.update(1.0 / 30.0);
+    }
+    assert!((hud.time_scale() - DAYS_PER_TICK * 30.0).abs() < 0.01);
+}
+
+#[test]
+fn visible_bodies_get_a_label_next_to_them() {
+    let scene = Scene::load(DISTANCE_SCALE);
+    let camera = camera();
+    let mut framebuffer = Framebuffer::new(400, 300);
+    let mut hud = Hud::new();
+    hud.show_telemetry = false;
+    hud.draw(&mut framebuffer, &scene, &camera, 0, None);
+
+    let uniforms = create_uniforms(&framebuffer, &camera, Mat4::identity(), 0);
+    let sun = project_to_screen(&uniforms, scene.body_bounds(0, 0).center).unwrap();
+    let labelled = framebuffer.buffer.iter().enumerate().any(|(i, pixel)| {
+        let (x, y) = ((i % 400) as f32, (i / 400) as f32);
+        // Texto blanco semitransparente sobre el fondo negro
+        *pixel & 0xFF > 0x80 && x > sun.x && (y - sun.y).abs() < 20.0
+    });
+    assert!(labelled);
+}
+
+#[test]
+fn body_info_shows_real_and_simulated_data() {
+    let scene = Scene::load(DISTANCE_SCALE);
+    let hud = Hud::new();
+    let earth = 3;
+    let data = scene.bodies[earth].data.unwrap();
+    assert_eq!(data.moons, 1);
+    assert!(data.mass > 5.9e24 && data.mass < 6.0e24);
+
+    let sections = hud.body_info(&scene, earth, 0);
+    assert_eq!(sections.len(), 2);
+    let (real, simulated) = (&sections[0].1, &sections[1].1);
+    assert!(real.contains(&String::from("Radio: 6371 km")));
+    assert!(real.contains(&String::from("Lunas: 1")));
+    assert!(simulated.contains(&String::from("Centro de la órbita: Sol")));
+    // Una vuelta de la Tierra dura un año simulado
+    assert!(simulated.contains(&String::from("Período: 365.2 días")));
+
+    let venus = &hud.body_info(&scene, 2, 0)[0].1;
+    assert!(venus.iter().any(|line| line.ends_with("(retrógrada)")));
+    let moon = &hud.body_info(&scene, 4, 0)[1].1;
+    assert!(moon.contains(&String::from("Centro de la órbita: Tierra")));
+}
+
+#[test]
+fn info_panel_appears_only_for_the_selected_body() {
+    let scene = Scene::load(DISTANCE_SCALE);
+    let camera = Camera::new(
+        Vec3::new(0.0, 200.0, 0.0),
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, -1.0),
+    );
+    let mut hud = Hud::new();
+    hud.show_labels = false;
+    hud.show_telemetry = false;
+    // Esquina superior izquierda, donde va el panel
+    let corner_lit = |highlight: Option<usize>, hud: &Hud| {
+        let mut framebuffer = Framebuffer::new(400, 300);
+        hud.draw(&mut framebuffer, &scene, &camera, 0, highlight);
+        (0..60).any(|y| (0..150).any(|x| framebuffer.buffer[y * 400 + x] != 0))
+    };
+
+    assert!(!corner_lit(None, &hud));
+    assert!(corner_lit(Some(3), &hud));
+    hud.show_info = false;
+    assert!(!corner_lit(Some(3), &hud));
+}
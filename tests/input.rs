@@ -0,0 +1,77 @@
+// Pruebas del archivo de controles y la asignación de teclas.
+
+use minifb::Key;
+use std::fs;
+use std::path::PathBuf;
+use space_traver::input::{key_name, parse_key, Action, Bindings, Controls};
+
+#[test]
+fn example_file_matches_the_defaults() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("controls.example.cfg");
+    let text = fs::read_to_string(path).unwrap();
+    let controls = Controls::parse(&text).unwrap();
+
+    assert_eq!(controls.bindings, Bindings::default());
+    for action in Action::all() {
+        assert!(
+            text.contains(&format!("\n{} =", action.name())),
+            "falta {} en controls.example.cfg",
+            action.name()
+        );
+    }
+}
+
+#[test]
+fn file_overrides_only_the_listed_actions() {
+    let controls = Controls::parse(
+        "# comentario\n\
+         ZoomIn = Equal, NumPadPlus   # dos teclas\n\
+         quit =\n\
+         focusbody3 = F\n",
+    )
+    .unwrap();
+    let bindings = &controls.bindings;
+
+    assert_eq!(bindings.keys(Action::ZoomIn), &[Key::Equal, Key::NumPadPlus]);
+    assert!(bindings.keys(Action::Quit).is_empty());
+    assert_eq!(bindings.keys(Action::FocusBody(2)), &[Key::F]);
+    assert_eq!(bindings.keys(Action::ZoomOut), &[Key::Down]);
+}
+
+#[test]
+fn mouse_settings_are_read_from_the_file() {
+    let controls = Controls::parse("mouse.invert_y = true\nmouse.zoom_sensitivity = 0.25").unwrap();
+
+    assert!(controls.mouse.invert_y);
+    assert_eq!(controls.mouse.zoom_sensitivity, 0.25);
+}
+
+#[test]
+fn errors_report_the_line() {
+    let unknown_key = Controls::parse("ZoomIn = Up\nZoomOut = Nope").err().unwrap();
+    assert!(unknown_key.contains("line 2") && unknown_key.contains("Nope"), "{}", unknown_key);
+
+    let unknown_action = Controls::parse("Teleport = T").err().unwrap();
+    assert!(unknown_action.contains("line 1") && unknown_action.contains("Teleport"));
+
+    let missing_equals = Controls::parse("\n\nZoomIn Up").err().unwrap();
+    assert!(missing_equals.contains("line 3"), "{}", missing_equals);
+
+    assert!(Controls::parse("mouse.invert_y = maybe").is_err());
+}
+
+#[test]
+fn key_names_round_trip() {
+    for key in [Key::Key0, Key::A, Key::F12, Key::LeftShift, Key::NumPadPlus] {
+        assert_eq!(parse_key(key_name(key)), Some(key));
+    }
+    assert_eq!(parse_key("space"), Some(Key::Space));
+    assert_eq!(key_name(Key::Key7), "7");
+}
+
+#[test]
+fn action_names_round_trip() {
+    for action in Action::all() {
+        assert_eq!(Action::from_name(&action.name()), Some(action));
+    }
+}
@@ -0,0 +1,92 @@
+// Pruebas del minimapa: selección por clic y área de dibujo.
+
+use nalgebra_glm::Vec3;
+use space_traver::framebuffer::Framebuffer;
+use space_traver::minimap::Minimap;
+use space_traver::pipeline::{create_top_down_uniforms, project_to_screen};
+use space_traver::scene::{Scene, DISTANCE_SCALE};
+
+const SIZE: usize = 120;
+
+/// Punto de la pantalla donde el minimapa dibuja el centro del cuerpo `index`.
+fn screen_point(
+    minimap: &Minimap,
+    screen: &Framebuffer,
+    scene: &Scene,
+    index: usize,
+) -> (f32, f32) {
+    let local = Framebuffer::new(minimap.size(), minimap.size());
+    let uniforms = create_top_down_uniforms(&local, minimap.extent, 0);
+    let center = project_to_screen(&uniforms, scene.body_bounds(index, 0).center).unwrap();
+    let (left, top) = minimap.origin(screen);
+    (left as f32 + center.x, top as f32 + center.y)
+}
+
+#[test]
+fn every_orbit_fits_in_the_map() {
+    let scene = Scene::load(DISTANCE_SCALE);
+    let minimap = Minimap::new(SIZE, &scene);
+    let screen = Framebuffer::new(400, 300);
+    for index in 0..scene.bodies.len() {
+        let (x, y) = screen_point(&minimap, &screen, &scene, index);
+        assert!(minimap.contains(&screen, x, y), "cuerpo {index} fuera del minimapa");
+    }
+}
+
+#[test]
+fn clicking_a_body_selects_it() {
+    let scene = Scene::load(DISTANCE_SCALE);
+    let minimap = Minimap::new(SIZE, &scene);
+    let screen = Framebuffer::new(400, 300);
+
+    let (x, y) = screen_point(&minimap, &screen, &scene, 0);
+    assert_eq!(minimap.pick(&screen, &scene, 0, x, y), Some(0));
+    // Júpiter está lejos de todo lo demás en el mapa
+    let jupiter = 6;
+    let (x, y) = screen_point(&minimap, &screen, &scene, jupiter);
+    assert_eq!(minimap.pick(&screen, &scene, 0, x + 1.0, y), Some(jupiter));
+}
+
+#[test]
+fn clicks_outside_or_while_hidden_select_nothing() {
+    let scene = Scene::load(DISTANCE_SCALE);
+    let mut minimap = Minimap::new(SIZE, &scene);
+    let screen = Framebuffer::new(400, 300);
+
+    let (left, top) = minimap.origin(&screen);
+    assert_eq!(minimap.pick(&screen, &scene, 0, left as f32 - 5.0, top as f32 + 5.0), None);
+    // Una esquina vacía del recuadro
+    assert_eq!(minimap.pick(&screen, &scene, 0, left as f32 + 2.0, top as f32 + 2.0), None);
+
+    let (x, y) = screen_point(&minimap, &screen, &scene, 0);
+    minimap.show = false;
+    assert_eq!(minimap.pick(&screen, &scene, 0, x, y), None);
+}
+
+#[test]
+fn drawing_stays_inside_the_corner() {
+    let mut scene = Scene::load(DISTANCE_SCALE);
+    scene.ship.velocity = Vec3::new(0.5, 0.0, 0.0);
+    let mut minimap = Minimap::new(SIZE, &scene);
+    let mut screen = Framebuffer::new(400, 300);
+    let camera = scene.ship.position + Vec3::new(3.0, 0.0, 0.0);
+    minimap.draw(&mut screen, &scene, camera, 0, Some(3));
+
+    let (left, top) = minimap.origin(&screen);
+    assert_eq!((left, top), (400 - SIZE - minimap.margin, minimap.margin));
+    for y in 0..screen.height {
+        for x in 0..screen.width {
+            let inside = x >= left && x < left + SIZE && y >= top && y < top + SIZE;
+            if !inside {
+                assert_eq!(screen.buffer[y * screen.width + x], 0, "píxel ({x}, {y})");
+            }
+        }
+    }
+    let touched = screen.buffer.iter().filter(|&&pixel| pixel != 0).count();
+    assert!(touched >= SIZE * SIZE / 2);
+
+    let mut hidden = Framebuffer::new(400, 300);
+    minimap.show = false;
+    minimap.draw(&mut hidden, &scene, camera, 0, None);
+    assert!(hidden.buffer.iter().all(|&pixel| pixel == 0));
+}
@@ -0,0 +1,94 @@
+// Pruebas del ruido coherente: semillas, rangos y continuidad.
+
+use nalgebra_glm::Vec3;
+use space_traver::noise::Noise;
+
+/// Puntos repartidos sin patrón en un cubo de lado 20.
+fn samples() -> impl Iterator<Item = Vec3> {
+    (0..2000).map(|i| {
+        let t = i as f32;
+        Vec3::new(
+            (t * 0.618_034).fract() * 20.0 - 10.0,
+            (t * 0.754_878).fract() * 20.0 - 10.0,
+            (t * 0.569_840).fract() * 20.0 - 10.0,
+        )
+    })
+}
+
+#[test]
+fn the_same_seed_gives_the_same_noise() {
+    let (a, b, other) = (Noise::new(7), Noise::new(7), Noise::new(8));
+    let point = Vec3::new(1.3, -2.7, 0.4);
+
+    assert_eq!(a.perlin(point), b.perlin(point));
+    assert_eq!(a.simplex(point), b.simplex(point));
+    assert_eq!(a.worley(point), b.worley(point));
+    assert!(samples().any(|point| a.perlin(point) != other.perlin(point)));
+}
+
+#[test]
+fn perlin_is_zero_on_the_lattice() {
+    let noise = Noise::new(1);
+    for point in [Vec3::new(0.0, 0.0, 0.0), Vec3::new(3.0, -4.0, 17.0)] {
+        assert_eq!(noise.perlin(point), 0.0);
+    }
+}
+
+#[test]
+fn noise_stays_in_range() {
+    let noise = Noise::new(42);
+    for point in samples() {
+        for value in [noise.perlin(point), noise.simplex(point), noise.fbm(point, 5)] {
+            assert!((-1.0..=1.0).contains(&value), "{} en {:?}", value, point);
+        }
+        let ridged = noise.ridged(point, 5);
+        assert!((0.0..=1.0).contains(&ridged), "{} en {:?}", ridged, point);
+    }
+
+    // Y no es constante
+    let values: Vec<f32> = samples().map(|point| noise.simplex(point)).collect();
+    let spread = values.iter().cloned().fold(f32::MIN, f32::max)
+        - values.iter().cloned().fold(f32::MAX, f32::min);
+    assert!(spread > 1.0, "rango {}", spread);
+}
+
+#[test]
+fn noise_is_continuous() {
+    let noise = Noise::new(5);
+    let step = Vec3::new(0.001, 0.001, 0.001);
+    for point in samples() {
+        assert!((noise.perlin(point) - noise.perlin(point + step)).abs() < 0.02);
+        assert!((noise.simplex(point) - noise.simplex(point + step)).abs() < 0.05);
+    }
+}
+
+#[test]
+fn fbm_with_one_octave_is_perlin() {
+    let noise = Noise::new(9);
+    for point in samples().take(50) {
+        assert_eq!(noise.fbm(point, 1), noise.perlin(point));
+    }
+    assert_eq!(noise.fbm(Vec3::new(0.5, 0.5, 0.5), 0), 0.0);
+}
+
+#[test]
+fn worley_orders_the_nearest_cells() {
+    let noise = Noise::new(11);
+    for point in samples() {
+        let cells = noise.worley(point);
+        assert!(cells.nearest <= cells.second);
+        // Siempre hay un punto en la propia celda, a menos de la diagonal del cubo
+        assert!(cells.nearest <= 3f32.sqrt());
+    }
+}
+
+#[test]
+fn warp_without_amount_leaves_the_point() {
+    let noise = Noise::new(13);
+    let point = Vec3::new(0.3, 0.2, -0.9);
+
+    assert_eq!(noise.warp(point, 0.0), point);
+    let warped = noise.warp(point, 0.5);
+    assert!(warped != point);
+    assert!((warped - point).magnitude() <= 0.5 * 3f32.sqrt());
+}
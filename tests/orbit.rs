@@ -0,0 +1,25 @@
+// Pruebas de las órbitas predichas de los cuerpos.
+
+use space_traver::body::{body_position, solar_system};
+use space_traver::orbit::predicted_orbit;
+use space_traver::scene::DISTANCE_SCALE;
+
+#[test]
+fn the_moon_orbit_closes_around_the_current_earth() {
+    let bodies = solar_system(DISTANCE_SCALE);
+    let earth = bodies.iter().position(|body| body.name == "Tierra").unwrap();
+    let moon = bodies.iter().position(|body| body.name == "Luna").unwrap();
+    let time = 240.0;
+
+    let points = predicted_orbit(&bodies, moon, time);
+    let (first, last) = (points[0], points[points.len() - 1]);
+    assert!((first - last).magnitude() < 1e-2, "{:?} vs {:?}", first, last);
+    assert!((first - body_position(&bodies, moon, time)).magnitude() < 1e-3);
+
+    // Todos los puntos a la misma distancia de la Tierra de ahora
+    let center = body_position(&bodies, earth, time) + bodies[moon].offset;
+    for point in &points {
+        let radius = (point - center).magnitude();
+        assert!((radius - bodies[moon].orbit_radius).abs() < 1e-3, "radio {}", radius);
+    }
+}
@@ -0,0 +1,139 @@
+// Pruebas del generador de planetas: semillas, océanos, hielo, nubes y sistemas
+// inventados.
+
+use nalgebra_glm::Vec3;
+use space_traver::body::fictional_system;
+use space_traver::color::Color;
+use space_traver::headless::{system_seed, HeadlessOptions};
+use space_traver::planet::{Biome, PlanetGenerator};
+use space_traver::scene::DISTANCE_SCALE;
+
+/// Puntos de la esfera unitaria sin patrón, distintos de los que mide el generador.
+fn sphere_points() -> impl Iterator<Item = Vec3> {
+    (0..3000).map(|i| {
+        let t = i as f32;
+        let y = (t * 0.754_878).fract() * 2.0 - 1.0;
+        let angle = (t * 0.569_840).fract() * std::f32::consts::TAU;
+        let ring = (1.0 - y * y).sqrt();
+        Vec3::new(ring * angle.cos(), y, ring * angle.sin())
+    })
+}
+
+fn distance(a: Color, b: Color) -> f32 {
+    (a.get_r() - b.get_r()).abs() + (a.get_g() - b.get_g()).abs() + (a.get_b() - b.get_b()).abs()
+}
+
+#[test]
+fn the_same_seed_gives_the_same_planet() {
+    let (a, b) = (PlanetGenerator::new(12), PlanetGenerator::new(12));
+    let other = PlanetGenerator::new(13);
+
+    for point in sphere_points().take(200) {
+        assert_eq!(a.color(point, 100), b.color(point, 100));
+    }
+    assert!(sphere_points().any(|point| a.is_ocean(point) != other.is_ocean(point)));
+    assert_eq!(a.seed(), 12);
+}
+
+#[test]
+fn ocean_level_is_the_fraction_of_the_surface_under_water() {
+    for level in [0.0, 0.3, 0.7, 1.0] {
+        let planet = PlanetGenerator::new(21).with_ocean_level(level);
+        let points: Vec<Vec3> = sphere_points().collect();
+        let ocean = points.iter().filter(|&&point| planet.is_ocean(point)).count();
+        let fraction = ocean as f32 / points.len() as f32;
+        assert!((fraction - level).abs() < 0.06, "nivel {}: {}", level, fraction);
+    }
+}
+
+#[test]
+fn ice_caps_cover_the_poles() {
+    let planet = PlanetGenerator::new(3).with_cloud_cover(0.0);
+    let ice = planet.palette.ice;
+    for pole in [Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0)] {
+        assert!(distance(planet.color(pole, 0), ice) < 1e-3);
+        let no_caps = planet.clone().with_ice_caps(90.0);
+        assert!(distance(no_caps.color(pole, 0), ice) > 0.1);
+    }
+    // El ecuador no tiene hielo
+    assert!(distance(planet.color(Vec3::new(1.0, 0.0, 0.0), 0), ice) > 0.1);
+}
+
+#[test]
+fn ninety_degrees_means_no_ice_caps() {
+    // Puntos alrededor de cada polo, a menos de 5° de él
+    let near_poles: Vec<Vec3> = (0..200)
+        .flat_map(|i| {
+            let angle = i as f32 * 0.37;
+            let tilt = (i % 5) as f32 * 0.02;
+            let (x, z) = (tilt * angle.cos(), tilt * angle.sin());
+            let y = (1.0 - tilt * tilt).sqrt();
+            [Vec3::new(x, y, z), Vec3::new(x, -y, z)]
+        })
+        .collect();
+
+    for seed in 0..10 {
+        let planet = PlanetGenerator::new(seed).with_cloud_cover(0.0).with_ice_caps(90.0);
+        // Con el hielo inalcanzable, ni una pizca de hielo en el color
+        let bare = planet.clone().with_ice_caps(1000.0);
+        for &point in &near_poles {
+            assert_eq!(planet.color(point, 0), bare.color(point, 0), "hielo en {:?}", point);
+        }
+    }
+}
+
+#[test]
+fn cloud_cover_hides_the_surface() {
+    let clear = PlanetGenerator::new(8).with_cloud_cover(0.0);
+    let overcast = clear.clone().with_cloud_cover(1.0);
+    let cloud = clear.palette.cloud;
+
+    let cloudy = |planet: &PlanetGenerator| {
+        sphere_points()
+            .filter(|&point| distance(planet.color(point, 0), cloud) < 0.3)
+            .count()
+    };
+    assert!(cloudy(&overcast) > cloudy(&clear) * 3);
+}
+
+#[test]
+fn craters_darken_part_of_the_surface() {
+    let smooth = PlanetGenerator::new(4).with_biome(Biome::Barren).with_cloud_cover(0.0);
+    let cratered = smooth.clone().with_craters(1.0);
+
+    let changed = sphere_points()
+        .filter(|&point| smooth.color(point, 0) != cratered.color(point, 0))
+        .count();
+    assert!(changed > 300, "{} puntos con cráteres", changed);
+}
+
+#[test]
+fn fictional_systems_repeat_with_the_same_seed() {
+    let names = |seed| -> Vec<String> {
+        fictional_system(seed, DISTANCE_SCALE).into_iter().map(|body| body.name).collect()
+    };
+    assert_eq!(names(5), names(5));
+    assert_ne!(names(5), names(6));
+
+    for seed in 0..20 {
+        let bodies = fictional_system(seed, DISTANCE_SCALE);
+        assert!((4..=7).contains(&bodies.len()), "{} cuerpos", bodies.len());
+        assert!(bodies[0].surface.is_none());
+        assert!(bodies[1..].iter().all(|body| body.surface.is_some() && body.has_orbit()));
+        // Cada planeta más lejos que el anterior
+        assert!(bodies[1..].windows(2).all(|pair| pair[0].orbit_radius < pair[1].orbit_radius));
+    }
+}
+
+#[test]
+fn the_window_and_headless_modes_read_the_same_system_seed() {
+    let args = |line: &str| -> Vec<String> { line.split_whitespace().map(String::from).collect() };
+
+    assert_eq!(system_seed(&args("--system 42")), Ok(Some(42)));
+    assert_eq!(system_seed(&args("")), Ok(None));
+    assert!(system_seed(&args("--system")).is_err());
+    assert!(system_seed(&args("--system lejos")).is_err());
+
+    let options = HeadlessOptions::from_args(&args("--headless --system 42")).unwrap().unwrap();
+    assert_eq!(options.system_seed, Some(42));
+}
@@ -0,0 +1,116 @@
+// Pruebas del posprocesado: filtro gaussiano, bloom y pila de efectos.
+
+use nalgebra_glm::{Mat4, Vec3};
+use space_traver::camera::Camera;
+use space_traver::color::Color;
+use space_traver::debug::RenderMode;
+use space_traver::framebuffer::Framebuffer;
+use space_traver::pipeline::{create_uniforms, project_to_screen};
+use space_traver::post::{gaussian_kernel, Bloom, PostEffect, PostStack};
+use space_traver::scene::{Scene, DISTANCE_SCALE};
+
+const SIZE: usize = 32;
+
+/// Imagen gris oscura con un cuadrado de 4x4 muy brillante en el centro.
+fn bright_square() -> Framebuffer {
+    let mut framebuffer = Framebuffer::new(SIZE, SIZE);
+    framebuffer.clear();
+    for pixel in framebuffer.color.iter_mut() {
+        *pixel = Color::rgb(0.2, 0.2, 0.2);
+    }
+    for y in 14..18 {
+        for x in 14..18 {
+            framebuffer.color[y * SIZE + x] = Color::rgb(8.0, 6.0, 2.0);
+        }
+    }
+    framebuffer
+}
+
+fn luminance_at(framebuffer: &Framebuffer, x: usize, y: usize) -> f32 {
+    framebuffer.color[y * SIZE + x].luminance()
+}
+
+#[test]
+fn gaussian_kernel_is_normalized() {
+    for sigma in [0.5, 1.0, 3.0, 7.5] {
+        let kernel = gaussian_kernel(sigma);
+        let total = kernel[0] + 2.0 * kernel[1..].iter().sum::<f32>();
+        assert!((total - 1.0).abs() < 1e-5);
+        assert!(kernel.windows(2).all(|pair| pair[0] >= pair[1]));
+    }
+    assert_eq!(gaussian_kernel(2.0).len(), 7);
+}
+
+#[test]
+fn bloom_spreads_only_what_passes_the_threshold() {
+    let original = bright_square();
+    let mut framebuffer = bright_square();
+    Bloom::new().apply(&mut framebuffer);
+
+    // Alrededor del cuadrado aparece el resplandor, más fuerte cerca
+    let near = luminance_at(&framebuffer, 12, 16) - luminance_at(&original, 12, 16);
+    let far = luminance_at(&framebuffer, 4, 16) - luminance_at(&original, 4, 16);
+    assert!(near > 0.05, "{}", near);
+    assert!(near > far && far >= 0.0);
+    // Y es simétrico
+    let other_side = luminance_at(&framebuffer, 19, 16) - luminance_at(&original, 19, 16);
+    assert!((near - other_side).abs() < near * 0.3);
+
+    // Una imagen sin nada sobre el umbral queda igual
+    let mut dim = Framebuffer::new(SIZE, SIZE);
+    dim.clear();
+    dim.color[5] = Color::rgb(0.9, 0.9, 0.9);
+    let before = dim.color.clone();
+    Bloom::new().apply(&mut dim);
+    assert_eq!(dim.color, before);
+}
+
+#[test]
+fn stack_applies_only_enabled_passes() {
+    let mut stack = PostStack::new();
+    assert_eq!(stack.passes.len(), 1);
+    assert_eq!(stack.passes[0].effect.name(), "Bloom");
+
+    assert_eq!(stack.toggle("Bloom"), Some(false));
+    let mut framebuffer = bright_square();
+    stack.apply(&mut framebuffer);
+    assert_eq!(framebuffer.color, bright_square().color);
+    assert_eq!(stack.toggle("Bloom"), Some(true));
+    assert_eq!(stack.toggle("Viñeta"), None);
+
+    // Dos blooms seguidos resplandecen más que uno
+    let wide = Bloom { sigma: 1.0, ..Bloom::new() };
+    let twice = PostStack::empty().with(PostEffect::Bloom(wide)).with(PostEffect::Bloom(wide));
+    let once = PostStack::empty().with(PostEffect::Bloom(wide));
+    let (mut a, mut b) = (bright_square(), bright_square());
+    twice.apply(&mut a);
+    once.apply(&mut b);
+    assert!(luminance_at(&a, 12, 16) > luminance_at(&b, 12, 16));
+}
+
+#[test]
+fn the_normals_view_skips_post_processing_and_tonemapping() {
+    let mut scene = Scene::load(DISTANCE_SCALE);
+    scene.show_orbits = false;
+    scene.show_trails = false;
+    scene.show_trajectory = false;
+    scene.debug.mode = RenderMode::Normals;
+    let time = 240;
+
+    // El Sol de frente desde +z: en el centro del disco la normal apunta a la cámara
+    let sun = scene.body_bounds(0, time);
+    let eye = sun.center + Vec3::new(0.0, 0.0, sun.radius * 4.0);
+    let camera = Camera::new(eye, sun.center, Vec3::new(0.0, 1.0, 0.0));
+    let mut framebuffer = Framebuffer::new(120, 90);
+    scene.render(&mut framebuffer, &camera, time);
+
+    let uniforms = create_uniforms(&framebuffer, &camera, Mat4::identity(), time);
+    let screen = project_to_screen(&uniforms, sun.center).unwrap();
+    let pixel = framebuffer.buffer[screen.y as usize * framebuffer.width + screen.x as usize];
+    // Deshaciendo el mapeo de `normal_shader` vuelve una normal unitaria; con ACES el
+    // azul de +z quedaría cerca de 200
+    let channel = |shift: u32| ((pixel >> shift) & 0xFF) as f32 / 255.0 * 2.0 - 1.0;
+    let normal = Vec3::new(channel(16), channel(8), channel(0));
+    assert!(pixel & 0xFF >= 250, "pixel {:06X}", pixel);
+    assert!((normal.magnitude() - 1.0).abs() < 0.03, "pixel {:06X}", pixel);
+}
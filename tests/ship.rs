@@ -0,0 +1,85 @@
+// Pruebas del modelo de vuelo de la nave y la cámara de persecución.
+
+use nalgebra_glm::{quat_identity, Vec3};
+use space_traver::camera::Camera;
+use space_traver::chase_camera::ChaseCamera;
+use space_traver::ship::{Ship, ShipInput};
+
+const EPSILON: f32 = 1e-4;
+
+fn ship() -> Ship {
+    Ship::new(Vec3::new(0.0, 0.0, 0.0), quat_identity())
+}
+
+fn thrust_forward() -> ShipInput {
+    ShipInput {
+        thrust: Vec3::new(0.0, 0.0, 1.0),
+        ..ShipInput::default()
+    }
+}
+
+#[test]
+fn ship_keeps_drifting_without_thrust() {
+    let mut ship = ship();
+    ship.update(&thrust_forward(), 1.0);
+    let velocity = ship.velocity;
+    assert!(velocity.z < 0.0, "la proa apunta a -z");
+
+    for _ in 0..10 {
+        ship.update(&ShipInput::default(), 0.5);
+    }
+    assert!((ship.velocity - velocity).magnitude() < EPSILON);
+}
+
+#[test]
+fn brake_stops_the_ship_without_reversing() {
+    let mut ship = ship();
+    ship.update(&thrust_forward(), 1.0);
+    let brake = ShipInput {
+        brake: true,
+        ..ShipInput::default()
+    };
+
+    for _ in 0..100 {
+        ship.update(&brake, 0.1);
+        assert!(ship.velocity.z <= 0.0);
+    }
+    assert_eq!(ship.speed(), 0.0);
+}
+
+#[test]
+fn turning_does_not_change_the_velocity() {
+    let mut ship = ship();
+    ship.update(&thrust_forward(), 1.0);
+    let velocity = ship.velocity;
+    let turn = ShipInput {
+        torque: Vec3::new(0.0, 1.0, 0.0),
+        ..ShipInput::default()
+    };
+
+    for _ in 0..20 {
+        ship.update(&turn, 0.05);
+    }
+    assert!((ship.velocity - velocity).magnitude() < EPSILON);
+    assert!(ship.forward().x < -0.1, "girar a la izquierda lleva la proa hacia -x");
+}
+
+#[test]
+fn chase_camera_keeps_up_with_a_fast_ship() {
+    let mut ship = ship();
+    ship.velocity = Vec3::new(0.0, 0.0, -50.0);
+    let mut chase = ChaseCamera::new();
+    let mut camera = Camera::new(
+        Vec3::new(0.0, 0.0, 1.0),
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    );
+    chase.snap(&mut camera, &ship);
+
+    for _ in 0..30 {
+        ship.update(&ShipInput::default(), 1.0 / 60.0);
+        chase.update(&mut camera, &ship, 1.0 / 60.0);
+    }
+    assert!((camera.eye - chase.target_eye(&ship)).magnitude() < EPSILON);
+    assert!(camera.forward().dot(&ship.forward()) > 0.9);
+}
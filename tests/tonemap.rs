@@ -0,0 +1,76 @@
+// Pruebas del color en luz lineal, el tonemapping y la presentación en sRGB.
+
+use space_traver::color::{linear_to_srgb, srgb_to_linear, Color};
+use space_traver::framebuffer::Framebuffer;
+use space_traver::tonemap::{ToneMapper, ToneMapping};
+
+#[test]
+fn srgb_round_trips_every_byte() {
+    for value in 0..=255u8 {
+        assert_eq!(linear_to_srgb(srgb_to_linear(value)), value);
+    }
+    assert_eq!(Color::from_hex(0x3A7FC8).to_hex(), 0x3A7FC8);
+    // El gris medio de sRGB es bastante más oscuro en luz lineal
+    assert!((srgb_to_linear(128) - 0.2159).abs() < 1e-3);
+}
+
+#[test]
+fn colors_keep_values_above_one() {
+    let white = Color::new(255, 255, 255);
+    let bright = white * 3.0;
+    assert!((bright.get_r() - 3.0).abs() < 1e-6);
+    assert!(((white + white).luminance() - 2.0).abs() < 1e-5);
+    // Recién al codificar se recorta
+    assert_eq!(bright.to_hex(), 0xFFFFFF);
+    assert_eq!(white * -1.0, Color::black());
+}
+
+#[test]
+fn operators_stay_in_range_and_keep_order() {
+    for operator in [ToneMapper::Linear, ToneMapper::Reinhard, ToneMapper::Aces] {
+        let mut previous = operator.apply(0.0);
+        assert!(previous.abs() < 0.01, "{:?}", operator);
+        for step in 1..=200 {
+            let value = operator.apply(step as f32 * 0.1);
+            assert!((0.0..=1.0).contains(&value), "{:?}", operator);
+            assert!(value >= previous, "{:?} no es monótono", operator);
+            previous = value;
+        }
+    }
+    assert_eq!(ToneMapper::Linear.apply(4.0), 1.0);
+    assert!((ToneMapper::Reinhard.apply(1.0) - 0.5).abs() < 1e-6);
+    // Un emisivo al doble del blanco se distingue del blanco
+    assert!(ToneMapper::Aces.apply(2.0) > ToneMapper::Aces.apply(1.0) + 0.05);
+    assert_eq!(ToneMapper::from_name("ACES"), Some(ToneMapper::Aces));
+    assert_eq!(ToneMapper::from_name("lineal"), Some(ToneMapper::Linear));
+    assert_eq!(ToneMapper::from_name("filmic"), None);
+}
+
+#[test]
+fn exposure_doubles_the_light_per_step() {
+    let linear = ToneMapping::linear();
+    let gray = Color::rgb(0.25, 0.25, 0.25);
+    let brighter = linear.with_exposure(1.0).map(gray);
+    assert!((brighter.get_g() - 0.5).abs() < 1e-6);
+    let darker = linear.with_exposure(-2.0).map(gray);
+    assert!((darker.get_b() - 0.0625).abs() < 1e-6);
+}
+
+#[test]
+fn present_encodes_the_hdr_image() {
+    let mut framebuffer = Framebuffer::new(2, 1);
+    framebuffer.clear();
+    framebuffer.set_current_color(Color::rgb(4.0, 0.5, 0.0));
+    framebuffer.point(0, 0, 0.5);
+    // Todavía nada en la imagen final
+    assert_eq!(framebuffer.buffer[0], 0x000000);
+
+    framebuffer.present(&ToneMapping::linear());
+    assert_eq!(framebuffer.buffer[0], Color::rgb(1.0, 0.5, 0.0).to_hex());
+    assert_eq!(framebuffer.buffer[1], 0x000000);
+
+    let reinhard = ToneMapping::new().with_operator(ToneMapper::Reinhard);
+    framebuffer.present(&reinhard);
+    let expected = Color::rgb(0.8, 1.0 / 3.0, 0.0).to_hex();
+    assert_eq!(framebuffer.buffer[0], expected);
+}